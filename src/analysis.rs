@@ -0,0 +1,176 @@
+use std::{collections::VecDeque, fmt};
+use crate::{
+    maze::{Maze, Node, NodeBuffer}, 
+    solve::Solution, 
+};
+
+/// Statistics describing the structure of a maze, and of its solution if given, as printed by `--stats`. 
+#[derive(Clone, Debug, PartialEq)]
+pub struct Analysis {
+    /// Number of nodes with each number of open edges, from zero to four. 
+    pub degrees: [usize; 5], 
+    /// Number of corridors, i.e. paths between dead ends and junctions without branching in between. 
+    pub corridors: usize, 
+    /// Mean length of the corridors, in edges. 
+    pub corridor_length: f64, 
+    /// Length of the longest shortest path between any two nodes, in edges. This is exact for perfect mazes,
+    /// and a lower bound otherwise. 
+    pub diameter: usize, 
+    /// Length of the solution, in edges. 
+    pub solution_length: Option<usize>, 
+    /// Difficulty of the solution (see [`Difficulty::score`]). 
+    pub difficulty: Option<f64>, 
+}
+
+impl Analysis {
+    /// Analyses a maze, and the solution found through it (e.g. via
+    /// [`solve::Phase::solution`](crate::solve::Phase::solution)). 
+    pub fn new(maze: &Maze, solution: Option<&Solution>) -> Analysis {
+        let degree = NodeBuffer::new_from_function(maze, |node| maze.open_neighbours(node).len());
+        let mut degrees = [0; 5];
+        for &d in degree.iter() {
+            degrees[d] += 1;
+        }
+
+        // walk each corridor from its ends until the next node that isn't in the middle of a corridor
+        let (mut corridors, mut length) = (0, 0);
+        for node in maze.nodes_iter().filter(|&node| degree[node] != 2) {
+            for edge in maze.open_neighbours(node) {
+                let (mut prev, mut head) = (node, edge.to);
+                length += 1;
+                while degree[head] == 2 {
+                    let Some(next) = maze.open_neighbours(head).into_iter().find(|e| e.to != prev) else {
+                        break
+                    };
+                    (prev, head) = (head, next.to);
+                    length += 1;
+                }
+                corridors += 1;
+            }
+        }
+        // each corridor was walked from both of its ends
+        let (corridors, length) = (corridors / 2, length / 2);
+        let corridor_length = match corridors {
+            0 => 0.0, 
+            _ => length as f64 / corridors as f64, 
+        };
+
+        let diameter = maze.diameter().len() - 1;
+
+        Analysis {
+            degrees, 
+            corridors, 
+            corridor_length, 
+            diameter, 
+            solution_length: solution.map(|solution| solution.path.len() - 1), 
+            difficulty: solution.map(|solution| Difficulty::new(maze, solution).score()), 
+        }
+    }
+
+    /// Number of nodes with a single open edge. 
+    pub fn dead_ends(&self) -> usize {
+        self.degrees[1]
+    }
+
+    /// Number of nodes where three corridors meet. 
+    pub fn t_junctions(&self) -> usize {
+        self.degrees[3]
+    }
+
+    /// Number of nodes where four corridors meet. 
+    pub fn x_junctions(&self) -> usize {
+        self.degrees[4]
+    }
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let branching: Vec<String> = self.degrees
+            .iter()
+            .enumerate()
+            .map(|(degree, count)| format!("{degree}: {count}"))
+            .collect();
+        writeln!(f, "dead ends:        {}", self.dead_ends())?;
+        writeln!(f, "junctions:        {} T, {} X", self.t_junctions(), self.x_junctions())?;
+        writeln!(f, "branching:        {}", branching.join(", "))?;
+        writeln!(f, "corridors:        {} (mean length {:.2})", self.corridors, self.corridor_length)?;
+        writeln!(f, "diameter:         {}", self.diameter)?;
+        if let Some(length) = self.solution_length {
+            let ratio = length as f64 / self.diameter.max(1) as f64;
+            writeln!(f, "solution length:  {length} ({:.0}% of the diameter)", 100.0 * ratio)?;
+        }
+        if let Some(difficulty) = self.difficulty {
+            writeln!(f, "difficulty:       {difficulty:.0}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Components of how hard it is to find the solution through a maze, combined by [`Difficulty::score`]. 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Difficulty {
+    /// Length of the solution, in edges. 
+    pub length: usize, 
+    /// Sum of the depths of all branches leaving the solution, in edges, i.e. how far each wrong turn leads
+    /// before it dead-ends. 
+    pub dead_end_depth: usize, 
+    /// Number of junctions along the solution. 
+    pub junctions: usize, 
+    /// Number of nodes in the maze. 
+    pub nodes: usize, 
+}
+
+impl Difficulty {
+    /// Measures the difficulty of a solution through a maze. 
+    pub fn new(maze: &Maze, solution: &Solution) -> Difficulty {
+        let mut on_path = NodeBuffer::new_with_values(maze, false);
+        for &node in &solution.path {
+            on_path[node] = true;
+        }
+
+        // search outward from the whole path at once, tracking which branch each node was reached through
+        let mut distances: NodeBuffer<Option<usize>> = NodeBuffer::new(maze);
+        let mut branches: NodeBuffer<Option<Node>> = NodeBuffer::new(maze);
+        let mut depths = NodeBuffer::new_with_values(maze, 0);
+        let mut queue = VecDeque::new();
+        for &node in &solution.path {
+            distances[node] = Some(0);
+            queue.push_back(node);
+        }
+        while let Some(node) = queue.pop_front() {
+            let distance = distances[node].expect("queued nodes have a distance");
+            for edge in maze.open_neighbours(node) {
+                if distances[edge.to].is_some() {
+                    continue
+                }
+                let branch = match on_path[node] {
+                    true => edge.to, 
+                    false => branches[node].expect("nodes off the path are reached through a branch"), 
+                };
+                distances[edge.to] = Some(distance + 1);
+                branches[edge.to] = Some(branch);
+                depths[branch] = usize::max(depths[branch], distance + 1);
+                queue.push_back(edge.to);
+            }
+        }
+
+        let junctions = solution.path
+            .iter()
+            .filter(|&&node| maze.open_neighbours(node).len() > 2)
+            .count();
+        Difficulty {
+            length: solution.path.len() - 1, 
+            dead_end_depth: depths.iter().sum(), 
+            junctions, 
+            nodes: maze.width * maze.height, 
+        }
+    }
+
+    /// Combines the components into a single score, scaled by the size of the maze such that it's within
+    /// `0..=100` for perfect mazes of any size. In those, the path and the branches leaving it together span
+    /// at most all nodes, and each node along the path is at most one junction. 
+    pub fn score(&self) -> f64 {
+        let total = self.length + self.dead_end_depth + self.junctions;
+        50.0 * total as f64 / self.nodes as f64
+    }
+}
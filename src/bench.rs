@@ -0,0 +1,221 @@
+use std::{
+    io::{self, Write}, 
+    time::{Duration, Instant}, 
+};
+use clap::ValueEnum;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use crate::{
+    generate::{self, Generator}, 
+    maze::Maze, 
+    solve::{self, Solver}, 
+    Error, Settings, 
+};
+
+/// Measurements of a single headless run of a generator and a solver. 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Measurement {
+    /// Timesteps taken by the generator. 
+    pub generate_steps: usize, 
+    /// Wall time taken by the generator. 
+    pub generate_time: Duration, 
+    /// Timesteps taken by the solver. 
+    pub solve_steps: usize, 
+    /// Wall time taken by the solver. 
+    pub solve_time: Duration, 
+    /// Number of nodes visited by the solver. 
+    pub visited: usize, 
+    /// Number of nodes along the solution, including the start and the goal. 
+    pub path_length: usize, 
+}
+
+impl Measurement {
+    /// Generates a maze of given dimensions and solves it without rendering, measuring both phases. The maze
+    /// is generated in tiles in parallel if `parallel` is set (see [`generate::run_parallel`]), and fails
+    /// only if it fails verification (see [`generate::verify`]). 
+    pub fn run(
+        generator: Generator, 
+        solver: Solver, 
+        parallel: bool, 
+        (width, height): (u16, u16), 
+        settings: Settings, 
+    ) -> Result<Measurement, Error> {
+        let mut state = generate::state(Maze::new(width, height), settings);
+        let start = Instant::now();
+        let generate_steps = match parallel {
+            true => generate::run_parallel(generator, &mut state), 
+            false => generator.run_headless(&mut state), 
+        };
+        let generate_time = start.elapsed();
+        generate::verify(&state)?;
+
+        let mut state = solve::state(state);
+        let start = Instant::now();
+        let solve_steps = solver.run_headless(&mut state);
+        let solve_time = start.elapsed();
+
+        Ok(Measurement {
+            generate_steps, 
+            generate_time, 
+            solve_steps, 
+            solve_time, 
+            visited: state.visited_count, 
+            path_length: state.phase.solution().map_or(0, |solution| solution.path.len()), 
+        })
+    }
+}
+
+/// Aggregation of the measurements of several runs of the same generator and solver. 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Stats {
+    /// Number of runs measured. 
+    pub runs: u32, 
+    /// Sum of all measurements. 
+    total: Measurement, 
+}
+
+impl Stats {
+    /// Adds the measurement of a run. 
+    pub fn add(&mut self, run: Measurement) {
+        let total = &mut self.total;
+        total.generate_steps += run.generate_steps;
+        total.generate_time += run.generate_time;
+        total.solve_steps += run.solve_steps;
+        total.solve_time += run.solve_time;
+        total.visited += run.visited;
+        total.path_length += run.path_length;
+        self.runs += 1;
+    }
+
+    /// Gets the mean of all measurements, or all zeroes if there are none. 
+    pub fn mean(&self) -> Measurement {
+        if self.runs == 0 {
+            return Measurement::default()
+        }
+        let (total, runs) = (self.total, self.runs);
+        Measurement {
+            generate_steps: total.generate_steps / runs as usize, 
+            generate_time: total.generate_time / runs, 
+            solve_steps: total.solve_steps / runs as usize, 
+            solve_time: total.solve_time / runs, 
+            visited: total.visited / runs as usize, 
+            path_length: total.path_length / runs as usize, 
+        }
+    }
+}
+
+/// Measurements of a single run along with what was run, as written by [`write_records`]. The field names
+/// (see [`Record::FIELDS`]) are kept stable, such that reports may be processed by external tools. 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Record {
+    /// Seed the maze was generated and solved with. 
+    pub seed: u64, 
+    /// Name of the generator. 
+    pub generator: &'static str, 
+    /// Name of the solver. 
+    pub solver: &'static str, 
+    /// Width of the maze in nodes. 
+    pub width: u16, 
+    /// Height of the maze in nodes. 
+    pub height: u16, 
+    /// Timesteps taken by the generator. 
+    pub generate_steps: usize, 
+    /// Wall time taken by the generator, in microseconds. 
+    pub generate_time_us: u64, 
+    /// Timesteps taken by the solver. 
+    pub solve_steps: usize, 
+    /// Wall time taken by the solver, in microseconds. 
+    pub solve_time_us: u64, 
+    /// Number of nodes visited by the solver. 
+    pub visited: usize, 
+    /// Number of nodes along the solution, including the start and the goal. 
+    pub path_length: usize, 
+}
+
+impl Record {
+    /// Names of the fields, in the order of the columns of the csv format. 
+    pub const FIELDS: [&str; 11] = [
+        "seed", 
+        "generator", 
+        "solver", 
+        "width", 
+        "height", 
+        "generate_steps", 
+        "generate_time_us", 
+        "solve_steps", 
+        "solve_time_us", 
+        "visited", 
+        "path_length", 
+    ];
+
+    /// Records the measurement of a run. 
+    pub fn new(
+        seed: u64, 
+        generator: Generator, 
+        solver: Solver, 
+        (width, height): (u16, u16), 
+        run: Measurement, 
+    ) -> Record {
+        let micros = |time: Duration| u64::try_from(time.as_micros()).unwrap_or(u64::MAX);
+        Record {
+            seed, 
+            generator: generator.name(), 
+            solver: solver.name(), 
+            width, 
+            height, 
+            generate_steps: run.generate_steps, 
+            generate_time_us: micros(run.generate_time), 
+            solve_steps: run.solve_steps, 
+            solve_time_us: micros(run.solve_time), 
+            visited: run.visited, 
+            path_length: run.path_length, 
+        }
+    }
+
+    /// Gets the values of the fields, in the order of [`Record::FIELDS`]. 
+    fn values(&self) -> [String; 11] {
+        [
+            self.seed.to_string(), 
+            self.generator.to_owned(), 
+            self.solver.to_owned(), 
+            self.width.to_string(), 
+            self.height.to_string(), 
+            self.generate_steps.to_string(), 
+            self.generate_time_us.to_string(), 
+            self.solve_steps.to_string(), 
+            self.solve_time_us.to_string(), 
+            self.visited.to_string(), 
+            self.path_length.to_string(), 
+        ]
+    }
+}
+
+/// Machine-readable format of benchmark records, as given by `--format`. 
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordFormat {
+    /// A header naming the fields, followed by a line of comma-separated values for each run. 
+    Csv, 
+    /// An array of objects, one for each run. 
+    #[cfg(feature = "serde")]
+    Json, 
+}
+
+/// Writes the records of all runs in a machine-readable format. 
+pub fn write_records(mut out: impl Write, format: RecordFormat, records: &[Record]) -> io::Result<()> {
+    match format {
+        RecordFormat::Csv => {
+            // names of generators and solvers don't contain commas or quotes, so nothing needs escaping
+            writeln!(out, "{}", Record::FIELDS.join(","))?;
+            for record in records {
+                writeln!(out, "{}", record.values().join(","))?;
+            }
+        }, 
+        #[cfg(feature = "serde")]
+        RecordFormat::Json => {
+            serde_json::to_writer_pretty(&mut out, records)?;
+            writeln!(out)?;
+        }, 
+    }
+    out.flush()
+}
@@ -0,0 +1,78 @@
+//! Capture of the frames written to the terminal as a raw stream of escape sequences, such that writing the
+//! stream to a terminal (e.g. with `cat`) draws the frames of the run one after the other, ending with the
+//! final one. 
+//!
+//! The delay between frames may be embedded in the stream too, as application program commands which
+//! terminals ignore, such that a player (see [`chunks`]) may play the frames back with their timing. 
+
+use std::{
+    io::{self, Write}, 
+    sync::{Arc, Mutex}, 
+    time::{Duration, Instant}, 
+};
+
+/// Start of a delay embedded in the stream, followed by the delay in microseconds and [`END`]. 
+const DELAY: &str = "\x1b_delay ";
+/// End of a delay embedded in the stream (i.e. the string terminator). 
+const END: &str = "\x1b\\";
+
+/// Writer capturing the frames written to the terminal, shared by every animation of a run (see
+/// [`Settings::capture`](crate::Settings::capture)). 
+#[derive(Clone)]
+pub struct Capture(Arc<Mutex<Stream>>);
+
+struct Stream {
+    writer: Box<dyn Write + Send>, 
+    /// Whether the delay between frames is embedded before each one. 
+    timing: bool, 
+    /// When the latest frame was captured. 
+    latest: Option<Instant>, 
+}
+
+impl Capture {
+    /// Constructs a capture writing to a writer (e.g. a file), embedding the delay between frames if `timing`
+    /// is set. 
+    pub fn new(writer: impl Write + Send + 'static, timing: bool) -> Capture {
+        Capture(Arc::new(Mutex::new(Stream{ writer: Box::new(writer), timing, latest: None })))
+    }
+
+    /// Writes a frame, preceded by the delay since the previous one (if any) if timing is embedded. 
+    pub fn frame(&self, frame: &str) -> io::Result<()> {
+        let mut stream = self.0.lock().expect("capture poisoned");
+        let now = Instant::now();
+        if let Some(latest) = stream.latest.replace(now) && stream.timing {
+            let delay = now.duration_since(latest).as_micros();
+            write!(stream.writer, "{DELAY}{delay}{END}")?;
+        }
+        stream.writer.write_all(frame.as_bytes())
+    }
+
+    /// Ends the stream on the line below the final frame, such that a shell prompt shown after writing it
+    /// doesn't cover the frame. 
+    pub fn finish(&self) -> io::Result<()> {
+        let mut stream = self.0.lock().expect("capture poisoned");
+        stream.writer.write_all(b"\r\n")?;
+        stream.writer.flush()
+    }
+}
+
+/// Splits a captured stream into the text written after each delay embedded in it, each with that delay
+/// (the first with none). A malformed delay ends the splitting, leaving the rest of the stream as is. 
+pub fn chunks(stream: &str) -> impl Iterator<Item = (Duration, &str)> {
+    let (mut rest, mut delay) = (Some(stream), Duration::ZERO);
+    std::iter::from_fn(move || {
+        let text = rest.take()?;
+        let Some((chunk, after)) = text.split_once(DELAY) else {
+            return Some((delay, text))
+        };
+        let Some((micros, after)) = after.split_once(END) else {
+            return Some((delay, text))
+        };
+        let Ok(micros) = micros.parse() else {
+            return Some((delay, text))
+        };
+        let chunk = (delay, chunk);
+        (rest, delay) = (Some(after), Duration::from_micros(micros));
+        Some(chunk)
+    })
+}
@@ -0,0 +1,84 @@
+use std::{collections::VecDeque, marker::PhantomData};
+use crate::{
+    maze::{Node, NodeBuffer}, 
+    state::{Phase, State}, 
+    Animation, Error, Signal, 
+};
+
+/// Number of timesteps the ring takes to expand across all visited nodes. 
+const STEPS: usize = 40;
+
+/// Plays an animation celebrating the goal being reached, as a ring of young colour expanding from the goal
+/// through the visited nodes. Does nothing if the goal hasn't been reached. 
+pub fn ring<T: Phase>(state: &mut State<T>) -> Result<(), Error> {
+    if state.phase.goal().is_some_and(|goal| state.is_visited(goal)) {
+        Ring::<T>::run(state)?;
+    }
+    Ok(())
+}
+
+/// Animation relighting the visited nodes in bands of increasing distance from the goal. Nodes are relit
+/// without being reported as visited again, so the hooks don't observe the celebration. 
+struct Ring<T> {
+    /// Nodes of each band, from the goal outward. 
+    bands: VecDeque<Vec<Node>>, 
+    _phase: PhantomData<T>, 
+}
+
+impl<T: Phase> Animation for Ring<T> {
+    type Phase = T;
+
+    fn new(state: &mut State<T>) -> Self {
+        let goal = state.phase.goal().expect("only celebrating reached goals");
+        let mut distances: NodeBuffer<Option<usize>> = NodeBuffer::new(&state.maze);
+        let mut queue = VecDeque::from([goal]);
+        let mut order = Vec::new();
+        distances[goal] = Some(0);
+
+        // search outward from the goal through the visited nodes only, since the others aren't drawn lit
+        while let Some(node) = queue.pop_front() {
+            let distance = distances[node].expect("queued nodes have a distance");
+            order.push((node, distance));
+            for edge in state.maze.open_neighbours(node) {
+                if distances[edge.to].is_none() && state.is_visited(edge.to) {
+                    distances[edge.to] = Some(distance + 1);
+                    queue.push_back(edge.to);
+                }
+            }
+        }
+
+        // nodes are found in order of distance, so the bands are consecutive runs of them
+        let farthest = order.last().map_or(0, |&(_, distance)| distance);
+        let width = (farthest + 1).div_ceil(STEPS);
+        let mut bands = VecDeque::new();
+        for (node, distance) in order {
+            if bands.len() <= distance / width {
+                bands.push_back(Vec::new());
+            }
+            bands.back_mut().expect("a band was just pushed").push(node);
+        }
+        Ring {
+            bands, 
+            _phase: PhantomData, 
+        }
+    }
+
+    fn step(&mut self, state: &mut State<T>) -> Signal {
+        let Some(band) = self.bands.pop_front() else {
+            return Signal::Done
+        };
+        for node in band {
+            state.visited_at.set(node, state.time);
+        }
+        Signal::Continue
+    }
+
+    fn steps_per_frame(&self, _state: &State<T>) -> u32 {
+        // celebrations are timed independently of the algorithms
+        1
+    }
+
+    fn finishes_phase(&self) -> bool {
+        false
+    }
+}
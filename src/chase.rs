@@ -0,0 +1,76 @@
+use std::mem;
+use crate::{
+    generate::{self, Generator}, 
+    maze::{Node, NodeBits}, 
+    Animation, Signal, Stepper, 
+};
+
+/// Searches the maze breadth-first from its top left to its bottom right corner while it's being generated,
+/// passing only edges already carved, such that the search chases the generator around the maze. Nodes
+/// reached are drawn as special (see [`generate::Phase::chased`]). 
+///
+/// Generators and solvers otherwise run one phase after the other, so the chase steps the generator itself
+/// and searches within the generate phase. The search is always breadth-first, since the solvers can't
+/// wait for edges to be carved. 
+pub struct Chase {
+    /// Generator being chased, until it's done. 
+    generator: Option<Stepper<generate::Phase>>, 
+    /// Reached nodes which may still lead to unreached ones, since edges next to them may yet be carved. 
+    boundary: Vec<Node>, 
+    goal: Node, 
+}
+
+impl Chase {
+    /// Constructs a chase of a generator, which is stepped along with the search. 
+    pub fn new_with(generator: Generator, state: &mut generate::State) -> Chase {
+        let chase = Chase::new(state);
+        Chase{ generator: Some(generator.stepper(state)), ..chase }
+    }
+}
+
+impl Animation for Chase {
+    type Phase = generate::Phase;
+
+    /// Constructs a search through the maze as it is, i.e. without a generator to chase. 
+    fn new(state: &mut generate::State) -> Chase {
+        let (start, goal) = state.maze.bounds();
+        let mut chased = NodeBits::new(&state.maze);
+        chased.set(start, true);
+        state.phase.chased = Some(chased);
+        Chase {
+            generator: None, 
+            boundary: vec![start], 
+            goal, 
+        }
+    }
+
+    fn step(&mut self, state: &mut generate::State) -> Signal {
+        if let Some(generator) = &mut self.generator && let Signal::Done = generator(state) {
+            self.generator = None;
+        }
+
+        let chased = state.phase.chased.as_mut().expect("chased nodes are set up on construction");
+        if !chased[self.goal] {
+            let mut boundary = Vec::new();
+            for node in mem::take(&mut self.boundary) {
+                for edge in state.maze.open_neighbours(node) {
+                    if !chased[edge.to] {
+                        chased.set(edge.to, true);
+                        boundary.push(edge.to);
+                    }
+                }
+                if !state.maze.neighbours(node).filter(|n| !chased[n]).is_empty() {
+                    boundary.push(node);
+                }
+            }
+            self.boundary = boundary;
+        }
+
+        // keep going until the generator is done, and the goal is reached or there's nowhere left to go
+        let caught = chased[self.goal] || self.boundary.is_empty();
+        match self.generator.is_none() && caught {
+            true => Signal::Done, 
+            false => Signal::Continue, 
+        }
+    }
+}
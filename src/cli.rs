@@ -0,0 +1,1047 @@
+use std::{ffi::OsString, path::PathBuf, str::FromStr};
+use clap::{
+    builder::{PossibleValuesParser, TypedValueParser}, 
+    ArgAction, Args, CommandFactory, FromArgMatches, Parser, Subcommand, 
+};
+use unicode_width::UnicodeWidthStr;
+use overlook::{
+    bench::RecordFormat, 
+    code::RunCode, 
+    colour::{Easing, Wall, CELL}, 
+    export::{Format, Output, SolutionFormat, SolutionOutput, Target}, 
+    fade::FadeStyle, 
+    options::Options, 
+    generate::{Generator, GENERATORS}, 
+    maze::Node, 
+    net, 
+    outro::Outro, 
+    registry::{Entry, Registry}, 
+    render::{Glyphs, Layout}, 
+    solve::{Endpoints, Solver, Sources, SOLVERS}, 
+    state::Phase, 
+    Error, StepsPerFrame, 
+    themes::{PaletteOverride, ThemeName}, 
+    walk::WalkStyle, 
+};
+
+#[derive(Parser)]
+#[clap(disable_help_flag = true, args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>, 
+
+    // running without a subcommand is shorthand for `overlook run`
+    #[command(flatten)]
+    pub run: RunArgs, 
+}
+
+impl Cli {
+    /// Parses the command from the process arguments, exiting on failure. 
+    ///
+    /// We can't use [`Parser::parse`] since the run arguments are required, but only when no subcommand is
+    /// given. 
+    pub fn parse_command() -> Command {
+        Cli::parse_command_from(std::env::args_os()).unwrap_or_else(|e| e.exit())
+    }
+
+    /// Parses the command from given arguments, including the program name. 
+    pub fn parse_command_from<I, T>(args: I) -> Result<Command, clap::Error>
+    where
+        I: IntoIterator<Item = T>, 
+        T: Into<OsString> + Clone, 
+    {
+        let matches = Cli::command().try_get_matches_from(args)?;
+        match matches.subcommand() {
+            Some(_) => Command::from_arg_matches(&matches), 
+            None => RunArgs::from_arg_matches(&matches).map(|run| Command::Run(Box::new(run))), 
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Generates and solves a maze, animated in the terminal. 
+    // boxed since the run arguments are much larger than those of the other subcommands
+    Run(Box<RunArgs>), 
+    /// Generates a maze without animating it and writes it to a file or stdout. 
+    Generate(GenerateArgs), 
+    /// Solves a maze loaded from a file, animated in the terminal. 
+    // boxed for the same reason, as they share the animation arguments
+    Solve(Box<SolveArgs>), 
+    /// Runs a generator and solver repeatedly without animating them and prints timing statistics. 
+    Bench(BenchArgs), 
+    /// Generates a maze and solves it with each solver, printing a table comparing them. 
+    Compare(CompareArgs), 
+    /// Generates and solves a maze without animating it and writes the result to a file or stdout. 
+    Export(ExportArgs), 
+    /// Plays back a run recorded with `--record-run`. 
+    Replay(ReplayArgs), 
+    /// Plays back the frames of a run written by `--record-ansi`, pausing for the delays between them. 
+    Play(PlayArgs), 
+    /// Generates a maze and races a solver through it, steering from the start to the goal with the arrow keys. 
+    Race(RaceArgs), 
+    /// Generates a maze and races two players through copies of it side by side, steering with WASD and the
+    /// arrow keys. 
+    Duel(DuelArgs), 
+    /// Generates a maze and waits for another instance to join (see `join`), racing them through it over the
+    /// network. 
+    Host(HostArgs), 
+    /// Joins a race hosted by another instance (see `host`) through the maze it offers. 
+    Join(JoinArgs), 
+    /// Generates two mazes of the same size side by side with two generators from the same seed, animated in
+    /// lockstep, and prints a table comparing them. 
+    Versus(VersusArgs), 
+    /// Presents a series of mazes of increasing size to solve with the arrow keys, timing and scoring each. 
+    Challenge(ChallengeArgs), 
+    /// Prints a shell completion script to stdout. 
+    Completions {
+        shell: clap_complete::Shell, 
+    }, 
+    /// Prints a man page in roff format to stdout. 
+    Manpage, 
+}
+
+#[derive(Args)]
+#[command(disable_help_flag = true)]
+pub struct RunArgs {
+    #[command(flatten)]
+    pub maze: MazeArgs, 
+
+    #[arg(long, short, value_parser = generator_parser())]
+    #[arg(required_unless_present_any = ["load", "code", "looping"])]
+    pub generator: Option<Generator>, 
+
+    #[command(flatten)]
+    pub difficulty: DifficultyArgs, 
+
+    #[arg(long, short, value_parser = solver_parser(), required_unless_present_any = ["code", "looping"])]
+    pub solver: Option<Solver>, 
+
+    /// Writes the generated maze to a file before solving it, with the format inferred from its extension. 
+    #[arg(long, value_name = "FILE")]
+    pub save: Option<Target>, 
+
+    /// Loads a maze from a json or txt file (as written by `--save`, or drawn by hand) and solves it instead
+    /// of generating one. Use `-` for stdin. 
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["generator", "width", "height", "size", "save"])]
+    #[arg(conflicts_with = "min_difficulty")]
+    pub load: Option<PathBuf>, 
+
+    /// Chases the generator with a breadth-first search from the top left corner to the bottom right one,
+    /// which may only pass edges already carved. 
+    #[arg(long, conflicts_with = "load")]
+    pub chase: bool, 
+
+    /// Records the arguments, seed, and every decision of the algorithms to a file once finished, to be
+    /// played back by `overlook replay`. 
+    #[arg(long, value_name = "FILE", conflicts_with = "load")]
+    pub record_run: Option<PathBuf>, 
+
+    /// Reproduces a run from the code printed in its summary, which gives the maze size, generator, solver,
+    /// and seed. 
+    #[arg(long, conflicts_with_all = ["generator", "solver", "width", "height", "size", "seed", "daily", "load"])]
+    #[arg(conflicts_with = "min_difficulty")]
+    pub code: Option<RunCode>, 
+
+    /// Runs mazes one after the other until a key is pressed, like a screensaver, each with a new seed (derived
+    /// from the given one) and the generator and solver chosen at random unless given. 
+    #[arg(long = "loop", conflicts_with_all = ["load", "code", "record_run"])]
+    pub looping: bool, 
+
+    /// Relative probabilities of choosing each generator and solver with `--loop`, as NAME=WEIGHT separated by
+    /// commas (e.g. `dfs=3,wilson=1`). Algorithms not listed weigh 1. 
+    #[arg(long, requires = "looping")]
+    pub weights: Option<Weights>, 
+
+    #[command(flatten)]
+    pub animation: AnimationArgs, 
+
+    #[command(flatten)]
+    pub help: Help, 
+}
+
+impl RunArgs {
+    /// Fills in the maze size, generator, solver, and seed from the run code, if given. 
+    pub fn apply_code(&mut self) {
+        let Some(code) = self.code else {
+            return
+        };
+        self.maze.size = Some(Size(code.width, code.height));
+        self.maze.seed = Some(code.seed);
+        self.generator = Some(code.generator);
+        self.solver = Some(code.solver);
+    }
+}
+
+#[derive(Args)]
+#[command(disable_help_flag = true)]
+pub struct GenerateArgs {
+    #[command(flatten)]
+    pub maze: MazeArgs, 
+
+    #[arg(long, short, value_parser = generator_parser())]
+    pub generator: Generator, 
+
+    #[command(flatten)]
+    pub tiling: ParallelArgs, 
+
+    #[command(flatten)]
+    pub difficulty: DifficultyArgs, 
+
+    #[command(flatten)]
+    pub output: OutputArgs, 
+
+    #[command(flatten)]
+    pub analysis: StatsArgs, 
+
+    #[command(flatten)]
+    pub help: Help, 
+}
+
+#[derive(Args)]
+#[command(disable_help_flag = true)]
+pub struct SolveArgs {
+    /// Maze file to solve, as written by `overlook generate` (or drawn by hand, see `--load`). Use `-` for
+    /// stdin, with the format detected from its contents. 
+    pub file: PathBuf, 
+
+    #[arg(long, short, value_parser = solver_parser())]
+    pub solver: Solver, 
+
+    /// Node to solve from, as X:Y, overriding the start marked in the file (if any) and `--endpoints`. 
+    #[arg(long, value_name = "X:Y", value_parser = node)]
+    pub start: Option<Node>, 
+
+    /// Node to solve to, as X:Y, overriding the goal marked in the file (if any) and `--endpoints`. 
+    #[arg(long, value_name = "X:Y", value_parser = node)]
+    pub goal: Option<Node>, 
+
+    /// Seed for the random number generator, such that randomised solvers behave the same each time. Defaults
+    /// to a random seed. 
+    #[arg(long)]
+    pub seed: Option<u64>, 
+
+    #[command(flatten)]
+    pub animation: AnimationArgs, 
+
+    #[command(flatten)]
+    pub help: Help, 
+}
+
+#[derive(Args)]
+#[command(disable_help_flag = true)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    pub maze: MazeArgs, 
+
+    /// Generators to compare, as a comma-separated list or all. 
+    #[arg(long, short, visible_alias = "generator", value_name = "GENERATORS", default_value = "all")]
+    #[arg(value_delimiter = ',', value_parser = selection_parser(&GENERATORS))]
+    pub generators: Vec<String>, 
+
+    #[command(flatten)]
+    pub tiling: ParallelArgs, 
+
+    /// Solvers to compare, as a comma-separated list or all. 
+    #[arg(long, short, visible_alias = "solver", value_name = "SOLVERS", default_value = "all")]
+    #[arg(value_delimiter = ',', value_parser = selection_parser(&SOLVERS))]
+    pub solvers: Vec<String>, 
+
+    /// Number of mazes to generate and solve for each combination of generator and solver. 
+    #[arg(long, short, default_value = "10")]
+    #[arg(value_parser = clap::value_parser!(u32).range(1..))]
+    pub runs: u32, 
+
+    /// Prints a record of each run in a machine-readable format instead of a table of the means. 
+    #[arg(long, value_name = "FORMAT")]
+    pub format: Option<RecordFormat>, 
+
+    #[command(flatten)]
+    pub help: Help, 
+}
+
+#[derive(Args)]
+#[command(disable_help_flag = true)]
+pub struct CompareArgs {
+    #[command(flatten)]
+    pub maze: MazeArgs, 
+
+    #[arg(long, short, value_parser = generator_parser())]
+    pub generator: Generator, 
+
+    /// Solvers to compare, as a comma-separated list or all. 
+    #[arg(long, short, visible_alias = "solver", value_name = "SOLVERS", default_value = "all")]
+    #[arg(value_delimiter = ',', value_parser = selection_parser(&SOLVERS))]
+    pub solvers: Vec<String>, 
+
+    /// Animates each solver in turn, instead of solving without animating. 
+    #[arg(long)]
+    pub animate: bool, 
+
+    /// Animation timestep. 
+    #[arg(long, short, default_value="60ms")]
+    pub delay: humantime::Duration, 
+
+    #[command(flatten)]
+    pub colours: ColourArgs, 
+
+    #[command(flatten)]
+    pub help: Help, 
+}
+
+impl CompareArgs {
+    /// Gets the selected solvers. 
+    pub fn solvers(&self) -> Vec<Solver> {
+        select(&SOLVERS, &self.solvers)
+    }
+}
+
+#[derive(Args)]
+#[command(disable_help_flag = true)]
+pub struct RaceArgs {
+    #[command(flatten)]
+    pub maze: MazeArgs, 
+
+    #[arg(long, short, value_parser = generator_parser(), required_unless_present = "ghost")]
+    pub generator: Option<Generator>, 
+
+    /// Solver racing the player, which takes one step of its search per timestep. 
+    #[arg(long, short, value_parser = solver_parser())]
+    pub solver: Solver, 
+
+    /// Animation timestep, i.e. how quickly the solver searches. 
+    #[arg(long, short, default_value="150ms")]
+    pub delay: humantime::Duration, 
+
+    /// Races a ghost too, replaying the moves of a race recorded with `--record-run` (or the search of a run
+    /// recorded with `overlook run --record-run`) through the same maze, at the timesteps they were made. 
+    #[arg(long, value_name = "FILE")]
+    #[arg(conflicts_with_all = ["generator", "width", "height", "size", "seed", "daily"])]
+    pub ghost: Option<PathBuf>, 
+
+    /// Records the maze and the moves made to a file once finished, to be raced as a ghost with `--ghost`. 
+    #[arg(long, value_name = "FILE")]
+    pub record_run: Option<PathBuf>, 
+
+    #[command(flatten)]
+    pub colours: ColourArgs, 
+
+    #[command(flatten)]
+    pub help: Help, 
+}
+
+#[derive(Args)]
+#[command(disable_help_flag = true)]
+pub struct DuelArgs {
+    // the dimensions are those of each copy, which fill half the terminal unless given
+    #[command(flatten)]
+    pub maze: MazeArgs, 
+
+    #[arg(long, short, value_parser = generator_parser())]
+    pub generator: Generator, 
+
+    #[command(flatten)]
+    pub colours: ColourArgs, 
+
+    #[command(flatten)]
+    pub help: Help, 
+}
+
+#[derive(Args)]
+#[command(disable_help_flag = true)]
+pub struct HostArgs {
+    // the dimensions fill the terminal of the host, which needs to fit that of the instance joining
+    #[command(flatten)]
+    pub maze: MazeArgs, 
+
+    #[arg(long, short, value_parser = generator_parser())]
+    pub generator: Generator, 
+
+    /// Port to wait for the other instance on. 
+    #[arg(long, short, default_value_t = net::DEFAULT_PORT)]
+    pub port: u16, 
+
+    #[command(flatten)]
+    pub colours: ColourArgs, 
+
+    #[command(flatten)]
+    pub help: Help, 
+}
+
+#[derive(Args)]
+#[command(disable_help_flag = true)]
+pub struct JoinArgs {
+    /// Address of the instance hosting the race, with the port defaulting to that hosted on by default. 
+    #[arg(value_name = "HOST[:PORT]")]
+    pub address: String, 
+
+    #[command(flatten)]
+    pub colours: ColourArgs, 
+
+    #[command(flatten)]
+    pub help: Help, 
+}
+
+#[derive(Args)]
+#[command(disable_help_flag = true)]
+pub struct VersusArgs {
+    // the dimensions are those of each maze, which fill half the terminal unless given
+    #[command(flatten)]
+    pub maze: MazeArgs, 
+
+    /// Generator of the maze on the left. 
+    #[arg(value_parser = generator_parser())]
+    pub left: Generator, 
+
+    /// Generator of the maze on the right. 
+    #[arg(value_parser = generator_parser())]
+    pub right: Generator, 
+
+    /// Animation timestep. 
+    #[arg(long, short, default_value="30ms")]
+    pub delay: humantime::Duration, 
+
+    #[command(flatten)]
+    pub colours: ColourArgs, 
+
+    #[command(flatten)]
+    pub help: Help, 
+}
+
+#[derive(Args)]
+#[command(disable_help_flag = true)]
+pub struct ChallengeArgs {
+    // the dimensions are those of the last maze, with the earlier ones scaled down
+    #[command(flatten)]
+    pub maze: MazeArgs, 
+
+    #[arg(long, short, value_parser = generator_parser())]
+    pub generator: Generator, 
+
+    /// Number of mazes to solve, each larger than the one before. 
+    #[arg(long, short, default_value = "5")]
+    #[arg(value_parser = clap::value_parser!(u32).range(1..))]
+    pub rounds: u32, 
+
+    #[command(flatten)]
+    pub colours: ColourArgs, 
+
+    #[command(flatten)]
+    pub help: Help, 
+}
+
+#[derive(Args)]
+#[command(disable_help_flag = true)]
+pub struct ReplayArgs {
+    /// Recording to play back, as written by `--record-run`. 
+    pub file: PathBuf, 
+
+    /// Overrides the recorded animation timestep, e.g. to play back faster. 
+    #[arg(long, short)]
+    pub delay: Option<humantime::Duration>, 
+
+    /// Writes the final maze to a file, with the format inferred from its extension. Use `-` for stdout. 
+    #[arg(long, short)]
+    pub output: Option<Target>, 
+
+    /// Format the output is written in, overriding the one inferred from its path (e.g. one without an
+    /// extension). 
+    #[arg(long, requires = "output")]
+    pub output_format: Option<Format>, 
+
+    #[command(flatten)]
+    pub help: Help, 
+}
+
+#[derive(Args)]
+#[command(disable_help_flag = true)]
+pub struct PlayArgs {
+    /// Stream of escape sequences to play back, as written by `--record-ansi`. Any key skips to its end. 
+    pub file: PathBuf, 
+
+    #[command(flatten)]
+    pub help: Help, 
+}
+
+#[derive(Args)]
+#[command(disable_help_flag = true)]
+pub struct ExportArgs {
+    #[command(flatten)]
+    pub maze: MazeArgs, 
+
+    #[arg(long, short, value_parser = generator_parser())]
+    pub generator: Generator, 
+
+    #[command(flatten)]
+    pub tiling: ParallelArgs, 
+
+    #[command(flatten)]
+    pub difficulty: DifficultyArgs, 
+
+    #[arg(long, short, value_parser = solver_parser())]
+    pub solver: Solver, 
+
+    #[command(flatten)]
+    pub colours: ColourArgs, 
+
+    #[command(flatten)]
+    pub output: OutputArgs, 
+
+    #[command(flatten)]
+    pub solution: SolutionArgs, 
+
+    #[command(flatten)]
+    pub analysis: StatsArgs, 
+
+    #[command(flatten)]
+    pub help: Help, 
+}
+
+impl BenchArgs {
+    /// Gets the selected generators. 
+    pub fn generators(&self) -> Vec<Generator> {
+        select(&GENERATORS, &self.generators)
+    }
+
+    /// Gets the selected solvers. 
+    pub fn solvers(&self) -> Vec<Solver> {
+        select(&SOLVERS, &self.solvers)
+    }
+}
+
+// arguments for the dimensions of a generated maze
+#[derive(Args)]
+pub struct MazeArgs {
+    /// Maze width in nodes. Defaults to fill the terminal. 
+    #[arg(long, short)]
+    #[arg(value_parser = clap::value_parser!(u16).range(2..))]
+    pub width: Option<u16>, 
+
+    /// Maze height in nodes. Defaults to fill the terminal. 
+    #[arg(long, short)]
+    #[arg(value_parser = clap::value_parser!(u16).range(2..))]
+    pub height: Option<u16>, 
+
+    /// Maze size in nodes as WIDTHxHEIGHT, shorthand for `--width` and `--height`. 
+    #[arg(long, conflicts_with_all = ["width", "height"])]
+    pub size: Option<Size>, 
+
+    /// Seed for the random number generator, such that the same maze is generated each time. Defaults to a
+    /// random seed. 
+    #[arg(long)]
+    pub seed: Option<u64>, 
+
+    /// Uses the current date (in UTC) as the seed, in the form YYYYMMDD, such that everyone gets the same maze
+    /// each day. Ignored if a seed is given. 
+    #[arg(long)]
+    pub daily: bool, 
+
+    /// Margin left around mazes filling the terminal, in cells (two columns wide and a row tall). 
+    #[arg(long, value_name = "CELLS", default_value_t = 0)]
+    pub margin: u16, 
+
+    /// Checks that the generated maze is perfect, i.e. that there's exactly one path between any two nodes,
+    /// failing otherwise. 
+    #[arg(long)]
+    pub verify: bool, 
+}
+
+impl MazeArgs {
+    /// Gets the width, if given either directly or as part of the size. 
+    pub fn width(&self) -> Option<u16> {
+        self.size.map(|Size(width, _)| width).or(self.width)
+    }
+
+    /// Gets the height, if given either directly or as part of the size. 
+    pub fn height(&self) -> Option<u16> {
+        self.size.map(|Size(_, height)| height).or(self.height)
+    }
+}
+
+/// Maze dimensions in nodes, as given by `--size`. 
+#[derive(Clone, Copy, Debug)]
+pub struct Size(pub u16, pub u16);
+
+impl FromStr for Size {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Size, String> {
+        let dimension = |x: &str| x.parse::<u16>().ok().filter(|&x| x >= 2);
+        let dimensions = s
+            .split_once('x')
+            .and_then(|(width, height)| Some(Size(dimension(width)?, dimension(height)?)));
+        dimensions.ok_or_else(|| format!("expected WIDTHxHEIGHT with both at least 2, got `{s}`"))
+    }
+}
+
+/// Weights of algorithms chosen at random, by name, as given by `--weights`. 
+#[derive(Clone, Debug, Default)]
+pub struct Weights(pub Vec<(String, f64)>);
+
+impl Weights {
+    /// Gets the weight of an algorithm, which is 1 unless given. 
+    pub fn weight(&self, name: &str) -> f64 {
+        self.0.iter().find(|(n, _)| n == name).map_or(1.0, |&(_, weight)| weight)
+    }
+}
+
+impl FromStr for Weights {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Weights, String> {
+        s.split(',').map(|pair| {
+            let (name, weight) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("expected NAME=WEIGHT, got `{pair}`"))?;
+            if GENERATORS.find(name).is_none() && SOLVERS.find(name).is_none() {
+                return Err(format!("no generator or solver is named `{name}`"))
+            }
+            match weight.parse::<f64>() {
+                Ok(weight) if weight.is_finite() && weight >= 0.0 => Ok((name.to_owned(), weight)), 
+                _ => Err(format!("expected a non-negative weight, got `{weight}`")), 
+            }
+        }).collect::<Result<_, _>>().map(Weights)
+    }
+}
+
+// arguments for how the animation is presented in the terminal
+#[derive(Args)]
+pub struct AnimationArgs {
+    /// Animation timestep. 
+    #[arg(long, short, default_value="60ms")]
+    pub delay: humantime::Duration, 
+
+    #[command(flatten)]
+    pub colours: ColourArgs, 
+
+    /// Renders in the normal screen buffer instead of the alternate screen, using at most the given number of
+    /// rows (at least 3, fitting a maze two nodes tall), such that the final maze remains in the scrollback
+    /// after exiting. 
+    #[arg(long, value_name = "ROWS")]
+    #[arg(value_parser = clap::value_parser!(u16).range(3..))]
+    pub inline: Option<Option<u16>>, 
+
+    /// Number of timesteps to advance between each rendered frame, or auto to scale with the maze size. 
+    #[arg(long, value_name = "STEPS", default_value = "1")]
+    pub steps_per_frame: StepsPerFrame, 
+
+    /// Transition played between phases. 
+    #[arg(long, alias = "transition", value_name = "STYLE", default_value = "flash")]
+    pub fade_style: FadeStyle, 
+
+    /// Duration of a full fade between phases, instead of scaling with the delay. 
+    #[arg(long, value_name = "DURATION")]
+    pub fade_duration: Option<humantime::Duration>, 
+
+    /// Skips the ring of colour expanding from the goal once it's reached. 
+    #[arg(long)]
+    pub no_celebration: bool, 
+
+    /// How the solution is walked once found. 
+    #[arg(long, value_name = "STYLE", default_value = "trace")]
+    pub walk: WalkStyle, 
+
+    /// Speed of the runner relative to the other animations, with `--walk runner`. 
+    #[arg(long, value_name = "FACTOR", default_value = "1", value_parser = factor)]
+    pub runner_speed: f64, 
+
+    /// Number of walkers with `--walk swarm`, one walking the solution and the others starting from random
+    /// dead ends. 
+    #[arg(long, value_name = "COUNT", default_value = "5")]
+    #[arg(value_parser = clap::value_parser!(u16).range(1..))]
+    pub walkers: u16, 
+
+    /// Number of times a pulse of brightness travels back and forth along the solution once walked, or 0 to
+    /// skip it. 
+    #[arg(long, value_name = "COUNT", default_value = "2")]
+    pub pulses: u32, 
+
+    /// Plays a finale once the solution is walked: closing the walls again until the grid is solid (in random
+    /// order or in the reverse of the order they were opened), or collapsing the maze row by row. 
+    #[arg(long, value_name = "FINALE")]
+    pub outro: Option<Outro>, 
+
+    /// Draws the nodes each algorithm is working on in a bright colour, e.g. the tip of a depth-first search
+    /// or the position of a mouse. 
+    #[arg(long)]
+    pub mark_heads: bool, 
+
+    /// Hides the maze while solving but for the nodes within a radius of those being visited, with nodes seen
+    /// before drawn dimmed. 
+    #[arg(long, value_name = "RADIUS", value_parser = radius)]
+    pub fog: Option<f64>, 
+
+    /// Slowly rotates the hue of all colours while animating, by the given number of degrees per second. 
+    #[arg(long, value_name = "DEGREES")]
+    pub hue_cycle: Option<f64>, 
+
+    /// Tones down the animations for those sensitive to motion or flashing: disables the flash transition,
+    /// the hue cycle, the celebration and the pulses, and draws frames in coarser chunks. 
+    #[arg(long)]
+    pub reduced_motion: bool, 
+
+    /// Draws cells as characters rather than colours, for monochrome terminals or to copy as text: `█` to `░`
+    /// as nodes age, `·` for unvisited nodes, and `*` for the path. 
+    #[arg(long)]
+    pub symbols: bool, 
+
+    /// How the maze is laid out: each node and edge as a cell two columns wide, as half a cell one column wide
+    /// (fitting mazes twice as wide and tall), each node as a single column with thin lines for walls (four
+    /// times as wide and twice as tall), or as a braille dot (four times as wide and tall). Mazes too large to
+    /// fit fall back to the denser layouts in that order. Toggled with `z` while animating. 
+    #[arg(long, value_name = "LAYOUT", default_value = "cells")]
+    pub layout: Layout, 
+
+    /// Glyphs drawn upon special nodes, as KEY=GLYPH pairs separated by commas, with keys `start`, `goal`,
+    /// and `path`, e.g. `start=🐭,goal=🧀`. Glyphs two columns wide (such as emoji) fill the cell, and are
+    /// left out of layouts with narrower cells. 
+    #[arg(long, value_name = "GLYPHS")]
+    pub glyphs: Option<Glyphs>, 
+
+    /// Characters each cell is filled with, one or two columns wide, e.g. `██` or `[]`, or a single character
+    /// to fit mazes twice as wide in narrow terminals. 
+    #[arg(long, value_name = "CELL", default_value = CELL, value_parser = cell)]
+    pub cell: String, 
+
+    /// Plays a title screen before the run, with the letters of `OVERLOOK` generating and solving themselves as
+    /// mazes. Skipped if the maze is too small to fit them. 
+    #[arg(long)]
+    pub title: bool, 
+
+    /// Tunes the algorithms, as KEY=VALUE pairs separated by commas, with keys of the form ALGORITHM.OPTION: 
+    /// `prim.frontier` (random, newest, or oldest edge carved next), `wilson.walk-cap` (edges walked before
+    /// starting over elsewhere), `wilson.start` (random, centre, corner, or X:Y node the maze grows from), and
+    /// `mouse.restart` (probability below 1 of returning to the start at each step, where values much above 0.05
+    /// effectively never reach the goal). 
+    #[arg(long, value_name = "OPTIONS")]
+    pub algo_opt: Vec<Options>, 
+
+    /// Where the maze is solved from and to, unless marked in a loaded maze: the dead ends nearest to the top
+    /// left and bottom right corners, the dead ends farthest apart, the ends of the longest path, or two nodes
+    /// at random. 
+    #[arg(long, value_name = "STRATEGY", default_value = "corners")]
+    pub endpoints: Endpoints, 
+
+    /// Traces the longest path through the maze once generated, before solving it. 
+    #[arg(long)]
+    pub diameter: bool, 
+
+    /// Solves from several nodes at once toward the same goal, each search drawn in its own hue: `corners` for
+    /// the dead ends nearest to each corner, or X:Y coordinates separated by commas. 
+    #[arg(long)]
+    pub starts: Option<Sources>, 
+
+    /// Places pairs of portals upon dead ends of the maze before solving it, each marked by a letter shared
+    /// with its twin: entering either moves straight on to the other. 
+    #[arg(long, value_name = "PAIRS", default_value = "0")]
+    pub portals: usize, 
+
+    /// Makes a share of the passages one-way before solving them, from 0 to 1, drawn as arrows. Each points
+    /// toward the goal, such that it can still be reached from everywhere. 
+    #[arg(long, value_name = "SHARE", default_value = "0", value_parser = share)]
+    pub one_way: f64, 
+
+    /// Shifts the maze every given number of timesteps while solving, closing a passage and opening a wall
+    /// elsewhere such that every node can still be reached. Solvers re-plan around the change. 
+    #[arg(long, value_name = "STEPS")]
+    #[arg(value_parser = clap::value_parser!(u32).range(1..))]
+    pub shift_every: Option<u32>, 
+
+    /// Covers a share of the nodes in ice before solving, from 0 to 1, drawn as waves. Moves onto ice carry on
+    /// in the same direction until hitting a wall or reaching a junction, for solvers and players alike. 
+    #[arg(long, value_name = "SHARE", default_value = "0", value_parser = share)]
+    pub ice: f64, 
+
+    /// Raises smooth hills over the maze before solving, drawn as nodes shaded lighter the higher they are. 
+    /// Moving uphill costs more, which A* weighs up in finding its way. 
+    #[arg(long)]
+    pub elevation: bool, 
+
+    /// Marks the nodes queued by A* with dots coloured by their f-score, the estimated length of the path
+    /// through them, from blue for the lowest to red for the highest. 
+    #[arg(long)]
+    pub f_scores: bool, 
+
+    /// Draws live counters below the maze while solving: the nodes visited, and the candidates queued to be
+    /// visited by solvers that keep a queue. 
+    #[arg(long)]
+    pub hud: bool, 
+
+    /// Draws a panel beside the maze showing the internals of the running algorithm, e.g. the depth of a
+    /// depth-first search's stack or the most promising candidates of A*. Toggled with `i` while animating. 
+    #[arg(long)]
+    pub debug_panel: bool, 
+
+    /// Queries the terminal for its background colour and enables `--light` if it's light. 
+    #[arg(long, conflicts_with = "light")]
+    pub detect_background: bool, 
+
+    /// Writes the final maze to a file, with the format inferred from its extension. Use `-` for stdout. 
+    #[arg(long, short)]
+    pub output: Option<Target>, 
+
+    /// Format the output is written in, overriding the one inferred from its path (e.g. one without an
+    /// extension). 
+    #[arg(long, requires = "output")]
+    pub output_format: Option<Format>, 
+
+    #[command(flatten)]
+    pub solution: SolutionArgs, 
+
+    #[command(flatten)]
+    pub analysis: StatsArgs, 
+
+    /// Writes each frame as plain text to a numbered file in the directory instead of animating, such that
+    /// runs with a fixed seed may be compared against golden files. 
+    #[arg(long, value_name = "DIR", hide = true)]
+    pub dump_frames: Option<PathBuf>, 
+
+    /// Writes the frames drawn to a file as the raw stream of escape sequences drawing them, such that `cat`
+    /// replays them up to the final frame, with the delays between them embedded for `overlook play`. 
+    #[arg(long, value_name = "FILE", conflicts_with = "dump_frames")]
+    pub record_ansi: Option<PathBuf>, 
+
+    /// Leaves the delays between frames out of the stream written by `--record-ansi`. 
+    #[arg(long, requires = "record_ansi")]
+    pub strip_timing: bool, 
+}
+
+impl AnimationArgs {
+    /// Gets the output (if any) with its format resolved, failing if it can't be inferred from the path and
+    /// isn't given either. 
+    pub fn output(&self) -> Result<Option<Output>, Error> {
+        self.output.clone().map(|target| target.with_format(self.output_format)).transpose()
+    }
+}
+
+// arguments for how the maze is coloured
+#[derive(Args)]
+pub struct ColourArgs {
+    /// Renders the maze using only standard ANSI colours, as it always is on legacy Windows consoles. 
+    #[arg(long, short)]
+    pub ansi: bool, 
+
+    /// Renders the maze using only the 256 colours of the xterm palette, for terminals without true colour. 
+    #[arg(long, conflicts_with = "ansi")]
+    pub indexed: bool, 
+
+    /// Dithers between the two colours of the 256-colour palette nearest to each, alternating them cell by
+    /// cell such that gradients look smoother. 
+    #[arg(long, requires = "indexed")]
+    pub dither: bool, 
+
+    /// Colour theme. 
+    #[arg(long, short, default_value = "classic")]
+    pub theme: ThemeName, 
+
+    /// Overrides colours of the generate phase, e.g. `young=#ff8800,old=#201000`. The keys are young, old,
+    /// unvisited, special, and wall. 
+    #[arg(long, value_name = "COLOURS")]
+    pub palette_generate: Vec<PaletteOverride>, 
+
+    /// Overrides colours of the solve phase (see `--palette-generate`). 
+    #[arg(long, value_name = "COLOURS")]
+    pub palette_solve: Vec<PaletteOverride>, 
+
+    /// Overrides colours of the walk phase (see `--palette-generate`). 
+    #[arg(long, value_name = "COLOURS")]
+    pub palette_walk: Vec<PaletteOverride>, 
+
+    /// Renders the maze in shades of gray, varying only in lightness. 
+    #[arg(long, conflicts_with = "ansi")]
+    pub grayscale: bool, 
+
+    /// Derives colours told apart by lightness alone, with nodes contrasting strongly with the walls and with
+    /// each other, overriding the theme. 
+    #[arg(long, conflicts_with = "ansi")]
+    pub high_contrast: bool, 
+
+    /// Inverts the lightness of all colours for terminals with a light background. 
+    #[arg(long)]
+    pub light: bool, 
+
+    /// Colour of walls: auto (contrasting with the maze), none (the terminal background), or a hex colour. 
+    #[arg(long, default_value = "auto")]
+    pub wall: Wall, 
+
+    /// Number of timesteps for trails to fully decay. 
+    #[arg(long, value_name = "STEPS", default_value = "255")]
+    #[arg(value_parser = clap::value_parser!(u32).range(1..=1_000_000))]
+    pub trail: u32, 
+
+    /// How quickly trails decay: linear, out-circ, out-expo, smoothstep, or steps:N. 
+    #[arg(long, short, default_value = "out-circ")]
+    pub easing: Easing, 
+
+    /// Wraps each frame in a synchronized update (mode 2026), such that terminals supporting it show the frame
+    /// at once rather than tearing as it's written. Other terminals ignore it. 
+    #[arg(long)]
+    pub synchronized: bool, 
+}
+
+impl Default for ColourArgs {
+    fn default() -> ColourArgs {
+        ColourArgs {
+            ansi: false, 
+            indexed: false, 
+            dither: false, 
+            theme: ThemeName::default(), 
+            palette_generate: Vec::new(), 
+            palette_solve: Vec::new(), 
+            palette_walk: Vec::new(), 
+            grayscale: false, 
+            high_contrast: false, 
+            light: false, 
+            wall: Wall::default(), 
+            trail: u8::MAX as u32, 
+            easing: Easing::default(), 
+            synchronized: false, 
+        }
+    }
+}
+
+// arguments for where headless commands write their result
+#[derive(Args)]
+pub struct OutputArgs {
+    /// File to write to, with the format inferred from its extension. Use `-` for stdout. 
+    #[arg(long, short, default_value = "-")]
+    pub output: Target, 
+
+    /// Format the output is written in, overriding the one inferred from its path (e.g. one without an
+    /// extension). 
+    #[arg(long)]
+    pub output_format: Option<Format>, 
+}
+
+impl OutputArgs {
+    /// Gets the output with its format resolved, failing if it can't be inferred from the path and isn't
+    /// given either. 
+    pub fn output(&self) -> Result<Output, Error> {
+        self.output.clone().with_format(self.output_format)
+    }
+}
+
+// arguments for where the solution found is written
+#[derive(Args)]
+pub struct SolutionArgs {
+    /// Writes the solution to a file once walked, as a json list of coordinates if the extension is json or
+    /// as moves like `NNEESW` otherwise. Use `-` for stdout. 
+    #[arg(long, value_name = "FILE")]
+    pub solution: Option<SolutionOutput>, 
+
+    /// Overrides the format inferred from the solution path. 
+    #[arg(long, value_name = "FORMAT", requires = "solution")]
+    pub solution_format: Option<SolutionFormat>, 
+}
+
+impl SolutionArgs {
+    /// Gets the solution output with any format override applied. 
+    pub fn solution(&self) -> Option<SolutionOutput> {
+        let solution = self.solution.clone()?;
+        Some(solution.with_format(self.solution_format))
+    }
+}
+
+// argument for generating headless mazes in tiles in parallel
+#[derive(Args)]
+pub struct ParallelArgs {
+    /// Generates the maze in tiles in parallel, which is much faster for huge mazes but leaves the tile
+    /// borders visible as long straight walls. 
+    #[arg(long)]
+    pub parallel: bool, 
+}
+
+// argument for the difficulty of generated mazes
+#[derive(Args)]
+pub struct DifficultyArgs {
+    /// Regenerates the maze with other seeds until its difficulty (from 0 to 100, as shown by `--stats`) is at
+    /// least the given score. 
+    #[arg(long, value_name = "SCORE")]
+    pub min_difficulty: Option<f64>, 
+}
+
+// argument for printing the analysis of a maze once finished
+#[derive(Args)]
+pub struct StatsArgs {
+    /// Prints statistics of the maze (and of its solution) to stderr once finished, such as its dead ends,
+    /// junctions, and corridors. 
+    #[arg(long)]
+    pub stats: bool, 
+}
+
+// long-only help flag, since `-h` is taken by `MazeArgs::height`. these are plain comments since doc
+// comments on flattened arguments would override the help text of the command
+#[derive(Args)]
+pub struct Help {
+    /// Print help. 
+    #[arg(long, action=ArgAction::HelpLong)]
+    help: Option<bool>, 
+}
+
+/// Parses the name of a generator in [`GENERATORS`], listing all of them as possible values. 
+fn generator_parser() -> impl TypedValueParser<Value = Generator> {
+    let names = GENERATORS.entries().into_iter().map(|g| g.name());
+    PossibleValuesParser::new(names).map(|name| GENERATORS.find(&name).expect("name is a possible value"))
+}
+
+/// Parses the name of a solver in [`SOLVERS`], listing all of them as possible values. 
+fn solver_parser() -> impl TypedValueParser<Value = Solver> {
+    let names = SOLVERS.entries().into_iter().map(|s| s.name());
+    PossibleValuesParser::new(names).map(|name| SOLVERS.find(&name).expect("name is a possible value"))
+}
+
+/// Parses the name of an entry in a registry or `all`, listing all of them as possible values. 
+fn selection_parser<T: Phase>(registry: &Registry<T>) -> PossibleValuesParser {
+    let names = registry.entries().into_iter().map(|entry| entry.name());
+    PossibleValuesParser::new(names.chain(["all"]))
+}
+
+/// Parses a share from 0 to 1. 
+fn share(s: &str) -> Result<f64, String> {
+    s.parse()
+        .ok()
+        .filter(|share| (0.0..=1.0).contains(share))
+        .ok_or_else(|| format!("expected a share from 0 to 1, got `{s}`"))
+}
+
+/// Parses a factor, which must be finite and greater than 0. 
+fn factor(s: &str) -> Result<f64, String> {
+    s.parse()
+        .ok()
+        .filter(|factor: &f64| factor.is_finite() && *factor > 0.0)
+        .ok_or_else(|| format!("expected a factor greater than 0, got `{s}`"))
+}
+
+/// Parses a radius, which must be finite and at least 0. 
+fn radius(s: &str) -> Result<f64, String> {
+    s.parse()
+        .ok()
+        .filter(|radius: &f64| radius.is_finite() && *radius >= 0.0)
+        .ok_or_else(|| format!("expected a radius of at least 0, got `{s}`"))
+}
+
+/// Parses the coordinates of a node, as X:Y. 
+fn node(s: &str) -> Result<Node, String> {
+    s.split_once(':')
+        .and_then(|(x, y)| Some(Node(x.parse().ok()?, y.parse().ok()?)))
+        .ok_or_else(|| format!("expected X:Y, got `{s}`"))
+}
+
+/// Parses the contents of a cell, which must be one or two columns wide. 
+fn cell(s: &str) -> Result<String, String> {
+    match s.width() {
+        1 | 2 => Ok(s.to_owned()), 
+        width => Err(format!("expected one or two columns, got {width}")), 
+    }
+}
+
+/// Looks up the entries selected by name, where `all` selects all entries in order of registration. 
+fn select<T: Phase>(registry: &Registry<T>, names: &[String]) -> Vec<Entry<T>> {
+    if names.iter().any(|name| name == "all") {
+        return registry.entries()
+    }
+    names
+        .iter()
+        .map(|name| registry.find(name).expect("name is a possible value"))
+        .collect()
+}
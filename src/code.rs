@@ -0,0 +1,160 @@
+use std::{fmt, str::FromStr};
+use crate::{
+    generate::{Generator, GENERATORS}, 
+    pipeline::Summary, 
+    registry::{Entry, Registry}, 
+    solve::{Solver, SOLVERS}, 
+    state::Phase, 
+};
+
+/// Digits of run codes, in order of their value. 
+const DIGITS: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Everything needed to reproduce a generated maze and its solve, written as a short base62 string (as
+/// printed by the summary, and accepted by `--code`). 
+///
+/// The fields are packed into the bits of a single number, with the generator and solver given by their
+/// index in [`GENERATORS`] and [`SOLVERS`]. Codes are thus only shared reliably between builds registering
+/// the same algorithms in the same order. 
+#[derive(Clone, Copy)]
+pub struct RunCode {
+    /// Width of the maze in nodes. 
+    pub width: u16, 
+    /// Height of the maze in nodes. 
+    pub height: u16, 
+    pub generator: Generator, 
+    pub solver: Solver, 
+    /// Seed the run was started from (see [`Settings::seed`](crate::Settings::seed)). 
+    pub seed: u64, 
+}
+
+impl RunCode {
+    /// Gets the code of a summarised run, or `None` if the maze was loaded rather than generated, or if its
+    /// generator or solver isn't registered (such that the code can't name it). 
+    pub fn new(summary: &Summary) -> Option<RunCode> {
+        let code = RunCode {
+            width: u16::try_from(summary.width).ok()?, 
+            height: u16::try_from(summary.height).ok()?, 
+            generator: GENERATORS.find(summary.generator?)?, 
+            solver: SOLVERS.find(summary.solver)?, 
+            seed: summary.seed, 
+        };
+        code.pack().map(|_| code)
+    }
+
+    /// Packs the fields into a number, from the seed in the most significant bits to the solver in the least, 
+    /// or `None` if the generator or solver isn't registered among the first 256 entries of its registry. 
+    fn pack(&self) -> Option<u128> {
+        let generator = u8::try_from(index(&GENERATORS, self.generator)?).ok()? as u128;
+        let solver = u8::try_from(index(&SOLVERS, self.solver)?).ok()? as u128;
+        Some((self.seed as u128) << 48
+            | (self.width as u128) << 32
+            | (self.height as u128) << 16
+            | generator << 8
+            | solver)
+    }
+}
+
+/// Writes the code as base62 digits. Fails for codes naming algorithms that aren't registered, which
+/// [`RunCode::new`] and parsing never construct. 
+impl fmt::Display for RunCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut packed = self.pack().ok_or(fmt::Error)?;
+        let mut digits = Vec::new();
+        while packed > 0 || digits.is_empty() {
+            digits.push(DIGITS[(packed % 62) as usize]);
+            packed /= 62;
+        }
+        digits.reverse();
+        // the digits are all ASCII
+        f.write_str(&String::from_utf8_lossy(&digits))
+    }
+}
+
+impl FromStr for RunCode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<RunCode, String> {
+        let invalid = || format!("invalid run code `{s}`");
+        let packed = s.bytes().try_fold(0u128, |packed, digit| {
+            let value = DIGITS.iter().position(|&d| d == digit)?;
+            packed.checked_mul(62)?.checked_add(value as u128)
+        });
+        let packed = packed.filter(|_| !s.is_empty()).ok_or_else(invalid)?;
+
+        let field = |shift: u32, bits: u32| (packed >> shift) & ((1 << bits) - 1);
+        let dimension = |x: u128| u16::try_from(x).ok().filter(|&x| x >= 2);
+        match (
+            dimension(field(32, 16)), 
+            dimension(field(16, 16)), 
+            entry(&GENERATORS, field(8, 8)), 
+            entry(&SOLVERS, field(0, 8)), 
+            u64::try_from(packed >> 48), 
+        ) {
+            (Some(width), Some(height), Some(generator), Some(solver), Ok(seed)) => {
+                Ok(RunCode{ width, height, generator, solver, seed })
+            }, 
+            _ => Err(invalid()), 
+        }
+    }
+}
+
+/// Gets the index of an entry in a registry, or `None` if it isn't registered. 
+fn index<T: Phase>(registry: &Registry<T>, entry: Entry<T>) -> Option<usize> {
+    registry.entries().iter().position(|e| e.name() == entry.name())
+}
+
+/// Gets the entry of a registry at an index. 
+fn entry<T: Phase>(registry: &Registry<T>, index: u128) -> Option<Entry<T>> {
+    registry.entries().get(usize::try_from(index).ok()?).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(generator: Option<&'static str>, solver: &'static str) -> Summary {
+        Summary {
+            width: 40, 
+            height: 12, 
+            seed: u64::MAX, 
+            generator, 
+            solver, 
+            generate_steps: 0, 
+            solve_steps: 0, 
+            expanded: 0, 
+            solution_length: None, 
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        for generator in GENERATORS.entries() {
+            for solver in SOLVERS.entries() {
+                let code = RunCode::new(&summary(Some(generator.name()), solver.name())).unwrap();
+                let parsed: RunCode = code.to_string().parse().unwrap();
+                assert_eq!((parsed.width, parsed.height, parsed.seed), (40, 12, u64::MAX));
+                assert_eq!(parsed.generator.name(), generator.name());
+                assert_eq!(parsed.solver.name(), solver.name());
+            }
+        }
+    }
+
+    #[test]
+    fn leaves_out_loaded_and_unregistered() {
+        assert!(RunCode::new(&summary(None, "a-star")).is_none());
+        assert!(RunCode::new(&summary(Some("unregistered"), "a-star")).is_none());
+        assert!(RunCode::new(&summary(Some("dfs"), "unregistered")).is_none());
+    }
+
+    #[test]
+    fn rejects_invalid() {
+        let code = RunCode::new(&summary(Some("dfs"), "a-star")).unwrap();
+        let too_small = RunCode{ width: 1, ..code }.to_string();
+        let unregistered = RunCode{ generator: Generator::new::<crate::chase::Chase>("unregistered"), ..code };
+        assert!(fmt::write(&mut String::new(), format_args!("{unregistered}")).is_err());
+        for s in ["", "0", "not-base62", &"z".repeat(30), &too_small] {
+            assert!(s.parse::<RunCode>().is_err(), "parsed `{s}`");
+        }
+    }
+}
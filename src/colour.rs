@@ -1,10 +1,18 @@
-use crossterm::style::Color as CrosstermColour;
+use std::{
+    fmt::{self, Display}, 
+    str::FromStr, 
+    sync::OnceLock, 
+};
 use palette::{FromColor, Okhsl, OklabHue, Srgb};
 use crate::Settings;
 
+/// Default contents of a cell, which is two characters wide to appear roughly square (see
+/// [`Settings::cell`]). 
+pub const CELL: &str = "  ";
+
 /// Represents a colour with HSL coordinates. 
 ///
-/// The colours are rendered to the terminal via [`Hsl::to_crossterm`] in the Okhsl colour space. 
+/// The colours are rendered to the terminal via `Hsl::to_colour` in the Okhsl colour space. 
 #[derive(Clone, Copy, Debug, Default)]
 pub struct Hsl {
     pub hue: f64,
@@ -13,6 +21,16 @@ pub struct Hsl {
 }
 
 impl Hsl {
+    /// Converts RGB components to the Okhsl colour space. 
+    pub fn from_rgb([r, g, b]: [u8; 3]) -> Hsl {
+        let okhsl = Okhsl::from_color(Srgb::new(r, g, b).into_format::<f64>());
+        Hsl {
+            hue: okhsl.hue.into_positive_degrees(), 
+            saturation: okhsl.saturation, 
+            lightness: okhsl.lightness, 
+        }
+    }
+
     pub const fn with_h(self, hue: f64) -> Hsl {
         let hue = wrap(hue, 360.0);
         Hsl{ hue, ..self }
@@ -40,7 +58,33 @@ impl Hsl {
         self.with_l(self.lightness + lightness)
     }
 
-    fn to_crossterm(self) -> CrosstermColour {
+    /// Inverts the lightness, such that dark colours become light and vice versa. 
+    pub const fn invert_l(self) -> Hsl {
+        self.with_l(1.0 - self.lightness)
+    }
+
+    /// Gets the relative luminance of the colour as defined by WCAG, from 0 for black to 1 for white. 
+    pub fn luminance(self) -> f64 {
+        let (r, g, b) = self.to_srgb().into_linear().into_components();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// Adjusts the lightness such that the colour has the given relative luminance (see [`Hsl::luminance`]). 
+    pub fn with_luminance(self, luminance: f64) -> Hsl {
+        // luminance grows with lightness, so the lightness is found by bisection
+        let (mut low, mut high) = (0.0, 1.0);
+        for _ in 0..32 {
+            let mid = (low + high) / 2.0;
+            match self.with_l(mid).luminance() < luminance {
+                true => low = mid, 
+                false => high = mid, 
+            }
+        }
+        self.with_l((low + high) / 2.0)
+    }
+
+    /// Converts the colour from the Okhsl colour space to SRGB. 
+    fn to_srgb(self) -> Srgb<f64> {
         // convert to palette::Okhsl (we're not using this type directly to provide a better interface)
         let Hsl{ hue, lightness, saturation } = self;
         let okhsl = Okhsl {
@@ -48,32 +92,261 @@ impl Hsl {
             saturation,
             lightness, 
         };
-        // convert Okhsl to SRGB
-        let (r, g, b) = Srgb::from_color(okhsl).into_components();
+        Srgb::from_color(okhsl)
+    }
+
+    pub(crate) fn to_colour(self) -> Colour {
+        let (r, g, b) = self.to_srgb().into_components();
 
         // convert SRGB to crossterm::Color
         let [r, g, b] = [r, g, b].map(|x| (x * 255.0) as u8);
-        CrosstermColour::Rgb{ r, g, b }
+        Colour::Rgb{ r, g, b }
+    }
+}
+
+/// Colour of a cell as drawn on the terminal. 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Colour {
+    /// The default colour of the terminal. 
+    Reset, 
+    /// An entry of the xterm 256-colour palette, the first 16 of which are the standard ANSI colours. 
+    Indexed(u8), 
+    Rgb{ r: u8, g: u8, b: u8 }, 
+}
+
+impl Colour {
+    pub(crate) const BLACK: Colour = Colour::Indexed(0);
+    pub(crate) const DARK_RED: Colour = Colour::Indexed(1);
+    pub(crate) const GREY: Colour = Colour::Indexed(7);
+    pub(crate) const DARK_GREY: Colour = Colour::Indexed(8);
+    pub(crate) const RED: Colour = Colour::Indexed(9);
+    pub(crate) const YELLOW: Colour = Colour::Indexed(11);
+    pub(crate) const BLUE: Colour = Colour::Indexed(12);
+    pub(crate) const WHITE: Colour = Colour::Indexed(15);
+
+    /// Writes the escape sequence setting the foreground (`38`) or background (`48`) to the colour. If colours
+    /// are disabled through the `NO_COLOR` environment variable, the sequence sets no colour at all. 
+    fn write_sgr(self, f: &mut fmt::Formatter, layer: u8) -> fmt::Result {
+        static NO_COLOR: OnceLock<bool> = OnceLock::new();
+        if *NO_COLOR.get_or_init(|| std::env::var_os("NO_COLOR").is_some_and(|x| !x.is_empty())) {
+            return f.write_str("\x1b[m")
+        }
+        match self {
+            Colour::Reset => write!(f, "\x1b[{}m", layer + 1), 
+            Colour::Indexed(i) => write!(f, "\x1b[{layer};5;{i}m"), 
+            Colour::Rgb{ r, g, b } => write!(f, "\x1b[{layer};2;{r};{g};{b}m"), 
+        }
+    }
+}
+
+/// Content drawn in a foreground and background colour (see [`Paint`]). Its `Display` impl writes the
+/// content between the escape sequences setting the colours and resetting them to the terminal's defaults. 
+pub(crate) struct Painted<T> {
+    content: T, 
+    foreground: Option<Colour>, 
+    background: Option<Colour>, 
+}
+
+impl<T> Painted<T> {
+    pub(crate) fn on(self, background: Colour) -> Painted<T> {
+        Painted{ background: Some(background), ..self }
+    }
+}
+
+impl<T: Display> Display for Painted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(background) = self.background {
+            background.write_sgr(f, 48)?;
+        }
+        if let Some(foreground) = self.foreground {
+            foreground.write_sgr(f, 38)?;
+        }
+        self.content.fmt(f)?;
+        if self.background.is_some() {
+            Colour::Reset.write_sgr(f, 48)?;
+        }
+        if self.foreground.is_some() {
+            Colour::Reset.write_sgr(f, 38)?;
+        }
+        Ok(())
+    }
+}
+
+/// Paints text in a foreground and background colour, e.g. `'@'.with(foreground).on(background)`. 
+pub(crate) trait Paint: Sized {
+    fn with(self, foreground: Colour) -> Painted<Self> {
+        Painted{ content: self, foreground: Some(foreground), background: None }
+    }
+
+    fn on(self, background: Colour) -> Painted<Self> {
+        Painted{ content: self, foreground: None, background: Some(background) }
+    }
+}
+
+impl Paint for char {}
+impl Paint for &str {}
+impl Paint for String {}
+
+/// Kind of colour a cell is drawn in, as looked up in a [`Lut`]. 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Swatch {
+    /// A visited node of given age. 
+    Age(u8), 
+    Unvisited, 
+    Special, 
+    Wall, 
+    /// A node being worked on by the running algorithm (see [`Animation::heads`](crate::Animation::heads)). 
+    Head, 
+    /// An open cell that was seen before but is out of sight (see [`Fog`](crate::state::Fog)). 
+    Fog, 
+}
+
+impl Swatch {
+    /// Gets the swatch of a node, which is unvisited if it has no age. 
+    pub fn node(age: Option<u8>) -> Swatch {
+        age.map(Swatch::Age).unwrap_or(Swatch::Unvisited)
+    }
+
+    /// Gets the index of the swatch in [`Lut::escapes`]. 
+    fn index(self) -> usize {
+        match self {
+            Swatch::Age(age) => age as usize, 
+            Swatch::Unvisited => 256, 
+            Swatch::Special => 257, 
+            Swatch::Wall => 258, 
+            Swatch::Head => 259, 
+            Swatch::Fog => 260, 
+        }
     }
 }
 
 /// Colour lookup derived from [`Palette`] to be used when rendering maze nodes. 
+///
+/// Besides the colours, the LUT holds the complete escape sequences drawing a cell in each colour, such that
+/// rendering a frame is merely a matter of copying strings. 
 #[derive(Clone)]
 pub struct Lut {
     /// Palette the LUT is derived from. 
     pub palette: Palette, 
     /// Precomputed age gradient. 
-    pub gradient: [CrosstermColour; 256], 
+    pub(crate) gradient: [Colour; 256], 
     /// Colour of unvisited nodes. 
-    pub unvisited: CrosstermColour, 
+    pub(crate) unvisited: Colour, 
     /// Colour of special nodes. 
-    pub special: CrosstermColour, 
+    pub(crate) special: Colour, 
+    /// Colour of walls. 
+    pub(crate) wall: Colour, 
+    /// Colour of the heads of the running algorithm. 
+    pub(crate) head: Colour, 
+    /// Colour of cells remembered through the fog of war. 
+    pub(crate) fog: Colour, 
+    /// Contents of a cell drawn by the escape sequences. 
+    cell: String, 
+    /// How the colours are reduced to the 256-colour palette, if at all. 
+    indexing: Indexing, 
+    /// Colour each swatch is drawn in, as reduced to the 256-colour palette if indexed, indexed by
+    /// [`Swatch::index`]. 
+    drawn: Vec<Colour>, 
+    /// Escape sequences drawing a cell in each colour, indexed by [`Swatch::index`]. 
+    escapes: Vec<String>, 
+    /// Escape sequences drawing a cell in the colour each swatch is dithered with, along with the number of
+    /// cells out of every four drawn in it (see [`Lut::escape_at`]). Empty unless dithering. 
+    dithered: Vec<(u8, String)>, 
 }
 
 impl Lut {
-    /// Gets the colour of a node by sampling the gradient if the node is unvisited. 
-    pub fn sample(&self, age: Option<u8>) -> CrosstermColour {
-        age.map(|i| self.gradient[i as usize]).unwrap_or(self.unvisited)
+    /// Constructs a LUT from its age gradient and the colours of the other swatches (unvisited, special, wall,
+    /// head, and fog), precomputing the escape sequences drawing a cell of given contents in each as reduced
+    /// to the 256-colour palette (if at all). 
+    fn new(
+        palette: Palette, 
+        gradient: [Colour; 256], 
+        [unvisited, special, wall, head, fog]: [Colour; 5], 
+        cell: &str, 
+        indexing: Indexing, 
+    ) -> Lut {
+        let reduced: Vec<_> = gradient
+            .iter()
+            .chain([&unvisited, &special, &wall, &head, &fog])
+            .map(|&colour| indexing.reduce(colour))
+            .collect();
+        let drawn = reduced.iter().map(|&(colour, _)| colour).collect();
+        let escapes = reduced.iter().map(|&(colour, _)| cell.on(colour).to_string()).collect();
+        let dithered = match indexing {
+            Indexing::Dither => reduced
+                .iter()
+                .map(|&(colour, dither)| {
+                    let (alternate, level) = dither.unwrap_or((colour, 0));
+                    (level, cell.on(alternate).to_string())
+                })
+                .collect(), 
+            Indexing::None | Indexing::Nearest => Vec::new(), 
+        };
+        Lut {
+            palette, 
+            gradient, 
+            unvisited, 
+            special, 
+            wall, 
+            head, 
+            fog, 
+            cell: cell.to_owned(), 
+            indexing, 
+            drawn, 
+            escapes, 
+            dithered, 
+        }
+    }
+
+    /// Replaces the wall colour. 
+    #[cfg(feature = "terminal")]
+    pub(crate) fn with_wall(self, wall: Colour) -> Lut {
+        let colours = [self.unvisited, self.special, wall, self.head, self.fog];
+        Lut::new(self.palette, self.gradient, colours, &self.cell, self.indexing)
+    }
+
+    /// Shifts the lightness of the age gradient, e.g. to shade nodes by their elevation (see
+    /// [`Elevation`](crate::elevation::Elevation)). Colours other than RGB ones are kept, as they can't be
+    /// shaded without leaving the terminal's palette. 
+    pub(crate) fn shade_l(&self, lightness: f64) -> Lut {
+        let gradient = self.gradient.map(|colour| match colour {
+            Colour::Rgb{ r, g, b } => Hsl::from_rgb([r, g, b]).shift_l(lightness).to_colour(), 
+            _ => colour, 
+        });
+        let colours = [self.unvisited, self.special, self.wall, self.head, self.fog];
+        Lut::new(self.palette, gradient, colours, &self.cell, self.indexing)
+    }
+
+    /// Gets the colour of a swatch, as drawn. 
+    pub(crate) fn colour(&self, swatch: Swatch) -> Colour {
+        self.drawn[swatch.index()]
+    }
+
+    /// Gets the RGB colour of a swatch, or `None` for the terminal's default colour. 
+    pub fn rgb(&self, swatch: Swatch) -> Option<[u8; 3]> {
+        to_rgb(self.colour(swatch))
+    }
+
+    /// Gets the contents of a cell as laid out by default, drawn upon the colour of its swatch. 
+    #[cfg(feature = "terminal")]
+    pub(crate) fn cell(&self) -> &str {
+        &self.cell
+    }
+
+    /// Gets the escape sequence drawing a cell in the colour of a swatch. 
+    pub fn escape(&self, swatch: Swatch) -> &str {
+        &self.escapes[swatch.index()]
+    }
+
+    /// Gets the escape sequence drawing a cell at a column and row in the colour of a swatch, which alternates
+    /// with the colour it's dithered with (if any) in an ordered pattern repeating every two cells either way. 
+    pub fn escape_at(&self, swatch: Swatch, x: usize, y: usize) -> &str {
+        // 2x2 Bayer matrix, spreading the cells of each level as evenly as possible
+        const THRESHOLDS: [[u8; 2]; 2] = [[0, 2], [3, 1]];
+        match self.dithered.get(swatch.index()) {
+            Some((level, escape)) if THRESHOLDS[y % 2][x % 2] < *level => escape, 
+            _ => self.escape(swatch), 
+        }
     }
 }
 
@@ -90,24 +363,11 @@ pub struct Palette {
     pub unvisited: Option<Hsl>, 
     /// Colour of special nodes (as defined by [`Phase::special`](crate::state::Phase::special)). 
     pub special: Option<Hsl>, 
+    /// Colour of walls. If not given, a colour contrasting with the rest of the palette is derived. 
+    pub wall: Option<Hsl>, 
 }
 
 impl Palette {
-    /// Derives an age gradient from a "base" colour. 
-    pub fn from_base(base: Hsl) -> Palette {
-        let old = base
-            .with_l(0.3);
-        let young = base
-            .shift_h(60.0);
-        Palette {
-            base, 
-            young, 
-            old, 
-            unvisited: None, 
-            special: None, 
-        }
-    }
-
     /// Constructs a new palette with given age gradient poles. 
     pub fn new(young: Hsl, old: Hsl) -> Palette {
         Palette {
@@ -116,6 +376,7 @@ impl Palette {
             old, 
             unvisited: None, 
             special: None, 
+            wall: None, 
         }
     }
 
@@ -133,59 +394,322 @@ impl Palette {
         Palette{ special, ..self }
     }
 
+    pub fn with_wall(self, wall: Hsl) -> Palette {
+        let wall = Some(wall);
+        Palette{ wall, ..self }
+    }
+
+    /// Inverts the lightness of all colours, for rendering on light backgrounds. 
+    pub fn invert_l(self) -> Palette {
+        Palette {
+            base: self.base.invert_l(), 
+            young: self.young.invert_l(), 
+            old: self.old.invert_l(), 
+            unvisited: self.unvisited.map(Hsl::invert_l), 
+            special: self.special.map(Hsl::invert_l), 
+            wall: self.wall.map(Hsl::invert_l), 
+        }
+    }
+
+    /// Derives a wall colour contrasting with the node colours of the palette: near-black (or near-white on
+    /// light backgrounds), unless the palette itself contains colours that dark (or light), in which case the
+    /// opposite. 
+    fn contrasting_wall(&self, light: bool) -> Hsl {
+        let lightness = [Some(self.young), Some(self.old), self.unvisited]
+            .into_iter()
+            .flatten()
+            .map(|colour| colour.lightness);
+        let dark = match light {
+            false => lightness.fold(1.0, f64::min) >= 0.1, 
+            true => lightness.fold(0.0, f64::max) > 0.9, 
+        };
+        let lightness = match dark {
+            true => 0.03, 
+            false => 0.95, 
+        };
+        self.old
+            .with_s(0.3)
+            .with_l(lightness)
+    }
+
     /// Derives a [`Lut`] from the palette. Note that the palette may be overriden by settings such as
-    /// [`Settings::ansi`]. 
+    /// [`Settings::ansi`], or have its lightness inverted by [`Settings::light`] and its hue and saturation
+    /// discarded by [`Settings::grayscale`]. 
     pub fn into_lut(self, settings: &Settings) -> Lut {
         if settings.ansi {
-            return self.ansi()
+            return self.ansi(settings)
         }
         
-        let normalise = |colour: Hsl| colour
-            .shift_h(settings.hue_shift)
-            .to_crossterm();
-        let ease = |t| (1.0 - f64::powi(t - 1.0, 2)).powf(1.0/3.0); // slightly more aggressive outCirc
+        // the LUT keeps the palette as given, since it may be used to derive other palettes
+        let palette = match (settings.high_contrast, settings.light) {
+            (true, light) => self.high_contrast(light), 
+            (false, true) => self.invert_l(), 
+            (false, false) => self, 
+        };
+        let normalise = |colour: Hsl| match settings.grayscale {
+            true => colour.with_s(0.0).to_colour(), 
+            false => colour.shift_h(settings.hue_shift).to_colour(), 
+        };
         let gradient = std::array::from_fn(|i| {
-            let t = ease(i as f64 / 255.0);
-            normalise(lerp(self.young, self.old, t))
+            let t = settings.easing.ease(i as f64 / 255.0);
+            normalise(lerp(palette.young, palette.old, t))
         });
 
-        let unvisited = self.unvisited
+        let unvisited = palette.unvisited
             .map(normalise)
-            .unwrap_or(CrosstermColour::Reset);
-        let special = self.special
+            .unwrap_or(Colour::Reset);
+        let special = palette.special
             .map(normalise)
-            .unwrap_or(CrosstermColour::Reset);
+            .unwrap_or(Colour::Reset);
+        let wall = settings.wall
+            .colour()
+            .unwrap_or_else(|| normalise(palette.wall.unwrap_or_else(|| palette.contrasting_wall(settings.light))));
+        // heads stand out from the youngest nodes by being brighter still (or darker, for light backgrounds)
+        let head = normalise(palette.young.with_l(match settings.light {
+            true => 0.15, 
+            false => 0.92, 
+        }));
+        // remembered cells are the oldest colour, dimmed toward the background
+        let old = palette.old.lightness;
+        let fog = normalise(palette.old.with_l(match settings.light {
+            true => 1.0 - 0.4 * (1.0 - old), 
+            false => 0.4 * old, 
+        }));
 
-        Lut {
-            palette: self, 
-            gradient, 
-            unvisited,
-            special,
+        Lut::new(self, gradient, [unvisited, special, wall, head, fog], &settings.cell, settings.indexing)
+    }
+
+    /// Derives a palette whose kinds of colours are told apart by lightness alone, as given by
+    /// `--high-contrast`. Each kind gets a fixed relative luminance (see [`Hsl::luminance`]), such that nodes
+    /// contrast with the walls by a ratio of at least 3:1 (as WCAG asks of graphics), and each kind with the
+    /// next by about 2:1. Hues are kept, except for the grey walls and unvisited nodes. 
+    fn high_contrast(self, light: bool) -> Palette {
+        // for light backgrounds, the luminances are mirrored to contrast with white as much as with black
+        let level = |colour: Hsl, luminance: f64| colour.with_luminance(match light {
+            true => 0.05 * 1.05 / (luminance + 0.05) - 0.05, 
+            false => luminance, 
+        });
+        let grey = Hsl::default();
+        Palette {
+            base: self.base, 
+            young: level(self.young, 0.5), 
+            old: level(self.old, 0.25), 
+            unvisited: Some(level(grey, 0.1)), 
+            special: self.special.map(|special| level(special, 1.0)), 
+            wall: Some(level(grey, 0.0)), 
         }
     }
 
     /// Derives a [`Lut`] using only standard ANSI colours (not RGB), ignoring most of the palette. 
-    fn ansi(self) -> Lut {
-        let mut gradient = [CrosstermColour::White; 256];
+    fn ansi(self, settings: &Settings) -> Lut {
+        let (old, unvisited, wall) = match settings.light {
+            true => (Colour::BLACK, Colour::GREY, Colour::WHITE), 
+            false => (Colour::WHITE, Colour::DARK_GREY, Colour::BLACK), 
+        };
+        // with few colours to choose from, remembered cells look like unvisited ones
+        let fog = unvisited;
+        let mut gradient = [old; 256];
         let unvisited = self.unvisited
-            .map(|_| CrosstermColour::DarkGrey)
-            .unwrap_or(CrosstermColour::Reset);
+            .map(|_| unvisited)
+            .unwrap_or(Colour::Reset);
         let special = self.special
-            .map(|_| CrosstermColour::Blue)
-            .unwrap_or(CrosstermColour::Reset);
+            .map(|_| Colour::BLUE)
+            .unwrap_or(Colour::Reset);
+
+        gradient[..4].fill(Colour::DARK_RED);
+        gradient[4..8].fill(Colour::RED);
+        let wall = settings.wall
+            .colour()
+            .unwrap_or(wall);
+
+        let colours = [unvisited, special, wall, Colour::YELLOW, fog];
+        Lut::new(self, gradient, colours, &settings.cell, settings.indexing)
+    }
+}
+
+/// Colour of walls (closed edges and the gaps between nodes), as given by `--wall`. 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Wall {
+    /// Derived from the palette to contrast with the node colours. 
+    #[default]
+    Auto, 
+    /// The default background colour of the terminal. 
+    None, 
+    /// A fixed colour. 
+    Rgb(u8, u8, u8), 
+}
+
+impl Wall {
+    /// Gets the colour of the wall, unless it is to be derived from the palette. 
+    fn colour(self) -> Option<Colour> {
+        match self {
+            Wall::Auto => None, 
+            Wall::None => Some(Colour::Reset), 
+            Wall::Rgb(r, g, b) => Some(Colour::Rgb{ r, g, b }), 
+        }
+    }
+}
 
-        for i in 0..4 {
-            gradient[i] = CrosstermColour::DarkRed;
+impl FromStr for Wall {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Wall, String> {
+        match s {
+            "auto" => return Ok(Wall::Auto), 
+            "none" => return Ok(Wall::None), 
+            _ => (), 
         }
-        for i in 4..8 {
-            gradient[i] = CrosstermColour::Red;
+        match parse_hex(s) {
+            Some([r, g, b]) => Ok(Wall::Rgb(r, g, b)), 
+            None => Err("expected auto, none, or a hex colour like #1e1e2e".to_owned()), 
         }
+    }
+}
 
-        Lut {
-            palette: self,
-            gradient,
-            unvisited,
-            special, 
+/// Easing function used to interpolate the age gradient, controlling how quickly trails decay. 
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Easing {
+    Linear, 
+    /// A slightly more aggressive `easeOutCirc`, taking the cube-root instead of the square-root. 
+    #[default]
+    OutCirc, 
+    OutExpo, 
+    Smoothstep, 
+    /// Decays in the given number of discrete steps. 
+    Steps(u8), 
+}
+
+impl Easing {
+    /// Eases a time value between 0 and 1. 
+    pub fn ease(self, t: f64) -> f64 {
+        match self {
+            Easing::Linear => t, 
+            Easing::OutCirc => (1.0 - f64::powi(t - 1.0, 2)).powf(1.0/3.0), 
+            Easing::OutExpo => 1.0 - f64::powf(2.0, -10.0 * t), 
+            Easing::Smoothstep => t * t * (3.0 - 2.0 * t), 
+            Easing::Steps(n) => {
+                let n = n as f64;
+                f64::min((t * n).floor(), n - 1.0) / (n - 1.0)
+            }
+        }
+    }
+}
+
+impl FromStr for Easing {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Easing, String> {
+        let easing = match s {
+            "linear" => Easing::Linear, 
+            "out-circ" => Easing::OutCirc, 
+            "out-expo" => Easing::OutExpo, 
+            "smoothstep" => Easing::Smoothstep, 
+            _ => {
+                let steps = s
+                    .strip_prefix("steps:")
+                    .ok_or("expected one of linear, out-circ, out-expo, smoothstep, steps:N")?
+                    .parse()
+                    .ok()
+                    .filter(|&n| n >= 2)
+                    .ok_or("the number of steps must be between 2 and 255")?;
+                Easing::Steps(steps)
+            }
+        };
+        Ok(easing)
+    }
+}
+
+/// Parses a hex colour of the form `#rrggbb` (with the `#` being optional) into RGB components. 
+pub fn parse_hex(s: &str) -> Option<[u8; 3]> {
+    let hex = s.strip_prefix('#').unwrap_or(s);
+    if hex.len() != 6 {
+        return None
+    }
+    let channel = |i: usize| hex
+        .get(i..i + 2)
+        .and_then(|x| u8::from_str_radix(x, 16).ok());
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// Converts a terminal colour to RGB components, using the xterm defaults for the standard ANSI colours. 
+/// Returns `None` for [`Colour::Reset`], which has no inherent value. 
+pub(crate) fn to_rgb(colour: Colour) -> Option<[u8; 3]> {
+    let rgb = match colour {
+        Colour::Reset => return None, 
+        Colour::Indexed(i) => ansi_value_to_rgb(i), 
+        Colour::Rgb{ r, g, b } => [r, g, b], 
+    };
+    Some(rgb)
+}
+
+/// How RGB colours are reduced to the xterm 256-colour palette, for terminals without true colour, as given
+/// by `--indexed` and `--dither`. Colours other than RGB ones are kept. 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Indexing {
+    /// Colours are kept as RGB. 
+    #[default]
+    None, 
+    /// Each colour is replaced by the nearest entry of the palette. 
+    Nearest, 
+    /// Each colour is replaced by the nearest entry of the palette and another on the far side of it, 
+    /// alternated cell by cell in proportion to how near each is, such that gradients look smoother. 
+    Dither, 
+}
+
+impl Indexing {
+    /// Gets the colour drawn in place of a colour, along with the colour it's dithered with and the number of
+    /// cells out of every four drawn in the latter, if any. 
+    fn reduce(self, colour: Colour) -> (Colour, Option<(Colour, u8)>) {
+        let Colour::Rgb{ r, g, b } = colour else {
+            return (colour, None)
+        };
+        if self == Indexing::None {
+            return (colour, None)
+        }
+        let rgb = [r, g, b].map(f64::from);
+        let distance = |a: [f64; 3], b: [f64; 3]| {
+            a.iter().zip(b).map(|(a, b)| (a - b).powi(2)).sum::<f64>().sqrt()
+        };
+        // the 16 standard colours are skipped, since terminals commonly redefine them
+        let nearest = |to: [f64; 3], except: Option<u8>| (16..=u8::MAX)
+            .filter(|&i| Some(i) != except)
+            .map(|i| (i, ansi_value_to_rgb(i).map(f64::from)))
+            .min_by(|&(_, a), &(_, b)| distance(a, to).total_cmp(&distance(b, to)))
+            .expect("the palette isn't empty");
+        let (index, entry) = nearest(rgb, None);
+        if self == Indexing::Nearest {
+            return (Colour::Indexed(index), None)
+        }
+        // the colour lies between the nearest entry and the one nearest to its reflection through the colour
+        let reflection = std::array::from_fn(|i| (2.0 * rgb[i] - entry[i]).clamp(0.0, 255.0));
+        let (alternate, far) = nearest(reflection, Some(index));
+        let (near, far) = (distance(entry, rgb), distance(far, rgb));
+        let level = match near + far > 0.0 {
+            true => (4.0 * near / (near + far)).round() as u8, 
+            false => 0, 
+        };
+        (Colour::Indexed(index), Some((Colour::Indexed(alternate), level)))
+    }
+}
+
+/// Converts an index into the xterm 256-colour table to RGB components. 
+fn ansi_value_to_rgb(i: u8) -> [u8; 3] {
+    const STANDARD: [[u8; 3]; 16] = [
+        [0, 0, 0], [205, 0, 0], [0, 205, 0], [205, 205, 0], 
+        [0, 0, 238], [205, 0, 205], [0, 205, 205], [229, 229, 229], 
+        [127, 127, 127], [255, 0, 0], [0, 255, 0], [255, 255, 0], 
+        [92, 92, 255], [255, 0, 255], [0, 255, 255], [255, 255, 255], 
+    ];
+    match i {
+        0..16 => STANDARD[i as usize], 
+        16..232 => {
+            let i = i - 16;
+            let level = |x: u8| if x == 0 { 0 } else { 55 + 40 * x };
+            [level(i / 36), level(i / 6 % 6), level(i % 6)]
+        }
+        232.. => {
+            let level = 8 + 10 * (i - 232);
+            [level; 3]
         }
     }
 }
@@ -0,0 +1,354 @@
+//! Support for terminals with limited escape sequence support: legacy Windows consoles (i.e. conhost without
+//! virtual terminal processing), which don't parse escape sequences, nor draw RGB colours, and terminal
+//! multiplexers (see [`Multiplexer`]). 
+//!
+//! On legacy consoles, frames are drawn by [`Console`], which issues crossterm commands rather than writing
+//! escape sequences, such that crossterm executes them through the Windows API. Colours are reduced to the 16
+//! standard ones beforehand (see [`Settings::ansi`]). 
+
+use std::{
+    env, 
+    fmt::Display, 
+    io::{self, BufWriter, Stdout, Write}, 
+    time::{Duration, Instant}, 
+};
+use crossterm::{
+    cursor::{MoveDown, MoveTo, MoveToColumn, RestorePosition}, 
+    style::{Color as CrosstermColour, Print, ResetColor, SetBackgroundColor, SetForegroundColor}, 
+    terminal::{Clear, ClearType}, 
+    Command, QueueableCommand, 
+};
+use unicode_width::UnicodeWidthChar;
+use crate::{
+    colour::{Colour, Lut, Swatch}, 
+    render::{half_block, padded, symbol, Layout, Renderer}, 
+    state::CellOverlay, 
+    viewport::Minimap, 
+};
+#[cfg(doc)]
+use crate::{render::Terminal, Settings};
+
+/// Whether the terminal is a legacy Windows console, which doesn't support escape sequences. Always `false`
+/// on other platforms. 
+pub fn is_legacy() -> bool {
+    #[cfg(windows)]
+    return !crossterm::ansi_support::supports_ansi();
+    #[cfg(not(windows))]
+    return false;
+}
+
+/// Terminal multiplexer the program runs inside, which sits between the program and the terminal and only
+/// passes on what it supports itself. Inside one, colours are reduced to the 256-colour palette unless the
+/// terminal declares true colour support (see [`true_colour`]), and the terminal isn't queried for its
+/// capabilities, since the multiplexer may leave queries unanswered or their responses garbled. 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Multiplexer {
+    Tmux, 
+    Screen, 
+}
+
+impl Multiplexer {
+    /// Detects the multiplexer from the environment variables it sets, if running inside one. 
+    pub fn detect() -> Option<Multiplexer> {
+        let term = env::var("TERM").unwrap_or_default();
+        match (env::var_os("TMUX"), env::var_os("STY")) {
+            (Some(_), _) => Some(Multiplexer::Tmux), 
+            (None, Some(_)) => Some(Multiplexer::Screen), 
+            // e.g. over SSH from inside one, where only the terminal type is passed on
+            (None, None) if term.starts_with("tmux") => Some(Multiplexer::Tmux), 
+            (None, None) if term.starts_with("screen") => Some(Multiplexer::Screen), 
+            (None, None) => None, 
+        }
+    }
+}
+
+/// Whether the terminal declares support for RGB colours (via `COLORTERM`), which multiplexers configured to
+/// pass them on set too. 
+pub fn true_colour() -> bool {
+    matches!(env::var("COLORTERM").as_deref(), Ok("truecolor" | "24bit"))
+}
+
+/// Renders to a legacy Windows console, issuing a crossterm command for each move of the cursor and change of
+/// colour. Unlike [`Terminal`], frames are drawn as they're rendered rather than on a separate thread, since
+/// each command is a call into the console anyway, and colours are only set again once they change. 
+pub struct Console {
+    writer: BufWriter<Stdout>, 
+    /// Whether cells are drawn as characters rather than colours (see [`symbol`]). 
+    symbols: bool, 
+    /// How the maze is laid out in cells. 
+    layout: Layout, 
+    /// Number of columns cells as laid out by default are wide (see [`Settings::pitch`]). 
+    pitch: usize, 
+    /// Whether frames are drawn at the saved cursor position rather than the top left (see
+    /// [`Settings::inline`]). 
+    inline: bool, 
+    /// Console column and row the top left of the frame is drawn at, unless drawn inline. 
+    origin: (u16, u16), 
+    /// Foreground and background colour text is printed in. 
+    colours: (Colour, Colour), 
+    /// Number of columns and rows of cells drawn, such that a panel may be drawn beside them. 
+    cells: (usize, usize), 
+    /// Whether a panel was drawn in the frame being drawn, and in the previous one. 
+    panel: (bool, bool), 
+    /// Time the frame being drawn was started. 
+    started: Instant, 
+    /// Time taken drawing the latest frame presented. 
+    write_time: Duration, 
+    /// Error of the first command that failed, returned by [`Console::finish`]. 
+    error: Option<io::Error>, 
+}
+
+impl Console {
+    /// Starts drawing to stdout. 
+    pub fn new(inline: bool) -> Console {
+        let mut console = Console {
+            writer: BufWriter::new(io::stdout()), 
+            symbols: false, 
+            layout: Layout::default(), 
+            pitch: 2, 
+            inline, 
+            origin: (0, 0), 
+            colours: (Colour::Reset, Colour::Reset), 
+            cells: (0, 0), 
+            panel: (false, false), 
+            started: Instant::now(), 
+            write_time: Duration::ZERO, 
+            error: None, 
+        };
+        // the colours the console was left in are unknown
+        console.queue(ResetColor);
+        console
+    }
+
+    /// Draws cells as characters rather than colours (see [`symbol`]). 
+    pub fn with_symbols(self, symbols: bool) -> Console {
+        Console{ symbols, ..self }
+    }
+
+    /// Draws cells as laid out by default a number of columns wide (see [`Settings::pitch`]). 
+    pub fn with_pitch(self, pitch: usize) -> Console {
+        Console{ pitch, ..self }
+    }
+
+    /// Lays out the maze in cells as given (see [`Layout`]). 
+    pub fn with_layout(self, layout: Layout) -> Console {
+        Console{ layout, ..self }
+    }
+
+    /// Draws frames with their top left at a console column and row, e.g. such that they're centred. Ignored
+    /// when drawn inline, since frames then start at the saved cursor position. 
+    pub fn with_origin(self, origin: (u16, u16)) -> Console {
+        match self.inline {
+            true => self, 
+            false => Console{ origin, ..self }, 
+        }
+    }
+
+    /// Resets the colours, failing if any command drawing the frames did. 
+    pub fn finish(mut self) -> io::Result<()> {
+        self.queue(ResetColor);
+        let flushed = self.writer.flush();
+        match self.error.take() {
+            Some(e) => Err(e), 
+            None => flushed, 
+        }
+    }
+
+    /// Number of columns each cell is wide. 
+    fn columns(&self) -> usize {
+        self.layout.columns(self.pitch)
+    }
+
+    /// Queues a command, keeping the error if it's the first to fail. 
+    fn queue(&mut self, command: impl Command) {
+        if let Err(e) = self.writer.queue(command) {
+            self.error.get_or_insert(e);
+        }
+    }
+
+    /// Moves the cursor to a column and row, counted from the top left of the frame. 
+    fn move_to(&mut self, column: usize, row: usize) {
+        let (column, row) = (column as u16, row as u16);
+        match self.inline {
+            true => {
+                self.queue(RestorePosition);
+                if row > 0 {
+                    self.queue(MoveDown(row));
+                }
+                self.queue(MoveToColumn(column));
+            }, 
+            false => self.queue(MoveTo(self.origin.0 + column, self.origin.1 + row)), 
+        }
+    }
+
+    /// Sets the foreground and background colour text is printed in, issuing commands only for those that
+    /// changed. 
+    fn set_colours(&mut self, foreground: Colour, background: Colour) {
+        if foreground != self.colours.0 {
+            self.queue(SetForegroundColor(foreground.into()));
+        }
+        if background != self.colours.1 {
+            self.queue(SetBackgroundColor(background.into()));
+        }
+        self.colours = (foreground, background);
+    }
+
+    /// Prints text in a foreground and background colour, or in the default colours if cells are drawn as
+    /// characters. 
+    fn print(&mut self, text: impl Display, foreground: Colour, background: Colour) {
+        match self.symbols {
+            true => self.set_colours(Colour::Reset, Colour::Reset), 
+            false => self.set_colours(foreground, background), 
+        }
+        self.queue(Print(text));
+    }
+
+    /// Starts a cell, moving to the start of its row if it's the first of it. 
+    fn start_cell(&mut self, x: usize, y: usize) {
+        if x == 0 {
+            if y == 0 {
+                self.started = Instant::now();
+            }
+            self.move_to(0, y);
+        }
+        self.cells = (self.cells.0.max(x + 1), self.cells.1.max(y + 1));
+    }
+
+    /// Writes the lines of a panel beside the cells, one per row from the top cut off at a number of columns,
+    /// and clears the rest of each row, such that shorter lines replace longer ones. 
+    fn write_panel(&mut self, lines: &[String], room: usize) {
+        let (columns, rows) = self.cells;
+        // the panel is set two columns apart from the cells
+        let column = self.columns() * columns + 2;
+        for row in 0..rows {
+            self.move_to(column, row);
+            let line: String = lines.get(row).map_or("", String::as_str).chars().take(room).collect();
+            self.print(line, Colour::Reset, Colour::Reset);
+            self.queue(Clear(ClearType::UntilNewLine));
+        }
+    }
+}
+
+impl Renderer for Console {
+    fn size(&self) -> Option<(usize, usize)> {
+        // cells start at the origin
+        let (columns, rows) = crossterm::terminal::size().ok()?;
+        let (columns, rows) = (columns.saturating_sub(self.origin.0), rows.saturating_sub(self.origin.1));
+        Some((columns as usize / self.columns(), rows as usize))
+    }
+
+    fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        if layout != self.layout {
+            self.layout = layout;
+            // the previous frame may have covered more of the console
+            self.move_to(0, 0);
+            self.set_colours(Colour::Reset, Colour::Reset);
+            self.queue(Clear(ClearType::FromCursorDown));
+        }
+    }
+
+    fn draw_cell(&mut self, x: usize, y: usize, swatch: Swatch, overlay: Option<CellOverlay>, colours: &Lut) {
+        self.start_cell(x, y);
+        let columns = self.columns();
+        // glyphs too wide for the cell are left out
+        let overlay = overlay.filter(|overlay| overlay.glyph.width().unwrap_or(1) <= columns);
+        let text = match overlay {
+            Some(overlay) => padded(overlay.glyph, columns).unwrap_or_default(), 
+            // cells a single column wide draw symbols once rather than twice
+            None if self.symbols => symbol(swatch).chars().take(columns).collect(), 
+            // the cell contents are only as narrow for cells as laid out by default
+            None if self.layout == Layout::Cells => colours.cell().to_owned(), 
+            None => " ".to_owned(), 
+        };
+        let foreground = overlay.map_or(Colour::Reset, CellOverlay::foreground);
+        self.print(text, foreground, colours.colour(swatch));
+    }
+
+    fn draw_halves(&mut self, x: usize, y: usize, halves: [(Swatch, &Lut); 2]) {
+        self.start_cell(x, y);
+        let [(top, top_colours), (bottom, bottom_colours)] = halves;
+        let glyph = match self.symbols {
+            true => half_block(top, bottom), 
+            false => '▀', 
+        };
+        self.print(glyph, top_colours.colour(top), bottom_colours.colour(bottom));
+    }
+
+    fn draw_dots(&mut self, x: usize, y: usize, dots: u8, swatch: Swatch, colours: &Lut) {
+        self.start_cell(x, y);
+        let glyph = char::from_u32(0x2800 + dots as u32).expect("braille patterns are valid characters");
+        self.print(glyph, colours.colour(swatch), colours.colour(Swatch::Wall));
+    }
+
+    fn draw_status(&mut self, status: &str) {
+        self.move_to(0, self.cells.1);
+        self.print(status, Colour::Reset, Colour::Reset);
+        // clear the rest of the line, since the previous status may have been longer
+        self.queue(Clear(ClearType::UntilNewLine));
+    }
+
+    /// Draws a panel as [`Terminal`] does, with lines cut off at the edge of the console. The panel is skipped
+    /// if the cells leave no room for it. 
+    fn draw_panel(&mut self, lines: &[String]) {
+        let (columns, _) = crossterm::terminal::size().unwrap_or((u16::MAX, 0));
+        let cells = self.columns() * self.cells.0;
+        let room = (columns as usize).saturating_sub(self.origin.0 as usize + cells + 2);
+        if room == 0 {
+            return
+        }
+        self.write_panel(lines, room);
+        self.panel.0 = true;
+    }
+
+    fn draw_minimap(&mut self, minimap: &Minimap, colours: &Lut) {
+        let Some(column) = (self.columns() * self.cells.0).checked_sub(minimap.width()) else {
+            return
+        };
+        // drawn upon walls, such that the minimap stands out from the cells around it
+        let background = colours.colour(Swatch::Wall);
+        for (row, glyphs) in minimap.rows.iter().take(self.cells.1).enumerate() {
+            self.move_to(column, row);
+            for &(glyph, swatch) in glyphs {
+                self.print(glyph, colours.colour(swatch), background);
+            }
+        }
+    }
+
+    /// Flushes the commands drawing the frame. Fails if any command has failed, whose error is then returned
+    /// by [`Console::finish`]. 
+    fn present(&mut self) -> io::Result<()> {
+        // a panel no longer drawn (e.g. once toggled off) is cleared, since the cells don't cover it
+        let (drawn, shown) = std::mem::take(&mut self.panel);
+        if shown && !drawn {
+            self.write_panel(&[], 0);
+        }
+        self.panel.1 = drawn;
+        self.cells = (0, 0);
+        if let Err(e) = self.writer.flush() {
+            self.error.get_or_insert(e);
+        }
+        self.write_time = self.started.elapsed();
+        match &self.error {
+            Some(e) => Err(e.kind().into()), 
+            None => Ok(()), 
+        }
+    }
+
+    fn write_time(&self) -> Duration {
+        self.write_time
+    }
+}
+
+impl From<Colour> for CrosstermColour {
+    fn from(colour: Colour) -> CrosstermColour {
+        match colour {
+            Colour::Reset => CrosstermColour::Reset, 
+            Colour::Indexed(i) => CrosstermColour::AnsiValue(i), 
+            Colour::Rgb{ r, g, b } => CrosstermColour::Rgb{ r, g, b }, 
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Tracing the longest path through a generated maze, showing how long its solve could be at most (see
+//! [`Maze::diameter`](crate::maze::Maze::diameter)). 
+
+use crate::{
+    generate::{Phase, State}, 
+    maze::{Node, NodeBits}, 
+    Animation, Error, Signal, 
+};
+
+/// Number of timesteps the path takes to be traced, regardless of its length. 
+const STEPS: usize = 60;
+
+/// Plays an animation tracing the longest path through the generated maze from one end to the other, drawing
+/// it in the special colour until the phase ends. 
+pub fn trace(state: &mut State) -> Result<(), Error> {
+    // themes leave the special colour of the generate phase unset, so the path borrows that of the solve phase
+    if state.colours.palette.special.is_none() {
+        let palette = state.colours.palette.with_maybe_special(state.settings.theme.solve.special);
+        state.colours = palette.into_lut(&state.settings);
+    }
+    state.phase.diameter = Some(NodeBits::new(&state.maze));
+    Trace::run(state)?;
+    Ok(())
+}
+
+/// Animation marking the nodes along the path a few at a time, relighting them without reporting them as
+/// visited again. 
+struct Trace {
+    /// Nodes along the path not yet traced, from the far end. 
+    path: Vec<Node>, 
+    /// Number of nodes traced per timestep. 
+    chunk: usize, 
+}
+
+impl Animation for Trace {
+    type Phase = Phase;
+
+    fn new(state: &mut State) -> Trace {
+        let mut path = state.maze.diameter();
+        path.reverse();
+        let chunk = path.len().div_ceil(STEPS);
+        Trace {
+            path, 
+            chunk, 
+        }
+    }
+
+    fn step(&mut self, state: &mut State) -> Signal {
+        if self.path.is_empty() {
+            return Signal::Done
+        }
+        let Some(diameter) = &mut state.phase.diameter else {
+            return Signal::Done
+        };
+        for node in self.path.split_off(self.path.len().saturating_sub(self.chunk)) {
+            diameter.set(node, true);
+            state.visited_at.set(node, state.time);
+        }
+        Signal::Continue
+    }
+
+    fn steps_per_frame(&self, _state: &State) -> u32 {
+        // the path is traced in a fixed number of timesteps, independently of the generator
+        1
+    }
+
+    fn finishes_phase(&self) -> bool {
+        false
+    }
+
+    fn heads(&self) -> Vec<Node> {
+        self.path.last().copied().into_iter().collect()
+    }
+}
@@ -0,0 +1,186 @@
+//! Race of two users on the same keyboard, each through their own copy of the same maze, as played by
+//! `overlook duel`. The copies are tiled side by side into a single maze, with a column masked out between
+//! them (see [`State::mask`](crate::state::State::mask)), such that both are drawn as one frame. 
+
+use std::fmt;
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::{rngs::StdRng, SeedableRng};
+use crate::{
+    game::{self, Player}, 
+    maze::{Direction, Maze, Node, NodeBits}, 
+    solve, 
+    state::{self, CellOverlay, Marker, Visits}, 
+    Animation, Error, Settings, Signal, 
+};
+
+/// Names of the players, by the side of the copy they race through. 
+const NAMES: [&str; 2] = ["left (WASD)", "right (arrows)"];
+
+/// State for a duel: the players, and where each copy of the maze is solved from and to. 
+pub struct Phase {
+    /// The players, left and right. 
+    pub players: [Player; 2], 
+    /// Node each player starts at. 
+    pub starts: [Node; 2], 
+    /// Node each player races to. 
+    pub goals: [Node; 2], 
+}
+
+impl Phase {
+    /// Gets the index of the player who reached their goal, if either has. 
+    pub fn winner(&self) -> Option<usize> {
+        (0..2).find(|&i| self.players[i].position == self.goals[i])
+    }
+}
+
+impl state::Phase for Phase {
+    const NAME: &str = "duel";
+
+    fn special(&self, node: Node) -> bool {
+        self.starts.contains(&node) || self.goals.contains(&node)
+    }
+
+    fn marker(&self, node: Node) -> Option<Marker> {
+        match (self.starts.contains(&node), self.goals.contains(&node)) {
+            (true, _) => Some(Marker::Start), 
+            (false, true) => Some(Marker::Goal), 
+            (false, false) => None, 
+        }
+    }
+
+    fn overlay(&self, node: Node) -> Option<CellOverlay> {
+        self.players.iter().find_map(|player| player.overlay(node))
+    }
+}
+
+pub type State = state::State<Phase>;
+
+/// Constructs the state for a duel through a maze, tiling two copies of it side by side. Each copy is solved
+/// between the endpoints of the maze (see [`solve::endpoints`]). 
+pub fn state(maze: &Maze, settings: Settings) -> State {
+    let (width, height) = (maze.width, maze.height);
+    let mut tiled = Maze::new((2 * width + 1) as u16, height as u16);
+    let offsets = [0, width + 1];
+    for edge in maze.edges_iter().filter(|&e| maze.open[e]) {
+        for offset in offsets {
+            let Node(x, y) = edge.from;
+            if let Some(copy) = tiled.edge(Node(x + offset, y), edge.direction) {
+                tiled.open.set(copy, true);
+            }
+        }
+    }
+    // the column between the copies is drawn as walls
+    let mut mask = NodeBits::new(&tiled);
+    for node in tiled.nodes_iter().filter(|node| node.0 != width) {
+        mask.set(node, true);
+    }
+
+    let (start, goal) = solve::endpoints(maze);
+    let shift = |Node(x, y): Node, offset: usize| Node(x + offset, y);
+    let starts = offsets.map(|offset| shift(start, offset));
+    let goals = offsets.map(|offset| shift(goal, offset));
+    let colours = settings.theme.solve.into_lut(&settings);
+    // a third of the colour wheel apart from each other and from the youngest nodes
+    let players = [0, 1].map(|i| {
+        let colour = game::glyph_colour(&colours, &settings, 120.0 * (i + 1) as f64);
+        Player::at(&tiled, starts[i], colour)
+    });
+    State {
+        visited_at: Visits::new(&tiled), 
+        rng: StdRng::seed_from_u64(settings.seed), 
+        maze: tiled, 
+        settings, 
+        visited_count: 0, 
+        time: 0, 
+        colours, 
+        active: Vec::new(), 
+        fog: None, 
+        mask: Some(mask), 
+        phase: Phase {
+            players, 
+            starts, 
+            goals, 
+        }, 
+    }
+}
+
+/// Animation of a duel, which ends as soon as either player reaches their goal. The players move whenever
+/// their keys are pressed: WASD steers the left, and the arrow keys the right. 
+pub struct Duel;
+
+impl Animation for Duel {
+    type Phase = Phase;
+
+    fn new(_state: &mut State) -> Duel {
+        Duel
+    }
+
+    fn step(&mut self, state: &mut State) -> Signal {
+        match state.winner() {
+            Some(_) => Signal::Done, 
+            None => Signal::Continue, 
+        }
+    }
+
+    fn steps_per_frame(&self, _state: &State) -> u32 {
+        1
+    }
+
+    fn key(&mut self, state: &mut State, key: KeyEvent) -> bool {
+        let (i, direction) = match key.code {
+            KeyCode::Char('w' | 'W') => (0, Direction::North), 
+            KeyCode::Char('a' | 'A') => (0, Direction::West), 
+            KeyCode::Char('s' | 'S') => (0, Direction::South), 
+            KeyCode::Char('d' | 'D') => (0, Direction::East), 
+            _ => match game::direction(key) {
+                Some(direction) => (1, direction), 
+                None => return false, 
+            }, 
+        };
+        // several keys may be pressed before the next step notices the goal being reached, so the players
+        // stay there once reached
+        if state.winner().is_none() {
+            state.phase.players[i].go(&state.maze, direction);
+        }
+        true
+    }
+}
+
+/// Outcome of a duel, as shown once it's over (see [`play`]). 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Results {
+    /// Index of the player who reached their goal first, if either did. 
+    pub winner: Option<usize>, 
+    /// Timesteps the duel took. 
+    pub steps: usize, 
+    /// Number of moves each player made. 
+    pub moves: [usize; 2], 
+    /// Length of the shortest path from the start to the goal, in edges. 
+    pub shortest: Option<usize>, 
+}
+
+impl fmt::Display for Results {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let winner = self.winner.map_or("nobody", |i| NAMES[i]);
+        writeln!(f, "winner:  {winner}, after {} timesteps", self.steps)?;
+        for (name, moves) in NAMES.iter().zip(self.moves) {
+            writeln!(f, "{:<15} {moves} moves", format!("{name}:"))?;
+        }
+        if let Some(shortest) = self.shortest {
+            writeln!(f, "shortest path: {shortest}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Races two users through copies of a maze, animated, returning the outcome once either reached their goal. 
+pub fn play(maze: &Maze, settings: Settings) -> Result<Results, Error> {
+    let mut state = state(maze, settings);
+    let steps = Duel.play(&mut state)?;
+    Ok(Results {
+        winner: state.winner(), 
+        steps, 
+        moves: state.players.each_ref().map(|player| player.moves), 
+        shortest: solve::shortest(maze).map(|solution| solution.path.len() - 1), 
+    })
+}
@@ -0,0 +1,94 @@
+//! Smooth elevation over a maze about to be solved, as given by `--elevation` (see
+//! [`Settings::elevation`](crate::Settings::elevation)). Moving uphill costs more than a single move, which
+//! cost-aware solvers weigh up (see [`Phase::cost`](crate::solve::Phase::cost)), and nodes are drawn lighter
+//! the higher they are. 
+
+use rand::Rng;
+use crate::{
+    colour::Lut, 
+    maze::{Maze, Node, NodeBuffer}, 
+};
+
+/// Nodes between the points of the lattice the noise is interpolated between, i.e. the breadth of the hills. 
+const SCALE: usize = 8;
+
+/// Extra cost of climbing from the lowest node to the highest, in moves. 
+const CLIMB: f64 = 20.0;
+
+/// Number of shades the nodes are drawn in, from the lowest to the highest. 
+const SHADES: usize = 8;
+
+/// Difference in lightness between the lowest and the highest shade, kept subtle such that the age of nodes
+/// can still be told apart. 
+const RELIEF: f64 = 0.16;
+
+/// Elevation of each node, with the colours it's shaded in. 
+pub struct Elevation {
+    /// Elevation of each node, from 0 for the lowest to 1 for the highest. 
+    heights: NodeBuffer<f64>, 
+    /// Shades of each LUT the nodes may be drawn in, from the lowest to the highest. 
+    shades: Vec<Vec<Lut>>, 
+}
+
+impl Elevation {
+    /// Raises hills over a maze by value noise: random heights upon a coarse lattice, smoothly interpolated
+    /// between. Each of the given LUTs is shaded, such that nodes drawn in any of them may be shaded. 
+    pub fn new<'a>(maze: &Maze, rng: &mut impl Rng, luts: impl IntoIterator<Item = &'a Lut>) -> Elevation {
+        let (columns, rows) = (maze.width / SCALE + 2, maze.height / SCALE + 2);
+        let lattice: Vec<f64> = (0..columns * rows).map(|_| rng.r#gen()).collect();
+        let at = |x: usize, y: usize| lattice[y * columns + x];
+
+        let mut heights = NodeBuffer::new(maze);
+        for node in maze.nodes_iter() {
+            let Node(x, y) = node;
+            let (column, row) = (x / SCALE, y / SCALE);
+            let tx = smoothstep((x % SCALE) as f64 / SCALE as f64);
+            let ty = smoothstep((y % SCALE) as f64 / SCALE as f64);
+            let top = at(column, row) + (at(column + 1, row) - at(column, row)) * tx;
+            let bottom = at(column, row + 1) + (at(column + 1, row + 1) - at(column, row + 1)) * tx;
+            heights[node] = top + (bottom - top) * ty;
+        }
+
+        // stretched to span the full range, such that every maze has its valleys and peaks
+        let low = heights.iter().copied().fold(f64::INFINITY, f64::min);
+        let high = heights.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        for height in heights.iter_mut() {
+            *height = match high > low {
+                true => (*height - low) / (high - low), 
+                false => 0.0, 
+            };
+        }
+
+        let shades = luts
+            .into_iter()
+            .map(|lut| {
+                (0..SHADES)
+                    .map(|i| lut.shade_l(RELIEF * (i as f64 / (SHADES - 1) as f64 - 0.5)))
+                    .collect()
+            })
+            .collect();
+        Elevation{ heights, shades }
+    }
+
+    /// Gets the elevation of a node, from 0 for the lowest to 1 for the highest. 
+    pub fn height(&self, node: Node) -> f64 {
+        self.heights[node]
+    }
+
+    /// Gets the cost of moving from one node to another: a single move, plus more for climbing uphill. 
+    pub fn cost(&self, from: Node, to: Node) -> usize {
+        let climb = (self.heights[to] - self.heights[from]).max(0.0);
+        1 + (CLIMB * climb).round() as usize
+    }
+
+    /// Gets the colours a node is drawn in: the shade for its elevation of one of the LUTs given, by index. 
+    pub(crate) fn colours(&self, lut: usize, node: Node) -> Option<&Lut> {
+        let shade = (self.heights[node] * SHADES as f64) as usize;
+        self.shades.get(lut)?.get(shade.min(SHADES - 1))
+    }
+}
+
+/// Eases a fraction in and out, such that the noise has no creases at the points of the lattice. 
+fn smoothstep(t: f64) -> f64 {
+    t * t * (3.0 - 2.0 * t)
+}
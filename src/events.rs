@@ -0,0 +1,66 @@
+use std::{fmt, sync::Arc};
+use crate::maze::{Edge, Node};
+
+/// Something that happened while running an animation, as reported to [`Hooks`]. 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// A node was visited for the first time (or again after being unvisited). 
+    NodeVisited(Node), 
+    /// An edge was opened. 
+    EdgeOpened(Edge), 
+    /// An edge was closed again, e.g. when Wilson's algorithm erases a loop. 
+    EdgeClosed(Edge), 
+    /// The goal node was visited by a solver. 
+    GoalReached(Node), 
+    /// The user moved their player to a node at a timestep (see [`Race`](crate::game::Race)), such that their
+    /// moves may be replayed in time. 
+    PlayerMoved{ node: Node, time: i64 }, 
+    /// An animation concluding a phase finished, with the name of the phase (see
+    /// [`Phase::NAME`](crate::state::Phase::NAME)). 
+    PhaseFinished(&'static str), 
+}
+
+impl fmt::Display for Event {
+    /// Formats the event as a line of plain text, e.g. `open 3 4 3 5`, such that runs may be recorded and
+    /// compared. 
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Event::NodeVisited(Node(x, y)) => write!(f, "visit {x} {y}"), 
+            Event::EdgeOpened(Edge{ from, to, .. }) => write!(f, "open {} {} {} {}", from.0, from.1, to.0, to.1), 
+            Event::EdgeClosed(Edge{ from, to, .. }) => write!(f, "close {} {} {} {}", from.0, from.1, to.0, to.1), 
+            Event::GoalReached(Node(x, y)) => write!(f, "goal {x} {y}"), 
+            Event::PlayerMoved{ node: Node(x, y), time } => write!(f, "move {time} {x} {y}"), 
+            Event::PhaseFinished(phase) => write!(f, "finish {phase}"), 
+        }
+    }
+}
+
+/// Callback observing events. 
+pub type Hook = Arc<dyn Fn(&Event) + Send + Sync>;
+
+/// Callbacks observing the [`Event`]s of animations, carried in [`Settings::hooks`](crate::Settings::hooks). 
+///
+/// Hooks are called synchronously from within the algorithms, so they should be quick. Since they're shared
+/// between states, any state they keep must be behind interior mutability (e.g. atomics or a mutex). 
+#[derive(Clone, Default)]
+pub struct Hooks(Vec<Hook>);
+
+impl Hooks {
+    /// Registers a callback to be called for every event. 
+    pub fn register(&mut self, hook: impl Fn(&Event) + Send + Sync + 'static) {
+        self.0.push(Arc::new(hook));
+    }
+
+    /// Reports an event to all callbacks. 
+    pub fn emit(&self, event: Event) {
+        for hook in &self.0 {
+            hook(&event);
+        }
+    }
+}
+
+impl fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Hooks({})", self.0.len())
+    }
+}
@@ -0,0 +1,24 @@
+use std::io::{self, Write};
+use crate::{
+    frame::Frame, 
+    state::{Phase, State}, 
+};
+
+/// Side length in pixels of each cell. 
+const SCALE: usize = 8;
+
+/// Writes the maze as a (single-frame) GIF image. 
+pub fn write<T: Phase>(state: &State<T>, out: &mut impl Write) -> io::Result<()> {
+    let (width, height, mut pixels) = super::rasterise(&Frame::capture(state), SCALE);
+    // GIF dimensions are 16-bit
+    let dimension = |x: usize| u16::try_from(x).map_err(|_| io::Error::new(
+        io::ErrorKind::InvalidInput, 
+        format!("image of {width}x{height} pixels is too large for a GIF (at most 65535 pixels across)"), 
+    ));
+    let (width, height) = (dimension(width)?, dimension(height)?);
+    let frame = gif::Frame::from_rgba_speed(width, height, &mut pixels, 10);
+
+    gif::Encoder::new(out, width, height, &[])
+        .and_then(|mut encoder| encoder.write_frame(&frame))
+        .map_err(super::encoding_error)
+}
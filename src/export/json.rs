@@ -0,0 +1,8 @@
+use std::io::{self, Write};
+use crate::state::{Phase, State};
+
+/// Writes the maze as a JSON object containing its dimensions and a list of its open edges. 
+pub fn write<T: Phase>(state: &State<T>, out: &mut impl Write) -> io::Result<()> {
+    serde_json::to_writer(&mut *out, &state.maze)?;
+    writeln!(out)
+}
@@ -0,0 +1,249 @@
+use std::{
+    fs::File, 
+    io::{self, BufWriter, Write}, 
+    path::{Path, PathBuf}, 
+    str::FromStr, 
+};
+use clap::ValueEnum;
+use crate::{
+    frame::Frame, 
+    solve::Solution, 
+    state::{Phase, State}, 
+    Error, 
+};
+
+mod gif;
+#[cfg(feature = "serde")]
+mod json;
+mod png;
+mod svg;
+mod txt;
+
+/// File format of an export. 
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Png, 
+    Gif, 
+    Svg, 
+    Txt, 
+    #[cfg(feature = "serde")]
+    Json, 
+}
+
+impl Format {
+    /// Infers the format from the extension of a path. 
+    pub fn from_path(path: &Path) -> Option<Format> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        let format = match extension.as_str() {
+            "png" => Format::Png, 
+            "gif" => Format::Gif, 
+            "svg" => Format::Svg, 
+            "txt" => Format::Txt, 
+            #[cfg(feature = "serde")]
+            "json" => Format::Json, 
+            _ => return None, 
+        };
+        Some(format)
+    }
+
+    /// Gets the names of the formats, as given by `--output-format`. 
+    fn names() -> String {
+        let names: Vec<_> = Format::value_variants()
+            .iter()
+            .filter_map(ValueEnum::to_possible_value)
+            .map(|value| value.get_name().to_owned())
+            .collect();
+        names.join(", ")
+    }
+}
+
+/// Where an export is written. 
+#[derive(Clone, Debug)]
+pub enum Target {
+    Stdout, 
+    File(PathBuf), 
+}
+
+impl Target {
+    /// Opens the target for writing. 
+    pub fn open(&self) -> io::Result<Box<dyn Write>> {
+        let writer: Box<dyn Write> = match self {
+            Target::Stdout => Box::new(io::stdout().lock()), 
+            Target::File(path) => Box::new(File::create(path)?), 
+        };
+        Ok(Box::new(BufWriter::new(writer)))
+    }
+
+    /// Resolves the format written to the target: the given one if any, and otherwise that inferred from the
+    /// extension of the path, or plain text for stdout. Fails if there's neither. 
+    pub fn with_format(self, format: Option<Format>) -> Result<Output, Error> {
+        let format = match (format, &self) {
+            (Some(format), _) => format, 
+            (None, Target::Stdout) => Format::Txt, 
+            (None, Target::File(path)) => Format::from_path(path).ok_or_else(|| {
+                let expected = Format::names();
+                let path = path.display();
+                Error::InvalidArguments(format!("can't infer the format of {path} (expected one of {expected})"))
+            })?, 
+        };
+        Ok(Output{ target: self, format })
+    }
+}
+
+impl FromStr for Target {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Target, String> {
+        match s {
+            "-" => Ok(Target::Stdout), 
+            _ => Ok(Target::File(PathBuf::from(s))), 
+        }
+    }
+}
+
+/// Destination of an export, as given by `--output` and resolved by [`Target::with_format`]. 
+///
+/// This is shared by all exporters: the format is inferred from the extension of the path unless given, and
+/// `-` may be given to write to stdout (as plain text, unless the format is given). 
+#[derive(Clone, Debug)]
+pub struct Output {
+    pub target: Target, 
+    pub format: Format, 
+}
+
+impl Output {
+    /// Writes the maze of a state to the target, rendered according to the format. 
+    pub fn write<T: Phase>(&self, state: &State<T>) -> io::Result<()> {
+        let mut out = self.target.open()?;
+        match self.format {
+            Format::Png => png::write(state, &mut out), 
+            Format::Gif => gif::write(state, &mut out), 
+            Format::Svg => svg::write(state, &mut out), 
+            Format::Txt => txt::write(state, &mut out), 
+            #[cfg(feature = "serde")]
+            Format::Json => json::write(state, &mut out), 
+        }?;
+        out.flush()
+    }
+}
+
+/// File format of an exported solution. 
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolutionFormat {
+    /// A list of the nodes along the path, as `[x, y]` coordinates. 
+    #[cfg(feature = "serde")]
+    Json, 
+    /// The moves along the path as compass directions, e.g. `NNEESW`. 
+    Moves, 
+}
+
+/// Destination of an exported solution, as given by `--solution`. Like [`Output`], the format is inferred
+/// from the extension of the path: json for `.json`, and moves for anything else (including stdout). 
+#[derive(Clone, Debug)]
+pub struct SolutionOutput {
+    pub target: Target, 
+    pub format: SolutionFormat, 
+}
+
+impl SolutionOutput {
+    /// Overrides the inferred format, if given. 
+    pub fn with_format(self, format: Option<SolutionFormat>) -> SolutionOutput {
+        let format = format.unwrap_or(self.format);
+        SolutionOutput{ format, ..self }
+    }
+
+    /// Writes a solution to the target. 
+    pub fn write(&self, solution: &Solution) -> io::Result<()> {
+        let mut out = self.target.open()?;
+        match self.format {
+            #[cfg(feature = "serde")]
+            SolutionFormat::Json => serde_json::to_writer(&mut out, &solution.path)?, 
+            SolutionFormat::Moves => write!(out, "{}", solution.moves())?, 
+        };
+        writeln!(out)?;
+        out.flush()
+    }
+}
+
+impl FromStr for SolutionOutput {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<SolutionOutput, String> {
+        let target = s.parse()?;
+        let format = match &target {
+            #[cfg(feature = "serde")]
+            Target::File(path) if Format::from_path(path) == Some(Format::Json) => SolutionFormat::Json, 
+            _ => SolutionFormat::Moves, 
+        };
+        Ok(SolutionOutput{ target, format })
+    }
+}
+
+/// Rasterises a frame to RGBA pixels, drawing each cell as a square of `scale` pixels. Cells with the
+/// terminal's default colour are left transparent. 
+fn rasterise(frame: &Frame, scale: usize) -> (usize, usize, Vec<u8>) {
+    let (pixel_width, pixel_height) = (frame.width * scale, frame.height * scale);
+    let mut pixels = vec![0; pixel_width * pixel_height * 4];
+
+    for py in 0..pixel_height {
+        for px in 0..pixel_width {
+            let Some([r, g, b]) = frame.cell(px / scale, py / scale) else {
+                continue
+            };
+            let i = 4 * (px + py * pixel_width);
+            pixels[i..i + 4].copy_from_slice(&[r, g, b, 255]);
+        }
+    }
+    (pixel_width, pixel_height, pixels)
+}
+
+/// Converts errors of external encoders to IO errors. 
+fn encoding_error(e: impl std::error::Error + Send + Sync + 'static) -> io::Error {
+    io::Error::other(e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(target: &str, format: Option<Format>) -> Result<Format, Error> {
+        let target: Target = target.parse().unwrap();
+        target.with_format(format).map(|output| output.format)
+    }
+
+    #[test]
+    fn infers_format_from_extension() {
+        assert_eq!(format("maze.png", None).unwrap(), Format::Png);
+        assert_eq!(format("maze.GIF", None).unwrap(), Format::Gif);
+        assert_eq!(format("dir.svg/maze.txt", None).unwrap(), Format::Txt);
+        assert_eq!(format("-", None).unwrap(), Format::Txt);
+    }
+
+    #[test]
+    fn given_format_overrides_extension() {
+        assert_eq!(format("maze", Some(Format::Svg)).unwrap(), Format::Svg);
+        assert_eq!(format("maze.png", Some(Format::Gif)).unwrap(), Format::Gif);
+        assert_eq!(format("maze.bmp", Some(Format::Png)).unwrap(), Format::Png);
+        assert_eq!(format("-", Some(Format::Svg)).unwrap(), Format::Svg);
+    }
+
+    #[test]
+    fn rejects_unknown_format() {
+        for target in ["maze", "maze.bmp", "maze."] {
+            let Err(Error::InvalidArguments(message)) = format(target, None) else {
+                panic!("inferred a format for {target}")
+            };
+            assert!(message.contains("png, gif, svg, txt"), "{message}");
+            assert_eq!(message.contains("json"), cfg!(feature = "serde"), "{message}");
+        }
+    }
+
+    #[test]
+    fn infers_solution_format_from_extension() {
+        let format = |s: &str| s.parse::<SolutionOutput>().unwrap().format;
+        assert_eq!(format("-"), SolutionFormat::Moves);
+        assert_eq!(format("path.txt"), SolutionFormat::Moves);
+        #[cfg(feature = "serde")]
+        assert_eq!(format("path.json"), SolutionFormat::Json);
+    }
+}
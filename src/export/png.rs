@@ -0,0 +1,21 @@
+use std::io::{self, Write};
+use crate::{
+    frame::Frame, 
+    state::{Phase, State}, 
+};
+
+/// Side length in pixels of each cell. 
+const SCALE: usize = 8;
+
+/// Writes the maze as a PNG image. 
+pub fn write<T: Phase>(state: &State<T>, out: &mut impl Write) -> io::Result<()> {
+    let (width, height, pixels) = super::rasterise(&Frame::capture(state), SCALE);
+    let mut encoder = png::Encoder::new(out, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .map_err(super::encoding_error)
+}
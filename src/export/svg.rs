@@ -0,0 +1,26 @@
+use std::io::{self, Write};
+use crate::{
+    frame::Frame, 
+    state::{Phase, State}, 
+};
+
+/// Writes the maze as an SVG image with one square per cell, leaving cells with the terminal's default
+/// colour transparent. 
+pub fn write<T: Phase>(state: &State<T>, out: &mut impl Write) -> io::Result<()> {
+    let frame = Frame::capture(state);
+    let (width, height) = (frame.width, frame.height);
+
+    writeln!(
+        out, 
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {width} {height}" shape-rendering="crispEdges">"#, 
+    )?;
+    for y in 0..height {
+        for x in 0..width {
+            let Some([r, g, b]) = frame.cell(x, y) else {
+                continue
+            };
+            writeln!(out, r#"<rect x="{x}" y="{y}" width="1" height="1" fill="rgb({r},{g},{b})"/>"#)?;
+        }
+    }
+    writeln!(out, "</svg>")
+}
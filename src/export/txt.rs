@@ -0,0 +1,41 @@
+use std::io::{self, Write};
+use crate::{
+    maze::{Direction, Node}, 
+    state::{Phase, State}, 
+};
+
+/// Writes the maze as ASCII-art in the common `+--+` format, marking special nodes with `**`. 
+pub fn write<T: Phase>(state: &State<T>, out: &mut impl Write) -> io::Result<()> {
+    let maze = &state.maze;
+    let open = |node, direction| maze
+        .edge(node, direction)
+        .is_some_and(|e| maze.open[e]);
+
+    writeln!(out, "+{}", "--+".repeat(maze.width))?;
+
+    for y in 0..maze.height {
+        write!(out, "|")?;
+        for x in 0..maze.width {
+            let node = Node(x, y);
+            let body = match state.special(node) {
+                true => "**", 
+                false => "  ", 
+            };
+            let east = match open(node, Direction::East) {
+                true => ' ', 
+                false => '|', 
+            };
+            write!(out, "{body}{east}")?;
+        }
+        write!(out, "\n+")?;
+        for x in 0..maze.width {
+            let south = match open(Node(x, y), Direction::South) {
+                true => "  ", 
+                false => "--", 
+            };
+            write!(out, "{south}+")?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
@@ -1,15 +1,49 @@
-use std::marker::PhantomData;
+#[cfg(feature = "terminal")]
+use std::{marker::PhantomData, time::Duration};
+use clap::ValueEnum;
+#[cfg(feature = "terminal")]
+use rand::{rngs::StdRng, Rng, SeedableRng};
+#[cfg(feature = "terminal")]
 use crate::{
     colour::Palette, 
+    maze::Node, 
+    solve, 
     state::{Phase, State}, 
     Animation, Error, Signal, 
 };
 
-/// Plays an animation to flash-fade between two states. 
-pub fn flash_between<T: Phase, U: Phase>(
+/// Transition played between phases, as given by `--fade-style` (or `--transition`). 
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FadeStyle {
+    /// All nodes flash at once, then fade into the next phase. 
+    #[default]
+    Flash, 
+    /// Nodes fade into the next phase one by one, in random order. 
+    Dissolve, 
+    /// Nodes fade into the next phase from left to right. 
+    Sweep, 
+    /// Nodes fade into the next phase in a circle expanding from the start node (see [`solve::endpoints`]). 
+    #[value(help = "Nodes fade into the next phase in a circle expanding from the start node")]
+    Radial, 
+    /// Squares of nodes dissolve into the next phase like the squares of a checkerboard, those of one colour
+    /// before those of the other. 
+    Checkerboard, 
+    /// Phases follow each other immediately, and trails are aged without animating. 
+    None, 
+}
+
+/// Plays an animation to fade between two states, in the style given by
+/// [`Settings::fade`](crate::Settings::fade). 
+#[cfg(feature = "terminal")]
+pub fn between<T: Phase, U: Phase>(
     mut prev_state: State<T>,
     next: impl FnOnce(State<T>) -> State<U>, 
 ) -> Result<State<U>, Error> {
+    let style = prev_state.settings.fade;
+    if style == FadeStyle::None {
+        return Ok(next(prev_state))
+    }
+
     // small delay to make it flow better
     Fade::<T, 5>::run(&mut prev_state)?;
 
@@ -24,43 +58,115 @@ pub fn flash_between<T: Phase, U: Phase>(
         return Ok(state)
     }
     
-    // derive colours to be used in the transition
-    let flash_colours = {
-        let young = prev_palette.young;
-        let old = next_palette.unvisited.unwrap();
+    // derive colours to be used in the transition. flashes start from the youngest colour of the previous
+    // state, and the others from how its nodes look once aged
+    let transition_colours = {
+        let young = match style {
+            FadeStyle::Flash => prev_palette.young, 
+            _ => prev_palette.old, 
+        };
+        let old = next_palette.unvisited.unwrap_or(next_palette.old);
+        // keep the walls of the next state, which would otherwise be derived from the transition colours
         Palette::new(young, old)
             .with_maybe_special(next_palette.special)
             .into_lut(&state.settings)
+            .with_wall(state.colours.wall)
     };
-    state.age.fill(Some(0));
+    match style {
+        FadeStyle::Flash => state.visited_at.fill(&state.maze, state.time), 
+        _ => stagger(&mut state), 
+    }
     
     // run fade in the next state (to get special nodes coloured) but with our custom colours
     let colours = state.colours.clone();
-    state.colours = flash_colours;
+    state.colours = transition_colours;
     Fade::<U>::run(&mut state)?;
+    if style != FadeStyle::Flash {
+        // the last nodes only start fading halfway through
+        Fade::<U, 128>::run(&mut state)?;
+    }
 
     // restore and return state
-    state.age.fill(None);
+    state.visited_at.clear();
     state.colours = colours;
     Ok(state)
 }
 
-/// Plays an animation to fully age all nodes. 
+/// Plays an animation to fully age all nodes, or ages them at once if fades are disabled (see
+/// [`FadeStyle::None`]). 
+#[cfg(feature = "terminal")]
 pub fn out<T: Phase>(state: &mut State<T>) -> Result<(), Error> {
-    Fade::<T>::run(state)
+    match state.settings.fade {
+        FadeStyle::None => state.time += state.settings.trail as i64, 
+        _ => {
+            Fade::<T>::run(state)?;
+        }, 
+    }
+    Ok(())
+}
+
+/// Marks all nodes as visited at staggered times within the first half of a fade, such that each starts
+/// fading at its own time as given by the style (see [`FadeStyle`]). Nodes visited in the future are drawn
+/// as the youngest, i.e. as before the fade. 
+#[cfg(feature = "terminal")]
+fn stagger<T: Phase>(state: &mut State<T>) {
+    /// Width and height of the squares of [`FadeStyle::Checkerboard`], in nodes. 
+    const SQUARE: usize = 4;
+
+    let spread = state.settings.trail as i64 / 2;
+    let width = state.maze.width as i64;
+    // seeded separately, such that the algorithms draw the same numbers regardless of the fade
+    let mut rng = StdRng::seed_from_u64(state.settings.seed);
+    let (start, _) = solve::endpoints(&state.maze);
+    let radius = {
+        let (top_left, bottom_right) = state.maze.bounds();
+        let corners = [top_left, Node(top_left.0, bottom_right.1), Node(bottom_right.0, top_left.1), bottom_right];
+        corners.map(|corner| distance(start, corner)).into_iter().fold(1.0, f64::max)
+    };
+
+    for node in state.maze.nodes_iter() {
+        let offset = match state.settings.fade {
+            FadeStyle::Sweep => node.0 as i64 * spread / width, 
+            FadeStyle::Radial => (distance(start, node) / radius * spread as f64) as i64, 
+            FadeStyle::Checkerboard => {
+                let colour = (node.0 / SQUARE + node.1 / SQUARE) as i64 % 2;
+                (colour * spread + rng.gen_range(0..=spread)) / 2
+            }, 
+            _ => rng.gen_range(0..=spread), 
+        };
+        state.visited_at.set(node, state.time + offset);
+    }
 }
 
+/// Gets the straight-line distance between two nodes, in nodes. 
+#[cfg(feature = "terminal")]
+fn distance(a: Node, b: Node) -> f64 {
+    f64::hypot(a.0 as f64 - b.0 as f64, a.1 as f64 - b.1 as f64)
+}
+
+/// Animation doing nothing but aging nodes for `STEPS` timesteps (scaled by [`Settings::trail`](crate::Settings::trail) to age
+/// nodes equally far in the same time). 
+#[cfg(feature = "terminal")]
 struct Fade<T, const STEPS: u8 = 255> {
-    steps: u8, 
+    steps: u32, 
+    timescale: u32, 
+    /// Delay between timesteps, overriding the timescale if the duration of fades is set (see
+    /// [`Settings::fade_duration`](crate::Settings::fade_duration)). 
+    delay: Option<Duration>, 
     _phase: PhantomData<T>,
 }
 
+#[cfg(feature = "terminal")]
 impl<T: Phase, const STEPS: u8> Animation for Fade<T, STEPS> {
     type Phase = T;
 
-    fn new(_state: &mut State<T>) -> Self {
+    fn new(state: &mut State<T>) -> Self {
+        let trail = state.settings.trail;
         Fade {
-            steps: STEPS, 
+            steps: (STEPS as u32 * trail).div_ceil(u8::MAX as u32), 
+            timescale: u32::max(350 * trail / u8::MAX as u32, 1), 
+            // a full fade takes `trail` timesteps
+            delay: state.settings.fade_duration.map(|duration| duration / trail.max(1)), 
             _phase: PhantomData, 
         }
     }
@@ -76,6 +182,19 @@ impl<T: Phase, const STEPS: u8> Animation for Fade<T, STEPS> {
     }
 
     fn timescale(&self) -> u32 {
-        350
+        self.timescale
+    }
+
+    fn delay(&self, state: &State<T>) -> Duration {
+        self.delay.unwrap_or(100 * state.settings.delay / self.timescale)
+    }
+
+    fn steps_per_frame(&self, _state: &State<T>) -> u32 {
+        // fades are timed independently of the algorithms
+        1
+    }
+
+    fn finishes_phase(&self) -> bool {
+        false
     }
 }
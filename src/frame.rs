@@ -0,0 +1,104 @@
+use std::{fmt, io};
+use crate::{
+    colour::{Lut, Swatch}, 
+    render::Renderer, 
+    state::{CellOverlay, Phase, State}, 
+    finished, Animation, Signal, 
+};
+
+/// Snapshot of the colours of all cells of a state, as laid out on screen (see [`State::grid_size`]). 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    /// Width in cells. 
+    pub width: usize, 
+    /// Height in cells. 
+    pub height: usize, 
+    /// RGB colour of each cell in row-major order, or `None` for cells with the terminal's default colour. 
+    pub cells: Vec<Option<[u8; 3]>>, 
+}
+
+impl Frame {
+    /// Captures the current colours of a state. 
+    pub fn capture<T: Phase>(state: &State<T>) -> Frame {
+        let (width, height) = state.grid_size();
+        let mut frame = Frame{ width, height, cells: vec![None; width * height] };
+        state.draw(&mut frame);
+        frame
+    }
+
+    /// Gets the colour of a cell. 
+    pub fn cell(&self, x: usize, y: usize) -> Option<[u8; 3]> {
+        self.cells[x + y * self.width]
+    }
+}
+
+impl fmt::Display for Frame {
+    /// Formats the frame as plain text, with one line per row and each cell as its hex colour (or `......` for
+    /// the terminal's default colour), such that frames may be compared line by line. 
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.cells.chunks(self.width) {
+            let cells: Vec<String> = row
+                .iter()
+                .map(|cell| match cell {
+                    Some([r, g, b]) => format!("{r:02x}{g:02x}{b:02x}"), 
+                    None => "......".to_owned(), 
+                })
+                .collect();
+            writeln!(f, "{}", cells.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+impl Renderer for Frame {
+    fn size(&self) -> Option<(usize, usize)> {
+        Some((self.width, self.height))
+    }
+
+    fn draw_cell(&mut self, x: usize, y: usize, swatch: Swatch, _overlay: Option<CellOverlay>, colours: &Lut) {
+        self.cells[x + y * self.width] = colours.rgb(swatch);
+    }
+
+    fn present(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Iterator over the frames of an animation, as returned by [`Animation::frames`]. 
+pub struct Frames<'a, A: Animation> {
+    algorithm: A, 
+    state: &'a mut State<A::Phase>, 
+    done: bool, 
+}
+
+impl<'a, A: Animation> Frames<'a, A> {
+    pub(crate) fn new(state: &'a mut State<A::Phase>) -> Frames<'a, A> {
+        Frames {
+            algorithm: A::new(state), 
+            state, 
+            done: false, 
+        }
+    }
+}
+
+impl<A: Animation> Iterator for Frames<'_, A> {
+    type Item = Frame;
+
+    fn next(&mut self) -> Option<Frame> {
+        if self.done {
+            return None
+        }
+        match self.algorithm.step(self.state) {
+            Signal::Continue => {
+                let frame = Frame::capture(self.state);
+                self.state.step();
+                Some(frame)
+            }, 
+            Signal::Done => {
+                finished(&self.algorithm, self.state);
+                self.done = true;
+                None
+            }, 
+        }
+    }
+}
@@ -0,0 +1,432 @@
+use std::{collections::VecDeque, fmt, time::{Duration, Instant}};
+#[cfg(feature = "terminal")]
+use crossterm::event::{KeyCode, KeyEvent};
+use crate::{
+    colour::{Hsl, Lut}, 
+    events::Event, 
+    maze::{Direction, Maze, Node, NodeBits}, 
+    solve::{self, Solver, SOLVERS}, 
+    state::CellOverlay, 
+    Animation, Settings, Signal, Stepper, 
+};
+#[cfg(feature = "terminal")]
+use crate::Error;
+
+/// Time allowed for each edge of the shortest path before a solve scores fewer points (see [`Score::points`]). 
+const PAR_PER_EDGE: Duration = Duration::from_millis(300);
+
+/// Number of nodes of the way on shown by a hint (see [`Player::hint`]). 
+const HINT_NODES: usize = 5;
+
+/// Time a hint is shown for. 
+const HINT_DURATION: Duration = Duration::from_millis(1500);
+
+/// Points the first hint costs, with each hint after it costing as much more again (see [`Score::points`]). 
+const HINT_COST: u32 = 10;
+
+/// The user finding their own way through the maze, steered with the arrow keys. The player and the nodes
+/// they've been at are drawn as glyphs upon the maze (see [`solve::Phase::player`]). 
+pub struct Player {
+    /// Node the player is at. 
+    pub position: Node, 
+    /// Nodes the player has been at. 
+    pub trail: NodeBits, 
+    /// Number of moves made, including those retracing the trail. 
+    pub moves: usize, 
+    /// Number of hints taken. 
+    pub hints: usize, 
+    /// Nodes of the way on shown by the latest hint, until it's hidden again. 
+    hint: Option<(Vec<Node>, Instant)>, 
+    /// Colour of the glyphs, contrasting with the colours of the solver. 
+    colour: Hsl, 
+}
+
+impl Player {
+    /// Constructs a player at the start of the maze being solved. 
+    pub fn new(state: &solve::State) -> Player {
+        // opposite the youngest nodes in hue
+        Player::at(&state.maze, state.start, glyph_colour(&state.colours, &state.settings, 180.0))
+    }
+
+    /// Constructs a player at a node of a maze, drawn in a given colour. 
+    pub(crate) fn at(maze: &Maze, position: Node, colour: Hsl) -> Player {
+        let mut trail = NodeBits::new(maze);
+        trail.set(position, true);
+        Player {
+            position, 
+            trail, 
+            moves: 0, 
+            hints: 0, 
+            hint: None, 
+            colour, 
+        }
+    }
+
+    /// Moves the player to the neighbouring node in a direction, unless a wall (or a one-way edge against
+    /// them) is in the way. Returns whether the player moved. 
+    pub fn go(&mut self, maze: &Maze, direction: Direction) -> bool {
+        let Some(edge) = maze.edge(self.position, direction).filter(|&e| maze.is_passable(e)) else {
+            return false
+        };
+        self.position = edge.to;
+        self.trail.set(edge.to, true);
+        self.moves += 1;
+        true
+    }
+
+    /// Briefly shows the next few nodes of a path to the goal from the player (e.g. as found by
+    /// [`Phase::path_to_goal`](solve::Phase::path_to_goal)), counting the hint taken. 
+    pub fn hint(&mut self, path: Vec<Node>) {
+        let nodes = path.into_iter().skip(1).take(HINT_NODES).collect();
+        self.hint = Some((nodes, Instant::now() + HINT_DURATION));
+        self.hints += 1;
+    }
+
+    /// Hides the latest hint once it has been shown for long enough. 
+    fn expire_hint(&mut self) {
+        if self.hint.as_ref().is_some_and(|&(_, until)| Instant::now() >= until) {
+            self.hint = None;
+        }
+    }
+
+    /// Gets the glyph drawn upon a node: the player itself, the way on while a hint is shown, or a dot where
+    /// they've been. 
+    pub(crate) fn overlay(&self, node: Node) -> Option<CellOverlay> {
+        let hinted = self.hint.as_ref().is_some_and(|(nodes, _)| nodes.contains(&node));
+        let (glyph, intensity) = match (node == self.position, hinted, self.trail[node]) {
+            (true, _, _) => ('@', 1.0), 
+            (false, true, _) => ('+', 1.0), 
+            (false, false, true) => ('•', 0.8), 
+            (false, false, false) => return None, 
+        };
+        Some(CellOverlay {
+            glyph, 
+            colour: self.colour, 
+            intensity, 
+        })
+    }
+}
+
+/// Gets the colour of the glyphs of a player: the youngest colour of a LUT shifted in hue, as light as possible
+/// against the background. 
+pub(crate) fn glyph_colour(colours: &Lut, settings: &Settings, hue_shift: f64) -> Hsl {
+    let colour = colours.palette.young.shift_h(hue_shift).with_s(1.0);
+    colour.with_l(match settings.light {
+        true => 0.35, 
+        false => 0.85, 
+    })
+}
+
+/// Gets the direction an arrow key steers the player in. 
+#[cfg(feature = "terminal")]
+pub fn direction(key: KeyEvent) -> Option<Direction> {
+    match key.code {
+        KeyCode::Up => Some(Direction::North), 
+        KeyCode::Down => Some(Direction::South), 
+        KeyCode::Right => Some(Direction::East), 
+        KeyCode::Left => Some(Direction::West), 
+        _ => None, 
+    }
+}
+
+/// Who reached the goal first in a race. 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Racer {
+    Player, 
+    Solver, 
+    Ghost, 
+}
+
+/// Race of the user against a solver from the start to the goal of the same maze, which ends as soon as
+/// either of them reaches the goal. The solver searches as it would on its own, one step per timestep, while
+/// the player moves whenever an arrow key is pressed. 
+///
+/// Like [`Chase`](crate::chase::Chase), the race steps the solver itself, such that both race within the
+/// solve phase. A ghost may race along too, replaying earlier moves (see [`Race::with_ghost`]). 
+pub struct Race {
+    /// Solver racing the player, until it's done. 
+    solver: Option<Stepper<solve::Phase>>, 
+    /// Solver steering the player while the autopilot is engaged: the one raced, or A* if solving alone. 
+    pilot: Solver, 
+    /// Nodes the autopilot is yet to move the player to, while it's engaged. 
+    route: Option<VecDeque<Node>>, 
+    /// Moves the ghost is yet to replay, each at the timestep it was made. 
+    ghost: VecDeque<(i64, Node)>, 
+}
+
+impl Race {
+    /// Constructs a race against a solver. 
+    pub fn new_with(solver: Solver, state: &mut solve::State) -> Race {
+        let mut race = Race::new(state);
+        race.solver = Some(solver.stepper(state));
+        race.pilot = solver;
+        race
+    }
+
+    /// Adds a ghost to the race, replaying moves from the start at the timesteps they were made (e.g. as
+    /// recorded by [`Event::PlayerMoved`] in an earlier race). The ghost is drawn dimmer than the player (see
+    /// [`Phase::ghost`](solve::Phase::ghost)). 
+    pub fn with_ghost(mut self, moves: Vec<(i64, Node)>, state: &mut solve::State) -> Race {
+        // a third of the colour wheel away from the player
+        let colour = glyph_colour(&state.colours, &state.settings, 300.0);
+        state.phase.ghost = Some(Player::at(&state.maze, state.start, colour));
+        self.ghost = moves.into();
+        self
+    }
+
+    /// Moves the ghost on by the moves due at the current timestep. 
+    fn replay(&mut self, state: &mut solve::State) {
+        let Some(ghost) = &mut state.phase.ghost else {
+            return
+        };
+        while let Some(&(time, node)) = self.ghost.front() && time <= state.time {
+            self.ghost.pop_front();
+            // moves beyond the maze would be from another maze, and are skipped
+            if let Some(node) = state.maze.node(node.0, node.1) {
+                ghost.position = node;
+                ghost.trail.set(node, true);
+                ghost.moves += 1;
+            }
+        }
+    }
+
+    /// Hands control of the player to the solver piloting them, which finds its way from wherever they are
+    /// (see [`solve::search_from`]), or takes it back if already engaged. 
+    #[cfg(feature = "terminal")]
+    fn toggle_autopilot(&mut self, state: &mut solve::State) {
+        if self.route.take().is_some() {
+            return
+        }
+        let Some(position) = state.player.as_ref().map(|player| player.position) else {
+            return
+        };
+        let solution = solve::search_from(self.pilot, state, position);
+        self.route = solution.map(|solution| solution.path.into_iter().skip(1).collect());
+    }
+}
+
+impl Animation for Race {
+    type Phase = solve::Phase;
+
+    /// Constructs a race without an opponent, i.e. the user solving the maze on their own. 
+    fn new(state: &mut solve::State) -> Race {
+        state.phase.player = Some(Player::new(state));
+        Race {
+            solver: None, 
+            pilot: SOLVERS.find("a-star").expect("a-star is a built-in solver"), 
+            route: None, 
+            ghost: VecDeque::new(), 
+        }
+    }
+
+    fn step(&mut self, state: &mut solve::State) -> Signal {
+        // the autopilot moves the player a node per timestep, as if steering them
+        let next = self.route.as_mut().and_then(VecDeque::pop_front);
+        match (next, &mut state.phase.player) {
+            (Some(node), Some(player)) => {
+                player.position = node;
+                player.trail.set(node, true);
+                player.moves += 1;
+                state.emit(Event::PlayerMoved{ node, time: state.time });
+            }, 
+            _ => self.route = None, 
+        }
+        // the player moved in between timesteps, so they got there before the solver's next step (and before
+        // the ghost's)
+        let goal = state.goal;
+        let at_goal = |player: &Option<Player>| player.as_ref().is_some_and(|player| player.position == goal);
+        if at_goal(&state.player) {
+            return Signal::Done
+        }
+        self.replay(state);
+        if at_goal(&state.ghost) {
+            return Signal::Done
+        }
+        if let Some(player) = &mut state.phase.player {
+            player.expire_hint();
+        }
+        if let Some(solver) = &mut self.solver && let Signal::Done = solver(state) {
+            self.solver = None;
+        }
+        match state.solution() {
+            Some(_) => Signal::Done, 
+            None => Signal::Continue, 
+        }
+    }
+
+    fn steps_per_frame(&self, _state: &solve::State) -> u32 {
+        // the player moves at most once per key press, so the solver gets no more than a step per frame
+        1
+    }
+
+    #[cfg(feature = "terminal")]
+    fn key(&mut self, state: &mut solve::State, key: KeyEvent) -> bool {
+        if key.code == KeyCode::Char('a') {
+            self.toggle_autopilot(state);
+            return true
+        }
+        if key.code == KeyCode::Char('h') && let Some(player) = &state.phase.player {
+            // found afresh from wherever the player is, without disturbing the solver's search
+            let path = state.phase.path_to_goal(&state.maze, player.position);
+            if let (Some(path), Some(player)) = (path, &mut state.phase.player) {
+                player.hint(path);
+            }
+            return true
+        }
+        let (Some(direction), Some(player)) = (direction(key), &mut state.phase.player) else {
+            return false
+        };
+        // steering takes control back from the autopilot
+        self.route = None;
+        // several keys may be pressed before the next step notices the goal being reached, so the player
+        // stays there once reached
+        if player.position != state.phase.goal && player.go(&state.maze, direction) {
+            // stepping onto ice slides the player on, leaving a trail across it
+            let goal = state.phase.goal;
+            let slid = state.phase.ice
+                .as_ref()
+                .map_or_else(Vec::new, |ice| ice.slide(&state.maze, player.position, direction, goal));
+            for node in slid {
+                player.position = node;
+                player.trail.set(node, true);
+            }
+            // entering a portal moves the player straight on to its twin
+            if let Some(twin) = state.phase.portals.as_ref().and_then(|portals| portals.twin(player.position)) {
+                player.position = twin;
+                player.trail.set(twin, true);
+            }
+            let node = player.position;
+            state.emit(Event::PlayerMoved{ node, time: state.time });
+        }
+        true
+    }
+}
+
+/// Outcome of a race, as shown once it's over (see [`race`]). 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Results {
+    /// Who reached the goal first, if anyone. 
+    pub winner: Option<Racer>, 
+    /// Name of the solver raced. 
+    pub solver: &'static str, 
+    /// Timesteps the race took. 
+    pub steps: usize, 
+    /// Number of moves the player made. 
+    pub moves: usize, 
+    /// Number of hints the player took. 
+    pub hints: usize, 
+    /// Number of nodes visited by the solver. 
+    pub visited: usize, 
+    /// Length of the shortest path from the start to the goal, in edges. 
+    pub shortest: Option<usize>, 
+}
+
+impl Results {
+    /// Gets the outcome of a race run upon a state. 
+    pub fn new(state: &solve::State, solver: Solver, steps: usize) -> Results {
+        let player = state.player.as_ref();
+        let at_goal = |player: Option<&Player>| player.is_some_and(|player| player.position == state.goal);
+        let winner = match (at_goal(player), at_goal(state.ghost.as_ref()), state.solution()) {
+            (true, _, _) => Some(Racer::Player), 
+            (false, true, _) => Some(Racer::Ghost), 
+            (false, false, Some(_)) => Some(Racer::Solver), 
+            (false, false, None) => None, 
+        };
+        Results {
+            winner, 
+            solver: solver.name(), 
+            steps, 
+            moves: player.map_or(0, |player| player.moves), 
+            hints: player.map_or(0, |player| player.hints), 
+            visited: state.visited_count, 
+            shortest: solve::shortest(&state.maze).map(|solution| solution.path.len() - 1), 
+        }
+    }
+}
+
+impl fmt::Display for Results {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let winner = match self.winner {
+            Some(Racer::Player) => "you", 
+            Some(Racer::Solver) => self.solver, 
+            Some(Racer::Ghost) => "the ghost", 
+            None => "nobody", 
+        };
+        writeln!(f, "winner:  {winner}, after {} timesteps", self.steps)?;
+        write!(f, "you:     {} moves", self.moves)?;
+        if self.hints > 0 {
+            write!(f, ", {} hints", self.hints)?;
+        }
+        if let Some(shortest) = self.shortest {
+            write!(f, " (shortest path: {shortest})")?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{:<8} {} nodes visited", format!("{}:", self.solver), self.visited)
+    }
+}
+
+/// A maze solved by the user on their own, timed from when it was first drawn (see [`solo`]). 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Score {
+    /// Width and height of the maze in nodes. 
+    pub size: (usize, usize), 
+    /// Number of moves the player made. 
+    pub moves: usize, 
+    /// Number of hints the player took. 
+    pub hints: usize, 
+    /// Length of the shortest path from the start to the goal, in edges. 
+    pub shortest: usize, 
+    /// Time the player took to reach the goal. 
+    pub time: Duration, 
+}
+
+impl Score {
+    /// Scores a solve of a maze. 
+    pub fn new(maze: &Maze, moves: usize, hints: usize, time: Duration) -> Score {
+        Score {
+            size: (maze.width, maze.height), 
+            moves, 
+            hints, 
+            shortest: solve::shortest(maze).map_or(0, |solution| solution.path.len() - 1), 
+            time, 
+        }
+    }
+
+    /// Gets the points scored: ten for each edge of the shortest path, scaled down by the share of moves made
+    /// beyond it, and by how much longer than par (300ms per edge of the shortest path) the solve took. Hints
+    /// cost ten points for the first, twenty for the second, and so on, down to no points at all. 
+    pub fn points(&self) -> u32 {
+        let efficiency = self.shortest as f64 / self.moves.max(1) as f64;
+        let par = PAR_PER_EDGE * self.shortest as u32;
+        let pace = f64::min(par.as_secs_f64() / self.time.as_secs_f64(), 1.0);
+        let hints = self.hints as u32;
+        let hint_cost = HINT_COST * hints * (hints + 1) / 2;
+        ((10.0 * self.shortest as f64 * efficiency * pace).round() as u32).saturating_sub(hint_cost)
+    }
+}
+
+/// Lets the user solve a maze on their own, animated, returning their score once they reached the goal. 
+#[cfg(feature = "terminal")]
+pub fn solo(state: &mut solve::State) -> Result<Score, Error> {
+    let start = Instant::now();
+    Race::new(state).play(state)?;
+    let (moves, hints) = state.player.as_ref().map_or((0, 0), |player| (player.moves, player.hints));
+    Ok(Score::new(&state.maze, moves, hints, start.elapsed()))
+}
+
+/// Races the user against a solver (and a ghost replaying given moves, if any) through a maze, animated,
+/// returning the outcome once any of them reached the goal (see [`Race`]). 
+#[cfg(feature = "terminal")]
+pub fn race(
+    solver: Solver, 
+    ghost: Option<Vec<(i64, Node)>>, 
+    state: &mut solve::State, 
+) -> Result<Results, Error> {
+    let race = Race::new_with(solver, state);
+    let race = match ghost {
+        Some(moves) => race.with_ghost(moves, state), 
+        None => race, 
+    };
+    let steps = race.play(state)?;
+    Ok(Results::new(state, solver, steps))
+}
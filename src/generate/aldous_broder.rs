@@ -13,7 +13,7 @@ impl Animation for AldousBroder {
     type Phase = Phase;
     
     fn new(state: &mut State) -> Self {
-        let head = state.maze.random_node();
+        let head = state.maze.random_node(&mut state.rng);
         AldousBroder{ head }
     }
 
@@ -26,13 +26,17 @@ impl Animation for AldousBroder {
         // take a step in a random direction from the head
         let edge = state.maze
             .neighbours(self.head)
-            .choose();
+            .choose(&mut state.rng);
         self.head = edge.to;
 
         // if we stepped out of the maze, open the wall
         if !state.is_visited(edge.to) {
-            state.maze.open[edge] = true;
+            state.open(edge);
         }
         Signal::Continue
     }
+
+    fn heads(&self) -> Vec<Node> {
+        vec![self.head]
+    }
 }
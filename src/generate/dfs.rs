@@ -15,7 +15,7 @@ impl Animation for Dfs {
     
     fn new(state: &mut State) -> Self {
         Dfs {
-            stack: vec![state.maze.random_node()], 
+            stack: vec![state.maze.random_node(&mut state.rng)], 
         }
     }
 
@@ -29,14 +29,24 @@ impl Animation for Dfs {
         let neighbour = state.maze
             .neighbours(head)
             .filter(|n| !state.is_visited(n))
-            .choose();
+            .choose(&mut state.rng);
 
         // if there is one, add it to the stack and open the wall between them
         if let Some(edge) = neighbour {
             self.stack.push(head);
             self.stack.push(edge.to);
-            state.maze.open[edge] = true;
+            state.open(edge);
         }
         Signal::Continue
     }
+
+    fn heads(&self) -> Vec<Node> {
+        self.stack.last().into_iter().copied().collect()
+    }
+
+    fn debug_info(&self) -> Vec<String> {
+        let mut info = vec![format!("stack depth: {}", self.stack.len())];
+        info.extend(self.stack.last().map(|Node(x, y)| format!("head: {x}:{y}")));
+        info
+    }
 }
@@ -1,6 +1,6 @@
 use rand::seq::SliceRandom;
 use crate::{
-    maze::{Edge, Maze, Node, NodeBuffer}, 
+    maze::{Edge, Node, NodeBuffer}, 
     Animation, Signal
 };
 use super::{State, Phase};
@@ -13,21 +13,37 @@ pub struct Kruskal {
     /// Stores the parent of each node. This is used to compute root nodes (nodes that are their own parents)
     /// which serves as set identifiers. 
     parents: NodeBuffer<Node>, 
+    /// Upper bound of the height of the tree below each root node, used to keep trees shallow when joining
+    /// them. 
+    ranks: NodeBuffer<u8>, 
 }
 
 impl Kruskal {
     /// Finds the root of a node, and sets it as the direct parent of the node and all nodes inbetween for
     /// quick future lookup. 
-    fn find_root(&mut self, maze: &Maze, node: Node) -> Node {
-        let parent = self.parents[node];
+    fn find_root(&mut self, node: Node) -> Node {
+        let mut root = node;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
 
-        match node == parent {
-            true => node, 
-            false => {
-                let root = self.find_root(maze, parent);
-                self.parents[node] = root;
-                root
-            }
+        let mut node = node;
+        while node != root {
+            node = std::mem::replace(&mut self.parents[node], root);
+        }
+        root
+    }
+
+    /// Joins the trees of two root nodes by attaching the shallower tree to the root of the deeper one. 
+    fn join(&mut self, root_a: Node, root_b: Node) {
+        let (shallow, deep) = match self.ranks[root_a] < self.ranks[root_b] {
+            true => (root_a, root_b), 
+            false => (root_b, root_a), 
+        };
+        self.parents[shallow] = deep;
+
+        if self.ranks[shallow] == self.ranks[deep] {
+            self.ranks[deep] += 1;
         }
     }
 }
@@ -38,32 +54,34 @@ impl Animation for Kruskal {
     fn new(state: &mut State) -> Self {
         let queue = {
             let mut walls: Vec<Edge> = state.maze.edges_iter().collect();
-            walls.shuffle(&mut rand::thread_rng());
+            walls.shuffle(&mut state.rng);
             walls
         };
         let parents = NodeBuffer::new_from_function(&state.maze, std::convert::identity);
-        Kruskal{ queue, parents }
+        let ranks = NodeBuffer::new(&state.maze);
+        Kruskal{ queue, parents, ranks }
     }
 
     fn step(&mut self, state: &mut State) -> Signal {
-        let Some(edge) = self.queue.pop() else {
-            return Signal::Done
-        };
+        loop {
+            let Some(edge) = self.queue.pop() else {
+                return Signal::Done
+            };
 
-        // open the edge only if the two nodes aren't in the same set (have the same root node) as we would
-        // otherwise introduce a loop
-        let root_a = self.find_root(&state.maze, edge.from);
-        let root_b = self.find_root(&state.maze, edge.to);
+            // open the edge only if the two nodes aren't in the same set (have the same root node) as we would
+            // otherwise introduce a loop
+            let root_a = self.find_root(edge.from);
+            let root_b = self.find_root(edge.to);
 
-        if root_a != root_b {
-            state.visit(edge.from);
-            state.visit(edge.to);
+            if root_a != root_b {
+                state.visit(edge.from);
+                state.visit(edge.to);
 
-            self.parents[root_a] = root_b;
-            state.maze.open[edge] = true;
-            Signal::Continue
-        } else { // otherwise, keep searching for an openable wall
-            self.step(state)
+                self.join(root_a, root_b);
+                state.open(edge);
+                return Signal::Continue
+            }
+            // otherwise, keep searching for an openable wall
         }
     }
 
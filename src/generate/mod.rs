@@ -1,62 +1,129 @@
-use clap::ValueEnum;
+use std::{iter, sync::LazyLock};
+use rand::{rngs::StdRng, RngCore, SeedableRng};
 use crate::{
-    colour::{Hsl, Palette},
-    maze::{Maze, NodeBuffer},
-    state,
-    Animation, Error, Settings 
+    analysis::Difficulty, 
+    events::Hooks, 
+    maze::{Maze, Node, NodeBits}, 
+    registry::{Entry, Registry}, 
+    solve, 
+    state::{self, Visits}, 
+    Error, Settings 
 };
 
 mod aldous_broder;
 mod dfs;
 mod kruskal;
+mod parallel;
 mod prim;
 mod wilson;
 
-/// State for the generate phase (currently none needed). 
-pub struct Phase;
+/// State for the generate phase. 
+#[derive(Default)]
+pub struct Phase {
+    /// Nodes reached by the search chasing the generator, if any (see [`Chase`](crate::chase::Chase)). 
+    pub chased: Option<NodeBits>, 
+    /// Nodes along the longest path through the maze traced so far, if any (see
+    /// [`diameter::trace`](crate::diameter::trace)). 
+    pub diameter: Option<NodeBits>, 
+}
+
+impl state::Phase for Phase {
+    const NAME: &str = "generate";
 
-impl state::Phase for Phase {}
+    fn special(&self, node: Node) -> bool {
+        [&self.chased, &self.diameter].into_iter().flatten().any(|nodes| nodes[node])
+    }
+}
 
 pub type State = state::State<Phase>;
 
 /// Constructs a new global state for the generate phase. 
 pub fn state(maze: Maze, settings: Settings) -> State {
-    let age = NodeBuffer::new(&maze);
-    let colours = {
-        let base = Hsl {
-            hue: 0.0, 
-            saturation: 1.0, 
-            lightness: 0.6, 
-        };
-        Palette::from_base(base).into_lut(&settings)
-    };
+    let visited_at = Visits::new(&maze);
+    let colours = settings.theme.generate.into_lut(&settings);
+    let rng = StdRng::seed_from_u64(settings.seed);
     State {
         maze, 
         settings, 
-        age, 
+        rng, 
+        visited_at, 
         visited_count: 0, 
+        time: 0, 
         colours, 
-        phase: Phase, 
+        active: Vec::new(), 
+        fog: None, 
+        mask: None, 
+        phase: Phase::default(), 
+    }
+}
+
+/// Constructs a state for the generate phase of an already generated maze, with all nodes fully aged. 
+pub fn finished(maze: Maze, settings: Settings) -> State {
+    let mut state = state(maze, settings);
+    for node in state.maze.nodes_iter() {
+        state.set_age(node, u8::MAX);
     }
+    state
 }
 
-#[derive(ValueEnum, Clone, Copy, PartialEq, Eq)]
-pub enum Generator {
-    AldousBroder, 
-    Dfs, 
-    Kruskal, 
-    Prim, 
-    Wilson, 
+/// A generator registered in [`GENERATORS`]. 
+pub type Generator = Entry<Phase>;
+
+/// All generators selectable by name. Additional generators may be registered at runtime. 
+pub static GENERATORS: LazyLock<Registry<Phase>> = LazyLock::new(|| Registry::new([
+    Generator::new::<aldous_broder::AldousBroder>("aldous-broder"), 
+    Generator::new::<dfs::Dfs>("dfs"), 
+    Generator::new::<kruskal::Kruskal>("kruskal"), 
+    Generator::new::<prim::Prim>("prim"), 
+    Generator::new::<wilson::Wilson>("wilson"), 
+]));
+
+/// Runs a generator without rendering upon tiles of the maze in parallel, returning the total number of
+/// timesteps taken. This is much faster for huge mazes, though the tile borders remain visible as long
+/// straight walls. 
+pub fn run_parallel(generator: Generator, state: &mut State) -> usize {
+    parallel::run(generator, state)
+}
+
+/// Checks that a generator produced a perfect maze (see [`Maze::assert_valid`]), if enabled by
+/// [`Settings::verify`]. 
+pub fn verify(state: &State) -> Result<(), Error> {
+    match state.settings.verify {
+        true => state.maze.assert_valid(), 
+        false => Ok(()), 
+    }
 }
 
-impl Generator {
-    pub fn run(self, state: &mut State) -> Result<(), Error> {
-        match self {
-            Generator::AldousBroder => aldous_broder::AldousBroder::run(state), 
-            Generator::Dfs => dfs::Dfs::run(state), 
-            Generator::Kruskal => kruskal::Kruskal::run(state), 
-            Generator::Prim => prim::Prim::run(state), 
-            Generator::Wilson => wilson::Wilson::run(state), 
+/// Number of seeds tried by [`seed_with_difficulty`] before giving up. 
+pub const MAX_ATTEMPTS: usize = 1000;
+
+/// Finds a seed for which a generator produces a maze of at least the given difficulty (see
+/// [`Difficulty::score`]), trying the seed of the settings first and then seeds derived from it. Each maze
+/// is generated without rendering (in parallel if `parallel` is set), such that the maze of the seed found
+/// may then be generated again, e.g. animated. Fails if none of [`MAX_ATTEMPTS`] seeds is found. 
+pub fn seed_with_difficulty(
+    generator: Generator, 
+    (width, height): (u16, u16), 
+    parallel: bool, 
+    settings: &Settings, 
+    min_difficulty: f64, 
+) -> Result<u64, Error> {
+    let mut seeds = StdRng::seed_from_u64(settings.seed);
+    let seeds = iter::once(settings.seed).chain(iter::repeat_with(|| seeds.next_u64()));
+    for seed in seeds.take(MAX_ATTEMPTS) {
+        // attempts aren't reported to the hooks, which should only observe the maze eventually generated
+        let settings = Settings{ seed, hooks: Hooks::default(), ..settings.clone() };
+        let mut state = state(Maze::new(width, height), settings);
+        match parallel {
+            true => run_parallel(generator, &mut state), 
+            false => generator.run_headless(&mut state), 
+        };
+        let Some(solution) = solve::shortest(&state.maze) else {
+            continue
+        };
+        if Difficulty::new(&state.maze, &solution).score() >= min_difficulty {
+            return Ok(seed)
         }
     }
+    Err(Error::InvalidArguments(format!("no maze of difficulty {min_difficulty} was found in {MAX_ATTEMPTS} attempts")))
 }
@@ -0,0 +1,114 @@
+use rand::{Rng, RngCore};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use crate::{
+    events::{Event, Hooks}, 
+    maze::{Direction, Edge, Maze, Node}, 
+    state::Phase as _, 
+    Settings, 
+};
+use super::{Generator, Phase, State};
+
+/// Side length of the tiles that mazes are split into, in nodes. 
+const TILE_SIZE: usize = 128;
+
+/// Runs a generator without rendering by splitting the maze into tiles which are generated in parallel, 
+/// returning the total number of timesteps taken. This is much faster for huge mazes, though the tile borders
+/// remain visible as long straight walls. 
+///
+/// The tiles are joined by running the generator once more upon the lattice of tiles, and opening a single
+/// random edge between each pair of tiles connected there. Since that lattice is a spanning tree, so is the
+/// resulting maze. Generators need both dimensions to be at least 2, so a lattice only one tile across is
+/// joined along its length instead, which is its only spanning tree. 
+///
+/// Without the `parallel` feature, the tiles are generated one after another, giving the same maze. 
+pub fn run(generator: Generator, state: &mut State) -> usize {
+    let (width, height) = (state.maze.width, state.maze.height);
+    let columns = tiles(width);
+    let rows = tiles(height);
+    // the last tile along each axis takes whatever remains, which is at least 2 (see `tiles`)
+    let tile_size = |index: usize, length: usize| match index + 1 == tiles(length) {
+        true => length - index * TILE_SIZE, 
+        false => TILE_SIZE, 
+    };
+    // tiles and the lattice have their own coordinates, so their events would only confuse the hooks.
+    // instead, events are reported as the tiles are copied into the maze
+    let settings = Settings{ hooks: Hooks::default(), ..state.settings.clone() };
+    // seed each tile from the maze's generator, such that the result is reproducible regardless of scheduling
+    let seeds: Vec<u64> = (0..columns * rows).map(|_| state.rng.next_u64()).collect();
+
+    // generate tiles
+    let tiles = 0..columns * rows;
+    #[cfg(feature = "parallel")]
+    let tiles = tiles.into_par_iter();
+    let tiles: Vec<_> = tiles
+        .map(|i| {
+            let Node(tx, ty) = Node(i % columns, i / columns);
+            let maze = Maze::new(tile_size(tx, width) as u16, tile_size(ty, height) as u16);
+            let mut tile = super::state(maze, Settings{ seed: seeds[i], ..settings.clone() });
+            let steps = generator.run_headless(&mut tile);
+            (Node(tx, ty), tile.maze, steps)
+        })
+        .collect();
+
+    let mut total_steps = 0;
+    for (Node(tx, ty), tile, steps) in tiles {
+        let offset = |Node(x, y)| Node(x + tx * TILE_SIZE, y + ty * TILE_SIZE);
+        for edge in tile.edges_iter().filter(|&e| tile.open[e]) {
+            let edge = Edge {
+                from: offset(edge.from), 
+                to: offset(edge.to), 
+                direction: edge.direction, 
+            };
+            state.open(edge);
+        }
+        total_steps += steps;
+    }
+
+    // join tiles
+    let lattice = Maze::new(columns as u16, rows as u16);
+    let joins: Vec<_> = match columns >= 2 && rows >= 2 {
+        true => {
+            let settings = Settings{ seed: state.rng.next_u64(), ..settings };
+            let mut lattice = super::state(lattice, settings);
+            total_steps += generator.run_headless(&mut lattice);
+            lattice.maze.edges_iter().filter(|&e| lattice.maze.open[e]).collect()
+        }, 
+        false => lattice.edges_iter().collect(), 
+    };
+
+    for edge in joins {
+        let Node(tx, ty) = edge.from;
+        // pick a node along the border of the tile, on the side facing the other tile
+        let node = match edge.direction {
+            Direction::East => Node(
+                tx * TILE_SIZE + tile_size(tx, width) - 1, 
+                ty * TILE_SIZE + state.rng.gen_range(0..tile_size(ty, height)), 
+            ), 
+            Direction::South => Node(
+                tx * TILE_SIZE + state.rng.gen_range(0..tile_size(tx, width)), 
+                ty * TILE_SIZE + tile_size(ty, height) - 1, 
+            ), 
+            _ => unreachable!("edges are iterated from their north-west node"), 
+        };
+        let edge = state.maze
+            .edge(node, edge.direction)
+            .expect("tile borders are within the maze");
+        state.open(edge);
+    }
+
+    for node in state.maze.nodes_iter() {
+        state.set_age(node, u8::MAX);
+    }
+    state.emit(Event::PhaseFinished(Phase::NAME));
+    total_steps
+}
+
+/// Gets the number of tiles along an axis of given length. A remainder of a single node is added to the last
+/// tile rather than making a tile of its own, since generators need both dimensions to be at least 2. 
+fn tiles(length: usize) -> usize {
+    match length > TILE_SIZE && length % TILE_SIZE == 1 {
+        true => length / TILE_SIZE, 
+        false => length.div_ceil(TILE_SIZE), 
+    }
+}
@@ -1,6 +1,7 @@
 use rand::Rng;
 use crate::{
     maze::Edge, 
+    options::Frontier, 
     Animation, Signal
 };
 use super::{State, Phase};
@@ -13,7 +14,7 @@ impl Animation for Prim {
     type Phase = Phase;
     
     fn new(state: &mut State) -> Self {
-        let start = state.maze.random_node();
+        let start = state.maze.random_node(&mut state.rng);
         let queue = state.maze.neighbours(start).into_iter().collect();
         state.visit(start);
         Prim{ queue }
@@ -23,8 +24,14 @@ impl Animation for Prim {
         if self.queue.is_empty() {
             return Signal::Done
         }
-        let index = rand::thread_rng().gen_range(0..self.queue.len());
-        let edge = self.queue.swap_remove(index);
+        let edge = match state.settings.options.prim_frontier.unwrap_or_default() {
+            Frontier::Random => {
+                let index = state.rng.gen_range(0..self.queue.len());
+                self.queue.swap_remove(index)
+            }, 
+            Frontier::Newest => self.queue.pop().expect("the queue isn't empty"), 
+            Frontier::Oldest => self.queue.remove(0), 
+        };
         let unvisited = match (state.is_visited(edge.from), state.is_visited(edge.to)) {
             (true, false) => Some(edge.to), 
             (false, true) => Some(edge.from), 
@@ -36,9 +43,14 @@ impl Animation for Prim {
 
         if let Some(unvisited) = unvisited {
             self.queue.extend(state.maze.neighbours(unvisited));
-            state.maze.open[edge] = true;
+            state.open(edge);
         }
         Signal::Continue
     }
+
+    fn debug_info(&self) -> Vec<String> {
+        // edges are queued once per visited end, so some lead between nodes both visited by now
+        vec![format!("queue: {} edges", self.queue.len())]
+    }
 }
 
@@ -1,5 +1,5 @@
 use crate::{
-    maze::{Edge, NodeBuffer}, 
+    maze::{Edge, Node, NodeBuffer}, 
     Animation, Signal, 
 };
 use super::{State, Phase};
@@ -12,11 +12,27 @@ pub struct Wilson {
     path_indices: NodeBuffer<Option<usize>>, 
 }
 
+impl Wilson {
+    /// Erases the whole path, such that the next step begins a new one from another node. 
+    fn abandon(&mut self, state: &mut State) {
+        for erased in self.path.drain(1..) {
+            self.path_indices[erased.to] = None;
+            state.close(erased);
+            state.unvisit(erased.to);
+        }
+        if let Some(first) = self.path.pop() {
+            self.path_indices[first.to] = None;
+            state.unvisit(first.to);
+        }
+    }
+}
+
 impl Animation for Wilson {
     type Phase = Phase;
     
     fn new(state: &mut State) -> Self {
-        let goal = state.maze.random_node();
+        let start = state.settings.options.wilson_start.unwrap_or_default();
+        let goal = start.node(&state.maze, &mut state.rng);
         state.visit(goal);
         
         Wilson {
@@ -30,7 +46,7 @@ impl Animation for Wilson {
         let head = match self.path.last() {
             Some(last) => last.to, 
             None => {
-                let first = state.maze.random_node_where(|n| !state.is_visited(n));
+                let first = state.maze.random_node_where(&mut state.rng, |n| !state.visited_at.is_visited(n));
                 let Some(first) = first else {
                     return Signal::Done
                 };
@@ -44,7 +60,7 @@ impl Animation for Wilson {
         // choose next node in the path
         let edge = state.maze
             .neighbours(head)
-            .choose();
+            .choose(&mut state.rng);
         let next = edge.to;
 
         match (self.path_indices[next], state.is_visited(next)) {
@@ -52,27 +68,41 @@ impl Animation for Wilson {
             (Some(loop_start), _) => {
                 for erased in self.path.drain(loop_start..) {
                     self.path_indices[erased.to] = None;
-                    state.maze.open[erased] = false;
+                    state.close(erased);
                     state.unvisit(erased.to);
                 }
             }
             // the next node is not on the path but is visited => we have found the established maze and can
             // finalise the path
             (None, true) => {
-                state.maze.open[edge] = true;
+                state.open(edge);
                 self.path_indices.fill(None);
                 self.path.clear();
             }
             // otherwise, we continue the path
             (None, false) => {
-                state.maze.open[edge] = true;
+                state.open(edge);
                 self.path.push(edge);
             } 
         }
+        // the path starts with an identity edge, so it has walked one edge less than its length
+        let cap = state.settings.options.wilson_walk_cap;
+        if cap.is_some_and(|cap| self.path.len() > cap + 1) {
+            self.abandon(state);
+        }
         Signal::Continue
     }
 
     fn timescale(&self) -> u32 {
         125
     }
+
+    fn heads(&self) -> Vec<Node> {
+        self.path.last().map(|edge| edge.to).into_iter().collect()
+    }
+
+    fn debug_info(&self) -> Vec<String> {
+        // the identity edge the path starts with isn't walked
+        vec![format!("path length: {} edges", self.path.len().saturating_sub(1))]
+    }
 }
@@ -0,0 +1,83 @@
+//! Slippery nodes placed in a maze before solving it, as given by `--ice` (see
+//! [`Settings::ice`](crate::Settings::ice)). Moving onto ice carries on in the same direction until a wall is
+//! hit, such that solvers (see [`solve::Phase::open_neighbours`]) and the player reach farther in one move. 
+
+use rand::Rng;
+use crate::{
+    colour::Hsl, 
+    maze::{Direction, Maze, Node, NodeBits}, 
+    solve, 
+    state::CellOverlay, 
+};
+
+/// Nodes covered in ice. 
+pub struct Ice {
+    /// Whether each node is covered in ice. 
+    cells: NodeBits, 
+    /// Colour of the glyphs marking the ice. 
+    colour: Hsl, 
+}
+
+impl Ice {
+    /// Covers a share of the nodes of a maze in ice at random, other than those to be kept clear (e.g. the
+    /// start and the goal). 
+    pub fn place(maze: &Maze, share: f64, keep_clear: &[Node], rng: &mut impl Rng, colour: Hsl) -> Ice {
+        let mut cells = NodeBits::new(maze);
+        for node in maze.nodes_iter() {
+            if !keep_clear.contains(&node) && rng.gen_bool(share) {
+                cells.set(node, true);
+            }
+        }
+        Ice{ cells, colour }
+    }
+
+    /// Whether a node is covered in ice. 
+    pub fn is_ice(&self, node: Node) -> bool {
+        self.cells[node]
+    }
+
+    /// Gets the nodes slid onto after moving onto a node in a direction, in order, until hitting a wall (or a
+    /// one-way edge against the move). The ice only carries along corridors: sliding stops at junctions,
+    /// where the way on may be chosen, such that no node becomes unreachable. It also stops at the goal. 
+    pub fn slide(&self, maze: &Maze, from: Node, direction: Direction, goal: Node) -> Vec<Node> {
+        let mut nodes = Vec::new();
+        let mut node = from;
+        while self.cells[node] && node != goal && maze.open_neighbours(node).len() <= 2 {
+            let Some(edge) = maze.edge(node, direction).filter(|&e| maze.is_passable(e)) else {
+                break
+            };
+            node = edge.to;
+            nodes.push(node);
+        }
+        nodes
+    }
+
+    /// Gets the glyph drawn upon a node if it's covered in ice. 
+    pub(crate) fn overlay(&self, node: Node) -> Option<CellOverlay> {
+        self.cells[node].then_some(CellOverlay {
+            glyph: '~', 
+            colour: self.colour, 
+            intensity: 0.8, 
+        })
+    }
+}
+
+/// Covers nodes of a maze about to be solved in ice, as large a share as given by the settings, kept clear of
+/// the nodes searched from and to. Returns `None` if there's to be no ice. 
+pub fn place(state: &mut solve::State) -> Option<Ice> {
+    if state.settings.ice <= 0.0 {
+        return None
+    }
+    let mut keep_clear = vec![state.start, state.goal];
+    keep_clear.extend(state.starts.iter().flat_map(|starts| starts.nodes.iter().copied()));
+    // pale blue, whatever the palette, and light against the background
+    let colour = Hsl {
+        hue: 230.0, 
+        saturation: 0.6, 
+        lightness: match state.settings.light {
+            true => 0.45, 
+            false => 0.85, 
+        }, 
+    };
+    Some(Ice::place(&state.maze, state.settings.ice, &keep_clear, &mut state.rng, colour))
+}
@@ -0,0 +1,16 @@
+use std::{fs::File, io::BufReader, path::Path};
+use crate::{maze::Maze, Error};
+
+/// Reads a maze written by the JSON exporter. 
+pub fn read(path: &Path) -> Result<Maze, Error> {
+    let reader = BufReader::new(File::open(path)?);
+    serde_json::from_reader(reader).map_err(|e| match e.is_io() {
+        true => Error::Io(e.into()), 
+        false => Error::InvalidMaze(e.to_string()), 
+    })
+}
+
+/// Parses a maze written by the JSON exporter. 
+pub fn parse(text: &str) -> Result<Maze, Error> {
+    serde_json::from_str(text).map_err(|e| Error::InvalidMaze(e.to_string()))
+}
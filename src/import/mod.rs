@@ -0,0 +1,46 @@
+use std::{io, path::Path};
+use crate::{
+    export::Format, 
+    maze::Maze, 
+    Error, 
+};
+
+#[cfg(feature = "serde")]
+mod json;
+mod txt;
+
+/// Reads a maze from a file, with the format inferred from its extension, or from stdin if the path is `-`
+/// (see [`parse`]). 
+pub fn read(path: &Path) -> Result<Maze, Error> {
+    if path == Path::new("-") {
+        return parse(&io::read_to_string(io::stdin())?)
+    }
+    let maze = match Format::from_path(path) {
+        #[cfg(feature = "serde")]
+        Some(Format::Json) => json::read(path), 
+        Some(Format::Txt) => txt::read(path), 
+        _ => Err(Error::InvalidArguments(
+            format!("cannot read mazes from {} (expected a json or txt file)", path.display()), 
+        )), 
+    }?;
+    validate(maze)
+}
+
+/// Parses a maze in any supported format, detected from the text itself: JSON if it starts with `{`, and
+/// ASCII-art otherwise. 
+pub fn parse(text: &str) -> Result<Maze, Error> {
+    #[cfg(feature = "serde")]
+    if text.trim_start().starts_with('{') {
+        return validate(json::parse(text)?)
+    }
+    validate(txt::parse(text)?)
+}
+
+/// Rejects mazes with unreachable nodes (e.g. drawn by hand), since solvers expect to reach the goal. Mazes
+/// with loops are accepted, and solved between their corners if they have no dead ends to start and end at. 
+fn validate(maze: Maze) -> Result<Maze, Error> {
+    match maze.is_connected() {
+        true => Ok(maze), 
+        false => Err(Error::InvalidMaze("some nodes can't be reached from the others".to_owned())), 
+    }
+}
@@ -0,0 +1,165 @@
+use std::{fs, path::Path};
+use crate::{
+    maze::{Direction, Maze, Node}, 
+    Error, 
+};
+
+/// Reads a maze drawn as ASCII-art, either in the `+--+` format written by the txt exporter or as a grid of
+/// `#` walls (see [`parse`]). 
+pub fn read(path: &Path) -> Result<Maze, Error> {
+    parse(&fs::read_to_string(path)?)
+}
+
+/// Parses a maze drawn as ASCII-art. Two formats are accepted: 
+///
+/// - The common `+--+` format, where `+` marks the corners between nodes, `|` and `-` mark walls, and
+///   anything else (usually spaces) is open. Nodes may be any number of columns wide, as given by the
+///   spacing of the `+` in the first line. 
+/// - A grid of `#` walls, where nodes are at odd rows and columns, separated by a cell that is either a wall
+///   or open. 
+///
+/// Lines may be shorter than the drawing is wide (e.g. with trailing spaces stripped), with the missing
+/// characters considered open. 
+pub fn parse(text: &str) -> Result<Maze, Error> {
+    let lines: Vec<Vec<char>> = text
+        .lines()
+        .skip_while(|line| line.trim().is_empty())
+        .map(|line| line.trim_end().chars().collect())
+        .collect();
+    let end = lines.iter().rposition(|line| !line.is_empty()).map_or(0, |i| i + 1);
+    let lines = &lines[..end];
+
+    let Some(first) = lines.first() else {
+        return Err(Error::InvalidMaze("the file is empty".to_owned()))
+    };
+    match (first.first(), first.contains(&'#')) {
+        (Some('+'), _) => parse_boxes(lines), 
+        (_, true) => parse_grid(lines), 
+        _ => Err(Error::InvalidMaze("expected a maze drawn with `+--+` or `#`".to_owned())), 
+    }
+}
+
+/// Parses the `+--+` format. 
+fn parse_boxes(lines: &[Vec<char>]) -> Result<Maze, Error> {
+    let corners: Vec<usize> = lines[0]
+        .iter()
+        .enumerate()
+        .filter(|&(_, &c)| c == '+')
+        .map(|(column, _)| column)
+        .collect();
+    let mut maze = new_maze(corners.len() - 1, (lines.len() - 1) / 2)?;
+
+    for node in maze.nodes_iter() {
+        let Node(x, y) = node;
+        // the wall east of a node is at the next corner, and the wall south of it is between the two corners
+        let walls = [
+            (Direction::East, 2 * y + 1, corners[x + 1]), 
+            (Direction::South, 2 * y + 2, (corners[x] + corners[x + 1]).div_ceil(2)), 
+        ];
+        for (direction, row, column) in walls {
+            let Some(edge) = maze.edge(node, direction) else {
+                continue
+            };
+            let wall = matches!(char_at(lines, row, column), '|' | '-' | '+');
+            maze.open.set(edge, !wall);
+        }
+    }
+    Ok(maze)
+}
+
+/// Parses the `#` grid format. 
+fn parse_grid(lines: &[Vec<char>]) -> Result<Maze, Error> {
+    let columns = lines.iter().map(Vec::len).max().unwrap_or(0);
+    let mut maze = new_maze((columns - 1) / 2, (lines.len() - 1) / 2)?;
+
+    for node in maze.nodes_iter() {
+        let Node(x, y) = node;
+        let walls = [
+            (Direction::East, 2 * y + 1, 2 * x + 2), 
+            (Direction::South, 2 * y + 2, 2 * x + 1), 
+        ];
+        for (direction, row, column) in walls {
+            let Some(edge) = maze.edge(node, direction) else {
+                continue
+            };
+            maze.open.set(edge, char_at(lines, row, column) != '#');
+        }
+    }
+    Ok(maze)
+}
+
+/// Constructs an empty maze of the parsed dimensions, validating them. 
+fn new_maze(width: usize, height: usize) -> Result<Maze, Error> {
+    let dimension = |x: usize| u16::try_from(x).ok().filter(|&x| x >= 2);
+    match (dimension(width), dimension(height)) {
+        (Some(width), Some(height)) => Ok(Maze::new(width, height)), 
+        _ => Err(Error::InvalidMaze(format!("invalid maze dimensions {width}x{height}"))), 
+    }
+}
+
+/// Gets the character at a row and column, or a space if the line is too short. 
+fn char_at(lines: &[Vec<char>], row: usize, column: usize) -> char {
+    lines
+        .get(row)
+        .and_then(|line| line.get(column))
+        .copied()
+        .unwrap_or(' ')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOXES: &str = "
++--+--+--+
+|     |  |
++--+  +  +
+|        |
++--+--+--+
+";
+
+    const GRID: &str = "
+#######
+#   # #
+### # #
+#     #
+#######
+";
+
+    /// Asserts that the maze is that drawn by [`BOXES`] and [`GRID`]. 
+    fn assert_drawn(maze: &Maze) {
+        assert_eq!((maze.width, maze.height), (3, 2));
+        let open = |x, y, direction| maze.open.get(maze.edge(Node(x, y), direction).unwrap());
+        assert!(open(0, 0, Direction::East));
+        assert!(!open(1, 0, Direction::East));
+        assert!(!open(0, 0, Direction::South));
+        assert!(open(1, 0, Direction::South));
+        assert!(open(2, 0, Direction::South));
+        assert!(open(0, 1, Direction::East));
+        assert!(open(1, 1, Direction::East));
+    }
+
+    #[test]
+    fn parses_boxes() {
+        assert_drawn(&parse(BOXES).unwrap());
+    }
+
+    #[test]
+    fn parses_wide_boxes() {
+        let text = "+----+----+----+\n|         |    |\n+----+    +    +\n|              |\n+----+----+----+";
+        assert_drawn(&parse(text).unwrap());
+    }
+
+    #[test]
+    fn parses_grid() {
+        assert_drawn(&parse(GRID).unwrap());
+    }
+
+    #[test]
+    fn rejects_invalid() {
+        let invalid = ["", " \n\n", "a maze", "+--+\n|  |\n+--+", "###\n# #\n###", "+--+--+\n"];
+        for text in invalid {
+            assert!(matches!(parse(text), Err(Error::InvalidMaze(_))), "parsed {text:?}");
+        }
+    }
+}
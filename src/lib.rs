@@ -0,0 +1,639 @@
+//! Generation and solving of mazes, animated in the terminal. 
+//!
+//! Mazes are generated by a [`generate::Generator`] and solved by a [`solve::Solver`], which dispatch to
+//! implementations of [`Animation`] stepping upon a [`state::State`]. Animations may be run in the terminal via
+//! [`Animation::run`] (expecting the terminal to have been set up by the caller, and needing the `terminal`
+//! feature), or without rendering via [`Animation::run_headless`]. 
+
+use std::{
+    fmt, 
+    io, 
+    str::FromStr, 
+    time::Duration, 
+};
+#[cfg(feature = "terminal")]
+use std::time::Instant;
+#[cfg(feature = "terminal")]
+use crossterm::event::{self as terminal, KeyCode, KeyEvent, KeyEventKind};
+use crate::{
+    capture::Capture, 
+    colour::{Easing, Indexing, Wall, CELL}, 
+    events::{Event, Hooks}, 
+    fade::FadeStyle, 
+    frame::Frames, 
+    maze::{Maze, Node}, 
+    options::Options, 
+    outro::Outro, 
+    solve::{Endpoints, Sources}, 
+    render::{Glyphs, Layout}, 
+    state::{Phase, State}, 
+    themes::{Theme, ThemeName}, 
+    walk::WalkStyle, 
+};
+#[cfg(feature = "terminal")]
+use crate::{
+    console::Console, 
+    render::{Renderer, Terminal}, 
+};
+
+pub mod analysis;
+pub mod bench;
+pub mod capture;
+#[cfg(feature = "terminal")]
+pub mod celebrate;
+pub mod chase;
+pub mod code;
+pub mod colour;
+#[cfg(feature = "terminal")]
+pub mod console;
+#[cfg(feature = "terminal")]
+pub mod diameter;
+#[cfg(feature = "terminal")]
+pub mod duel;
+pub mod elevation;
+pub mod events;
+pub mod export;
+pub mod fade;
+pub mod frame;
+pub mod game;
+pub mod generate;
+pub mod ice;
+pub mod import;
+pub mod maze;
+#[cfg(feature = "terminal")]
+pub mod net;
+pub mod options;
+pub mod outro;
+pub mod pipeline;
+pub mod portals;
+pub mod registry;
+pub mod render;
+pub mod solve;
+pub mod state;
+pub mod themes;
+#[cfg(feature = "terminal")]
+pub mod title;
+#[cfg(feature = "terminal")]
+pub mod versus;
+pub mod viewport;
+pub mod walk;
+#[cfg(feature = "wasm")]
+pub mod web;
+#[cfg(feature = "ratatui")]
+pub mod widget;
+
+/// Greatest number of frames skipped in a row while the terminal catches up (see [`Animation::run`]), such
+/// that the animation still visibly progresses on very slow terminals. 
+#[cfg(feature = "terminal")]
+const MAX_SKIPPED_FRAMES: u32 = 30;
+
+/// Signals the algorithm runtime what to do after each timestep. 
+pub enum Signal {
+    Continue, 
+    Done, 
+}
+
+/// Errors stopping the program, each mapping to a distinct exit code (see [`Error::exit_code`]). 
+#[derive(Debug)]
+pub enum Error {
+    /// Reading or writing failed. 
+    Io(io::Error), 
+    /// The terminal (of given size) is too small to fit a maze. 
+    TerminalTooSmall {
+        columns: u16, 
+        rows: u16, 
+    }, 
+    /// A maze couldn't be read, or describes an invalid maze. 
+    InvalidMaze(String), 
+    /// The arguments are invalid in a way that the argument parser can't detect. 
+    InvalidArguments(String), 
+    /// The user pressed a key to stop the animation. 
+    Break, 
+}
+
+impl Error {
+    /// Gets the exit code of the process when stopped by the error. Invalid arguments share their code with
+    /// those detected by the argument parser, and breaking uses the conventional code for an interrupt. 
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Io(_) => 1, 
+            Error::InvalidArguments(_) => 2, 
+            Error::InvalidMaze(_) => 3, 
+            Error::TerminalTooSmall{ .. } => 4, 
+            Error::Break => 130, 
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{e}"), 
+            Error::TerminalTooSmall{ columns, rows } => {
+                write!(f, "terminal is too small ({columns}x{rows}) to fit a maze")
+            }, 
+            Error::InvalidMaze(message) => write!(f, "invalid maze: {message}"), 
+            Error::InvalidArguments(message) => write!(f, "invalid arguments: {message}"), 
+            Error::Break => write!(f, "interrupted"), 
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e), 
+            _ => None, 
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+/// Type-erased algorithm, stepping upon the state it was constructed from (see [`Animation::stepper`]). 
+pub type Stepper<T> = Box<dyn FnMut(&mut State<T>) -> Signal>;
+
+/// Generalisation over different algorithms being animated upon a maze. 
+pub trait Animation: Sized {
+    type Phase: Phase;
+    
+    /// Constructs the algorithm from a given state. 
+    fn new(state: &mut State<Self::Phase>) -> Self;
+    /// Moves the animation one timestep forward. 
+    fn step(&mut self, state: &mut State<Self::Phase>) -> Signal;
+    /// Animation timescale working in conjunction with [`Settings::delay`]. 
+    fn timescale(&self) -> u32 {
+        100
+    }
+    /// Delay between rendered frames, by default [`Settings::delay`] scaled by [`Animation::timescale`]. 
+    fn delay(&self, state: &State<Self::Phase>) -> Duration {
+        100 * state.settings.delay / self.timescale()
+    }
+    /// Number of timesteps to advance between each rendered frame. 
+    fn steps_per_frame(&self, state: &State<Self::Phase>) -> u32 {
+        state.settings.steps_per_frame.resolve(&state.maze)
+    }
+    /// Whether finishing the animation concludes its phase, as reported by [`Event::PhaseFinished`]. This is
+    /// not the case for transitions like fades. 
+    fn finishes_phase(&self) -> bool {
+        true
+    }
+    /// Number of nodes queued to be visited (i.e. the frontier of a search), if the algorithm keeps a queue, as
+    /// shown by the HUD (see [`Settings::hud`]). 
+    fn frontier(&self) -> Option<usize> {
+        None
+    }
+    /// Nodes the algorithm is currently working on (e.g. the tip of a depth-first search), drawn in a bright
+    /// colour if enabled (see [`Settings::mark_heads`]). 
+    fn heads(&self) -> Vec<Node> {
+        Vec::new()
+    }
+    /// Lines describing the internals of the algorithm (e.g. the depth of its stack), drawn in a panel beside
+    /// the maze while enabled (see [`Settings::debug_panel`]). 
+    fn debug_info(&self) -> Vec<String> {
+        Vec::new()
+    }
+    /// Handles a key pressed while animating (e.g. to steer a player, see [`game`]), returning whether it was
+    /// handled. Any other input stops the animation (see [`Error::Break`]). 
+    #[cfg(feature = "terminal")]
+    fn key(&mut self, _state: &mut State<Self::Phase>, _key: KeyEvent) -> bool {
+        false
+    }
+    
+    /// Runs the animation until it signals to stop, printing the current state at each timestep, and returning
+    /// the number of timesteps taken (as [`Animation::run_headless`] does). 
+    ///
+    /// Frames are written to the terminal on a separate thread, such that slow terminal IO doesn't stall the
+    /// algorithm. If the terminal can't keep up, frames are dropped rather than queued, and if writing a frame
+    /// takes longer than the delay between frames (e.g. over a slow connection), the frames that would be
+    /// written meanwhile aren't drawn at all while the algorithm keeps stepping. 
+    #[cfg(feature = "terminal")]
+    fn run(state: &mut State<Self::Phase>) -> Result<usize, Error> {
+        let algorithm = Self::new(state);
+        algorithm.play(state)
+    }
+
+    /// Runs an already constructed animation as [`Animation::run`] does, e.g. one constructed with more than
+    /// the state. 
+    #[cfg(feature = "terminal")]
+    fn play(self, state: &mut State<Self::Phase>) -> Result<usize, Error> {
+        let settings = &state.settings;
+        // legacy consoles don't parse escape sequences, so commands are issued instead (see [`console`])
+        if console::is_legacy() {
+            let mut renderer = Console::new(settings.inline)
+                .with_symbols(settings.symbols)
+                .with_pitch(settings.pitch())
+                .with_layout(settings.layout)
+                .with_origin(settings.origin);
+            let result = animate(self, state, &mut renderer);
+            renderer.finish()?;
+            return result
+        }
+        let mut renderer = Terminal::spawn_capturing(settings.inline, settings.capture.clone())
+            .with_synchronized(settings.synchronized)
+            .with_symbols(settings.symbols)
+            .with_pitch(settings.pitch())
+            .with_layout(settings.layout)
+            .with_origin(settings.origin);
+        let result = animate(self, state, &mut renderer);
+        renderer.finish()?;
+        result
+    }
+
+    /// Constructs the algorithm behind a closure stepping it, such that it may be stepped without knowing its
+    /// type (e.g. when selected from a [`registry`]). 
+    fn stepper(state: &mut State<Self::Phase>) -> Stepper<Self::Phase>
+    where
+        Self: 'static, 
+    {
+        let mut algorithm = Self::new(state);
+        Box::new(move |state| algorithm.step(state))
+    }
+
+    /// Steps the animation lazily, yielding a snapshot of the state at each timestep (i.e., the frames that
+    /// [`Animation::run`] would draw). 
+    fn frames(state: &mut State<Self::Phase>) -> Frames<'_, Self> {
+        Frames::new(state)
+    }
+
+    /// Runs the animation until it signals to stop without rendering anything, returning the number of
+    /// timesteps taken. 
+    fn run_headless(state: &mut State<Self::Phase>) -> usize {
+        let algorithm = Self::new(state);
+        algorithm.play_headless(state)
+    }
+
+    /// Runs an already constructed animation as [`Animation::run_headless`] does. 
+    fn play_headless(self, state: &mut State<Self::Phase>) -> usize {
+        let mut algorithm = self;
+        let mut steps = 0;
+
+        while let Signal::Continue = algorithm.step(state) {
+            state.step();
+            steps += 1;
+        }
+        finished(&algorithm, state);
+        steps
+    }
+}
+
+/// Plays an animation as [`Animation::play`] does, drawing frames with a renderer. 
+#[cfg(feature = "terminal")]
+fn animate<A: Animation>(
+    mut algorithm: A, 
+    state: &mut State<A::Phase>, 
+    renderer: &mut impl Renderer, 
+) -> Result<usize, Error> {
+    let mut frame = Instant::now();
+    let chunk = state.settings.frame_chunk();
+    let steps_per_frame = algorithm.steps_per_frame(state) * chunk;
+    let mut steps = 0;
+    // frames left to skip while the terminal catches up, and whether the latest one was skipped
+    let (mut skipping, mut stale) = (0, false);
+
+    let result = loop {
+        let elapsed = frame.elapsed();
+        frame = Instant::now();
+
+        let mut signal = algorithm.step(state);
+        for _ in 1..steps_per_frame {
+            let Signal::Continue = signal else {
+                break
+            };
+            state.step();
+            steps += 1;
+            signal = algorithm.step(state);
+        }
+        let Signal::Continue = signal else {
+            // the final frame is drawn even if skipped, since the next one may not be for a while
+            if stale {
+                draw(&algorithm, state, renderer);
+            }
+            finished(&algorithm, state);
+            break Ok(steps)
+        };
+        if state.settings.hue_cycle != 0.0 {
+            state.shift_hue(state.settings.hue_cycle * elapsed.as_secs_f64());
+        }
+        match skipping {
+            0 => {
+                if !draw(&algorithm, state, renderer) {
+                    // the renderer failed, so stop and report its error once finished
+                    break Ok(steps)
+                }
+                skipping = frames_to_skip(renderer.write_time(), algorithm.delay(state) * chunk);
+                stale = false;
+            }, 
+            _ => {
+                skipping -= 1;
+                stale = true;
+            }, 
+        }
+        state.step();
+        steps += 1;
+
+        // wait from the start of the frame, such that the configured cadence is honoured regardless of the
+        // time spent stepping and rendering
+        let deadline = frame + algorithm.delay(state) * chunk;
+        if let Err(e) = wait(&mut algorithm, state, renderer, deadline) {
+            break Err(e)
+        }
+    };
+
+    state.active.clear();
+    result
+}
+
+/// Draws a frame of an animation (marking its heads and drawing its status and internals, if enabled) and
+/// presents it, returning whether the renderer is still running. 
+#[cfg(feature = "terminal")]
+fn draw<A: Animation>(algorithm: &A, state: &mut State<A::Phase>, renderer: &mut impl Renderer) -> bool {
+    if state.settings.mark_heads {
+        state.active = algorithm.heads();
+    }
+    renderer.set_layout(state.settings.layout);
+    state.draw(renderer);
+    if state.settings.hud && let Some(status) = A::Phase::status(state, algorithm.frontier()) {
+        renderer.draw_status(&status);
+    }
+    if state.settings.debug_panel {
+        renderer.draw_panel(&algorithm.debug_info());
+    }
+    renderer.present().is_ok()
+}
+
+/// Gets the number of frames to skip drawing after one, such that a terminal taking a given time writing each
+/// frame keeps up with a given delay between frames. 
+#[cfg(feature = "terminal")]
+fn frames_to_skip(write_time: Duration, delay: Duration) -> u32 {
+    let frames = write_time.as_nanos() / delay.as_nanos().max(1);
+    frames.min(MAX_SKIPPED_FRAMES as u128) as u32
+}
+
+/// Waits until a deadline, passing the keys pressed meanwhile to an animation (see [`Animation::key`]) and
+/// redrawing after each one handled. `i` toggles the panel of internals (see [`Settings::debug_panel`]), and
+/// `z` the layout (see [`Settings::layout`]). Fails with [`Error::Break`] upon any other input. 
+#[cfg(feature = "terminal")]
+fn wait<A: Animation>(
+    algorithm: &mut A, 
+    state: &mut State<A::Phase>, 
+    renderer: &mut impl Renderer, 
+    deadline: Instant, 
+) -> Result<(), Error> {
+    while terminal::poll(deadline.saturating_duration_since(Instant::now()))? {
+        match terminal::read()? {
+            // some platforms report releasing keys too, which shouldn't count twice
+            terminal::Event::Key(key) if key.kind == KeyEventKind::Release => (), 
+            terminal::Event::Key(key) if key.code == KeyCode::Char('i') => {
+                state.settings.debug_panel = !state.settings.debug_panel;
+                draw(algorithm, state, renderer);
+            }, 
+            terminal::Event::Key(key) if key.code == KeyCode::Char('z') => {
+                state.settings.layout = state.settings.layout.next();
+                draw(algorithm, state, renderer);
+            }, 
+            terminal::Event::Key(key) if algorithm.key(state, key) => {
+                // a failed renderer is noticed when drawing the next frame
+                draw(algorithm, state, renderer);
+            }, 
+            _ => return Err(Error::Break), 
+        }
+    }
+    Ok(())
+}
+
+/// Reports that an animation finished, if that concludes its phase (see [`Animation::finishes_phase`]). 
+pub(crate) fn finished<A: Animation>(algorithm: &A, state: &State<A::Phase>) {
+    if algorithm.finishes_phase() {
+        state.emit(Event::PhaseFinished(A::Phase::NAME));
+    }
+}
+
+#[derive(Clone)]
+pub struct Settings {
+    pub delay: Duration, 
+    pub ansi: bool, 
+    /// How colours are reduced to the 256-colour palette, if at all. 
+    pub indexing: Indexing, 
+    pub inline: bool, 
+    /// Whether each frame is wrapped in a synchronized update (mode 2026), such that terminals supporting it
+    /// show the frame at once rather than tearing as it's written. 
+    pub synchronized: bool, 
+    /// Captures the frames written to the terminal as a raw stream of escape sequences, or `None` not to (see
+    /// [`capture`]). 
+    pub capture: Option<Capture>, 
+    pub light: bool, 
+    pub grayscale: bool, 
+    /// Whether to derive colours told apart by lightness alone, contrasting strongly with each other. 
+    pub high_contrast: bool, 
+    pub hue_shift: f64, 
+    pub hue_cycle: f64, 
+    pub trail: u32, 
+    pub steps_per_frame: StepsPerFrame, 
+    pub theme: Theme, 
+    pub easing: Easing, 
+    pub wall: Wall, 
+    pub seed: u64, 
+    pub hooks: Hooks, 
+    /// Whether to check that generated mazes are perfect (see [`generate::verify`]). 
+    pub verify: bool, 
+    /// Whether to draw a line of live counters below the maze while animating (see [`Phase::status`]). 
+    pub hud: bool, 
+    /// Whether to draw a panel beside the maze showing the internals of the running algorithm (see
+    /// [`Animation::debug_info`]), toggled with `i` while animating. 
+    pub debug_panel: bool, 
+    /// Transition played between phases. 
+    pub fade: FadeStyle, 
+    /// Duration of a full fade, or `None` to scale it with the delay. 
+    pub fade_duration: Option<Duration>, 
+    /// How the solution is walked. 
+    pub walk: WalkStyle, 
+    /// Speed of the runner relative to the other animations (see [`WalkStyle::Runner`]). 
+    pub runner_speed: f64, 
+    /// Number of walkers, including the one walking the solution (see [`WalkStyle::Swarm`]). 
+    pub walkers: usize, 
+    /// Whether to celebrate the goal being reached before walking the solution (see [`celebrate::ring`]). 
+    pub celebrate: bool, 
+    /// Number of times a pulse travels back and forth along the solution once walked (see [`walk::pulse`]). 
+    pub pulses: u32, 
+    /// Finale played after walking the solution instead of just fading out, or `None` to skip it (see
+    /// [`Outro`]). 
+    pub outro: Option<Outro>, 
+    /// Whether to draw the nodes the running algorithm is working on in a bright colour (see
+    /// [`Animation::heads`]). 
+    pub mark_heads: bool, 
+    /// Radius around the nodes being visited within which the maze is drawn while solving, in nodes (at least
+    /// 0), or `None` to draw all of it (see [`Fog`](state::Fog)). 
+    pub fog: Option<f64>, 
+    /// Whether frames are drawn in coarser chunks, each shown for longer (see
+    /// [`Settings::with_reduced_motion`]). 
+    pub reduced_motion: bool, 
+    /// Whether cells are drawn as characters rather than colours (see [`render::symbol`]). 
+    pub symbols: bool, 
+    /// How the maze is laid out in cells, toggled with `z` while animating. 
+    pub layout: Layout, 
+    /// Glyphs drawn upon the start, the goal, and the path, if any. 
+    pub glyphs: Glyphs, 
+    /// Contents of each cell as laid out by default, one or two columns wide (see [`Settings::pitch`]). 
+    pub cell: String, 
+    /// Whether the title screen is played before generating (see [`title::play`]). 
+    pub title: bool, 
+    /// Terminal column and row the top left of the maze is drawn at, such that it may be centred (see
+    /// [`Terminal::with_origin`]). 
+    pub origin: (u16, u16), 
+    /// Tuning parameters of the algorithms (see [`options`]). 
+    pub options: Options, 
+    /// How the endpoints of mazes not marking their own are placed. 
+    pub endpoints: Endpoints, 
+    /// Whether the longest path through the maze is traced once generated (see [`diameter::trace`]). 
+    pub diameter: bool, 
+    /// Nodes solved from at once instead of the start alone, or `None` to solve from the start (see
+    /// [`solve::run`]). 
+    pub sources: Option<Sources>, 
+    /// Number of portal pairs placed in the maze before solving it, or 0 for none (see [`portals`]). 
+    pub portals: usize, 
+    /// Share of the passages made one-way before solving, from 0 to 1, each pointing toward the goal such that
+    /// it's still reached from everywhere (see [`Maze::one_way`]). 
+    pub one_way: f64, 
+    /// Number of timesteps between shifts of the maze while solving, each closing an edge and opening a wall
+    /// elsewhere, or `None` to keep the maze as generated (see [`solve::replan`]). 
+    pub shift_every: Option<u32>, 
+    /// Share of the nodes covered in ice before solving, from 0 to 1, upon which moves carry on until hitting
+    /// a wall (see [`ice`]). 
+    pub ice: f64, 
+    /// Whether the maze is given smooth elevation before solving, such that uphill moves cost more to solvers
+    /// weighing up costs and nodes are shaded by their height (see [`elevation`]). 
+    pub elevation: bool, 
+    /// Whether the nodes queued by A* are marked by their `f`-scores (see [`FScores`](solve::FScores)). 
+    pub f_scores: bool, 
+}
+
+/// Number of timesteps to advance between each rendered frame, as given by `--steps-per-frame`. 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepsPerFrame {
+    /// Scaled with the maze area, such that large mazes don't take forever to animate. 
+    Auto, 
+    Fixed(u32), 
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            delay: Duration::from_millis(60), 
+            ansi: false, 
+            indexing: Indexing::default(), 
+            inline: false, 
+            synchronized: false, 
+            capture: None, 
+            light: false, 
+            grayscale: false, 
+            high_contrast: false, 
+            hue_shift: 0.0, 
+            hue_cycle: 0.0, 
+            trail: u8::MAX as u32, 
+            steps_per_frame: StepsPerFrame::Fixed(1), 
+            theme: ThemeName::default().theme(), 
+            easing: Easing::default(), 
+            wall: Wall::default(), 
+            seed: rand::random(), 
+            hooks: Hooks::default(), 
+            verify: false, 
+            hud: false, 
+            debug_panel: false, 
+            fade: FadeStyle::default(), 
+            fade_duration: None, 
+            walk: WalkStyle::default(), 
+            runner_speed: 1.0, 
+            walkers: 5, 
+            celebrate: true, 
+            pulses: 2, 
+            outro: None, 
+            mark_heads: false, 
+            fog: None, 
+            reduced_motion: false, 
+            symbols: false, 
+            layout: Layout::default(), 
+            glyphs: Glyphs::default(), 
+            cell: CELL.to_owned(), 
+            title: false, 
+            origin: (0, 0), 
+            options: Options::default(), 
+            endpoints: Endpoints::default(), 
+            diameter: false, 
+            sources: None, 
+            portals: 0, 
+            one_way: 0.0, 
+            shift_every: None, 
+            ice: 0.0, 
+            elevation: false, 
+            f_scores: false, 
+        }
+    }
+}
+
+impl Settings {
+    /// Number of timesteps drawn as one frame with reduced motion, which is shown as long as they'd take. 
+    #[cfg(feature = "terminal")]
+    const REDUCED_MOTION_CHUNK: u32 = 4;
+
+    /// Tones down the animations for those sensitive to motion or flashing: the flash transition (see
+    /// [`FadeStyle::Flash`]), the hue cycle, the celebration and the pulses are disabled, and frames are drawn
+    /// in coarser chunks, such that colours change less often. 
+    pub fn with_reduced_motion(self) -> Settings {
+        let fade = match self.fade {
+            FadeStyle::Flash => FadeStyle::None, 
+            fade => fade, 
+        };
+        Settings {
+            fade, 
+            hue_cycle: 0.0, 
+            celebrate: false, 
+            pulses: 0, 
+            reduced_motion: true, 
+            ..self
+        }
+    }
+
+    /// Gets the number of columns each cell as laid out by default is wide, being the width of its contents
+    /// (see [`Settings::cell`]). 
+    pub fn pitch(&self) -> usize {
+        render::pitch(&self.cell)
+    }
+
+    /// Gets the number of timesteps drawn as one frame, scaling the steps of each frame and their delay. 
+    #[cfg(feature = "terminal")]
+    fn frame_chunk(&self) -> u32 {
+        match self.reduced_motion {
+            true => Settings::REDUCED_MOTION_CHUNK, 
+            false => 1, 
+        }
+    }
+}
+
+impl StepsPerFrame {
+    /// Gets the number of steps for a given maze. 
+    fn resolve(self, maze: &Maze) -> u32 {
+        match self {
+            StepsPerFrame::Auto => (maze.width * maze.height).div_ceil(1000) as u32, 
+            StepsPerFrame::Fixed(steps) => steps, 
+        }
+    }
+}
+
+impl FromStr for StepsPerFrame {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<StepsPerFrame, String> {
+        match s {
+            "auto" => Ok(StepsPerFrame::Auto), 
+            _ => match s.parse() {
+                Ok(steps) if steps > 0 => Ok(StepsPerFrame::Fixed(steps)), 
+                _ => Err("expected auto or a positive number".to_owned()), 
+            }, 
+        }
+    }
+}
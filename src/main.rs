@@ -1,180 +1,1070 @@
-use std::{io, time::Duration};
-use clap::{ArgAction, Parser};
+use std::{
+    fs::{self, File}, 
+    io::{self, BufWriter, Write}, 
+    path::Path, 
+    time::{Duration, Instant, SystemTime}, 
+};
+use clap::CommandFactory;
 use crossterm::{
-    cursor::{Hide, MoveTo, Show}, 
+    cursor::{Hide, MoveToColumn, MoveToPreviousLine, SavePosition, Show}, 
+    event::{self, Event, KeyCode, KeyModifiers}, 
     style::Print, 
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen}, 
+    terminal::{Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen}, 
+};
+use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
+use overlook::{
+    analysis::Analysis, 
+    colour::{Indexing, CELL}, 
+    bench::{self, Measurement, Record, Stats}, 
+    capture::{self, Capture}, 
+    console::{self, Multiplexer}, 
+    duel, 
+    events::Hooks, 
+    fade::{self, FadeStyle}, 
+    game, 
+    generate, 
+    import, 
+    maze::{Maze, Node}, 
+    net::{self, Connection, Offer}, 
+    options::Options, 
+    pipeline::{Overlook, Summary}, 
+    render::{self, Glyphs, Layout}, 
+    solve::{self, Endpoints, Solver}, 
+    themes::{PaletteOverride, Theme}, 
+    versus, 
+    walk::{self, WalkStyle}, 
+    Error, Settings, StepsPerFrame, 
 };
-use rand::Rng;
-use walk::Walker;
 use crate::{
-    generate::Generator, 
-    maze::Maze, 
-    solve::Solver, 
-    state::{Phase, State}, 
+    cli::{
+        AnimationArgs, BenchArgs, ChallengeArgs, Cli, ColourArgs, Command, CompareArgs, DuelArgs, ExportArgs, 
+        GenerateArgs, HostArgs, JoinArgs, MazeArgs, PlayArgs, RaceArgs, ReplayArgs, RunArgs, SolutionArgs, SolveArgs, 
+        VersusArgs, 
+    }, 
+    record::{Recorder, Recording}, 
 };
 
-mod fade;
-mod generate;
-mod colour;
-mod maze;
-mod state;
-mod solve;
-mod walk;
+mod cli;
+mod record;
+
+/// Delay between frames of games, in which only the players move, such that frames are just frequent enough
+/// to keep the colours aging. 
+const GAME_DELAY: Duration = Duration::from_millis(50);
+
+/// Sets up the terminal environment. 
+///
+/// In inline mode, the animation is drawn in the normal screen buffer (so that it remains in the scrollback
+/// after exiting) instead of the alternate screen. Since we can't draw relative to the top of the screen, we
+/// reserve `rows` lines below the cursor and save the position of the first one to draw from. 
+fn setup(inline: bool, rows: u16) -> io::Result<()> {
+    crossterm::terminal::enable_raw_mode()?;
+    let mut stdout = io::stdout();
+
+    if inline {
+        // scroll the terminal as needed before saving the position, since scrolling would invalidate it
+        crossterm::queue!(stdout, MoveToColumn(0), Print("\n".repeat(rows as usize - 1)))?;
+        if rows > 1 {
+            crossterm::queue!(stdout, MoveToPreviousLine(rows - 1))?;
+        }
+        crossterm::queue!(stdout, SavePosition)?;
+    } else {
+        crossterm::queue!(stdout, EnterAlternateScreen)?;
+    }
+    crossterm::execute!(stdout, Hide)?;
 
-/// Signals the algorithm runtime what to do after each timestep. 
-pub enum Signal {
-    Continue, 
-    Done, 
+    let prev = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        reset(inline);
+        prev(info);
+    }));
+    Ok(())
 }
 
-pub enum Error {
-    Io(io::Error),
-    Break, 
+/// Resets the terminal environment. In inline mode, the cursor is left on the line below the animation. 
+fn reset(inline: bool) {
+    let _ = match inline {
+        true => crossterm::execute!(std::io::stdout(), Print("\r\n"), Show), 
+        false => crossterm::execute!(std::io::stdout(), LeaveAlternateScreen, Show), 
+    };
+    let _ = crossterm::terminal::disable_raw_mode();
 }
 
-impl From<io::Error> for Error {
-    fn from(e: io::Error) -> Self {
-        Error::Io(e)
+/// Queries the background colour of the terminal (via OSC 11) to determine whether it's light, returning
+/// `None` if the terminal doesn't report it in time. Must be called in raw mode so that the response isn't
+/// echoed. Terminals inside a multiplexer aren't queried, since the multiplexer may answer in their place, not
+/// pass on the query, or print it as garbage (see [`Multiplexer`]). 
+///
+/// The response is read through the same event reader as keys, such that no input is lost to a terminal that
+/// doesn't answer. That reader doesn't know operating system commands, so it reports `ESC ]` as `Alt+]` and
+/// the rest of the response as the keys typing it, up to the terminator (`ESC \` or BEL, i.e. `Ctrl+G`). 
+fn detect_light_background() -> io::Result<Option<bool>> {
+    if Multiplexer::detect().is_some() {
+        return Ok(None)
     }
+    crossterm::execute!(io::stdout(), Print("\x1b]11;?\x1b\\"))?;
+
+    let deadline = Instant::now() + Duration::from_millis(200);
+    let mut response = None;
+    while event::poll(deadline.saturating_duration_since(Instant::now()))? {
+        let Event::Key(key) = event::read()? else {
+            continue
+        };
+        match (key.code, key.modifiers) {
+            (KeyCode::Char(']'), KeyModifiers::ALT) => response = Some(String::from("\x1b]")), 
+            (KeyCode::Char('\\'), KeyModifiers::ALT) | (KeyCode::Char('g'), KeyModifiers::CONTROL) => break, 
+            (KeyCode::Char(c), _) => response.iter_mut().for_each(|response| response.push(c)), 
+            _ => (), 
+        }
+    }
+    Ok(response.and_then(|response| parse_background(response.as_bytes())))
+}
+
+/// Parses a response of the form `ESC ] 11 ; rgb:RRRR/GGGG/BBBB` (with 1-4 hex digits per channel) to
+/// determine whether the colour is light. 
+fn parse_background(response: &[u8]) -> Option<bool> {
+    let response = std::str::from_utf8(response).ok()?;
+    let (_, colour) = response.split_once("]11;rgb:")?;
+    let colour = colour.split(['\x1b', '\x07']).next()?;
+    let channels = colour
+        .split('/')
+        .map(|x| {
+            let max = 16f64.powi(x.len() as i32) - 1.0;
+            u16::from_str_radix(x, 16).ok().map(|x| x as f64 / max)
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let &[r, g, b] = channels.as_slice() else {
+        return None
+    };
+    let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    Some(luminance > 0.5)
 }
 
-/// Generalisation over different algorithms being animated upon a maze. 
-pub trait Animation: Sized {
-    type Phase: Phase;
-    
-    /// Constructs the algorithm from a given state. 
-    fn new(state: &mut State<Self::Phase>) -> Self;
-    /// Moves the animation one timestep forward. 
-    fn step(&mut self, state: &mut State<Self::Phase>) -> Signal;
-    /// Animation timescale working in conjunction with [`Settings::delay`]. 
-    fn timescale(&self) -> u32 {
-        100
+impl ColourArgs {
+    /// Constructs settings for rendering without animating. The hue shift (if any) is drawn from the seed as
+    /// well, such that the colours are the same each time too. 
+    fn settings(&self, seed: u64) -> Settings {
+        let theme = self.theme();
+        let hue_shift = match theme.shift_hue && !self.grayscale {
+            true => StdRng::seed_from_u64(seed).gen_range(0.0..360.0), 
+            false => 0.0, 
+        };
+        Settings {
+            delay: Duration::ZERO, 
+            ansi: self.ansi, 
+            indexing: match (self.indexed, self.dither) {
+                (false, _) => Indexing::None, 
+                (true, false) => Indexing::Nearest, 
+                (true, true) => Indexing::Dither, 
+            }, 
+            inline: false, 
+            synchronized: self.synchronized, 
+            capture: None, 
+            light: self.light, 
+            grayscale: self.grayscale, 
+            high_contrast: self.high_contrast, 
+            hue_shift, 
+            hue_cycle: 0.0, 
+            trail: self.trail, 
+            steps_per_frame: StepsPerFrame::Fixed(1), 
+            theme, 
+            easing: self.easing, 
+            wall: self.wall, 
+            seed, 
+            hooks: Hooks::default(), 
+            verify: false, 
+            hud: false, 
+            debug_panel: false, 
+            fade: FadeStyle::default(), 
+            fade_duration: None, 
+            walk: WalkStyle::default(), 
+            runner_speed: 1.0, 
+            walkers: 5, 
+            celebrate: false, 
+            pulses: 0, 
+            outro: None, 
+            mark_heads: false, 
+            fog: None, 
+            reduced_motion: false, 
+            symbols: false, 
+            layout: Layout::default(), 
+            glyphs: Glyphs::default(), 
+            cell: CELL.to_owned(), 
+            title: false, 
+            origin: (0, 0), 
+            options: Options::default(), 
+            endpoints: Endpoints::default(), 
+            diameter: false, 
+            sources: None, 
+            portals: 0, 
+            one_way: 0.0, 
+            shift_every: None, 
+            ice: 0.0, 
+            elevation: false, 
+            f_scores: false, 
+        }
     }
-    
-    /// Runs the animation until it signals to stop, printing the current state at each timestep. 
-    fn run(state: &mut State<Self::Phase>) -> Result<(), Error> {
-        let mut algorithm = Self::new(state);
 
-        while let Signal::Continue = algorithm.step(state) {
-            crossterm::execute!{
-                io::stdout(), 
-                MoveTo(0, 0), 
-                Print(&state), 
-            }?;
-            state.step();
+    /// Constructs settings for rendering in the terminal, with colours reduced to those the terminal draws: 
+    /// the standard ones on legacy Windows consoles, and the 256-colour palette inside multiplexers unless the
+    /// terminal declares true colour support (see [`console`]). 
+    fn terminal_settings(&self, seed: u64) -> Settings {
+        let settings = self.settings(seed);
+        if console::is_legacy() {
+            return Settings{ ansi: true, indexing: Indexing::None, ..settings }
+        }
+        let indexing = match settings.indexing {
+            Indexing::None if !self.ansi && Multiplexer::detect().is_some() && !console::true_colour() => {
+                Indexing::Nearest
+            }, 
+            indexing => indexing, 
+        };
+        Settings{ indexing, ..settings }
+    }
 
-            let delay = 100 * state.settings.delay / algorithm.timescale();
+    /// Gets the theme with any palette overrides applied. Hue shifting is disabled if any colours are
+    /// overriden, since they were presumably chosen deliberately. 
+    fn theme(&self) -> Theme {
+        let theme = self.theme.theme();
+        let apply = |palette, overrides: &[PaletteOverride]| overrides
+            .iter()
+            .fold(palette, |palette, overrides| overrides.apply(palette));
+        let overriden = [&self.palette_generate, &self.palette_solve, &self.palette_walk]
+            .iter()
+            .any(|overrides| !overrides.is_empty());
 
-            if crossterm::event::poll(delay)? {
-                return Err(Error::Break)
-            }
+        Theme {
+            generate: apply(theme.generate, &self.palette_generate), 
+            solve: apply(theme.solve, &self.palette_solve), 
+            walk: apply(theme.walk, &self.palette_walk), 
+            shift_hue: theme.shift_hue && !overriden, 
         }
+    }
+}
+
+impl SolutionArgs {
+    /// Writes the solution walked in a state to the solution output, if any. 
+    fn write(&self, state: &walk::State) -> Result<(), Error> {
+        let Some(output) = self.solution() else {
+            return Ok(())
+        };
+        let solution = state.phase
+            .solution()
+            .ok_or_else(|| Error::InvalidMaze("no solution was found".to_owned()))?;
+        output.write(&solution)?;
         Ok(())
     }
 }
 
-/// Sets up the terminal environment. 
-fn setup() -> io::Result<()> {
+impl MazeArgs {
+    /// Gets the maze dimensions, filling a terminal of given size (within the margin) for those not specified. 
+    /// Fails if the terminal is too small to fit a maze of at least 2x2 nodes. 
+    fn dimensions(&self, size: (u16, u16)) -> Result<(u16, u16), Error> {
+        self.dimensions_in(size, Layout::Cells, 2)
+    }
+
+    /// Gets the maze dimensions as [`MazeArgs::dimensions`] does, for a maze laid out as given with cells of
+    /// given pitch (see [`Layout::columns`]). 
+    fn dimensions_in(
+        &self, 
+        (columns, rows): (u16, u16), 
+        layout: Layout, 
+        pitch: usize, 
+    ) -> Result<(u16, u16), Error> {
+        // the margin is in cells as laid out by default
+        let margin = (2 * pitch as u16 * self.margin, 2 * self.margin);
+        let (columns, rows) = (columns.saturating_sub(margin.0), rows.saturating_sub(margin.1));
+        let (fill_width, fill_height) = layout.fitting((columns as usize, rows as usize), pitch);
+        let width = self.width().unwrap_or(fill_width.min(u16::MAX as usize) as u16);
+        let height = self.height().unwrap_or(fill_height.min(u16::MAX as usize) as u16);
+        match width >= 2 && height >= 2 {
+            true => Ok((width, height)), 
+            false => Err(Error::TerminalTooSmall{ columns, rows }), 
+        }
+    }
+
+    /// Gets the maze dimensions, filling the terminal (or a default-sized one if there is none) for those not
+    /// specified. 
+    fn dimensions_headless(&self) -> Result<(u16, u16), Error> {
+        let terminal_size = crossterm::terminal::size().unwrap_or((80, 24));
+        self.dimensions(terminal_size)
+    }
+
+    /// Gets the seed, or the date if daily, or a random one if not specified. 
+    fn seed(&self) -> u64 {
+        match (self.seed, self.daily) {
+            (Some(seed), _) => seed, 
+            (None, true) => daily_seed(), 
+            (None, false) => rand::random(), 
+        }
+    }
+}
+
+/// Gets the terminal column and row at which a maze of given dimensions is drawn centred in a terminal of
+/// given size, or at the top left if it doesn't fit. 
+fn centre(size: (u16, u16), area: (u16, u16)) -> (u16, u16) {
+    centre_in(size, area, Layout::Cells, 2)
+}
+
+/// Gets the terminal column and row the top left of a maze laid out as given (with cells of given pitch) is
+/// drawn at, as [`centre`] does. 
+fn centre_in(size: (u16, u16), (columns, rows): (u16, u16), layout: Layout, pitch: usize) -> (u16, u16) {
+    let (extent_x, extent_y) = layout.extent((size.0 as usize, size.1 as usize), pitch);
+    let x = (columns as usize).saturating_sub(extent_x) / 2;
+    let y = (rows as usize).saturating_sub(extent_y) / 2;
+    (x as u16, y as u16)
+}
+
+/// Gets the current date (in UTC) as a number of the form YYYYMMDD, which is the seed of daily mazes. 
+fn daily_seed() -> u64 {
+    let days = SystemTime::UNIX_EPOCH.elapsed().map_or(0, |elapsed| elapsed.as_secs() / 86400);
+    // converts days since 1970 to a date in the proleptic Gregorian calendar, counting years from March such
+    // that leap days come last (see https://howardhinnant.github.io/date_algorithms.html#civil_from_days)
+    let days = days + 719468;
+    let (era, day_of_era) = (days / 146097, days % 146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let (year, month) = match month < 10 {
+        true => (400 * era + year_of_era, month + 3), 
+        false => (400 * era + year_of_era + 1, month - 9), 
+    };
+    10000 * year + 100 * month + day
+}
+
+/// Runs the pipeline built from the settings with the terminal set up, resetting it afterwards and writing
+/// the final maze to the output (if any). `rows` is the number of rows needed to draw the maze. 
+///
+/// If frames are dumped instead, the terminal is left untouched and the frames are written as plain text
+/// to numbered files in the directory, as quickly as possible. 
+fn animate(
+    args: &AnimationArgs, 
+    rows: u16, 
+    seed: u64, 
+    pipeline: impl FnOnce(Settings) -> Result<Overlook, Error>, 
+) -> Result<(), Error> {
+    // resolved up front, such that an output of unknown format fails before the run rather than after it
+    let output = args.output()?;
+    let mut settings = Settings {
+        delay: args.delay.into(), 
+        inline: args.inline.is_some(), 
+        hue_cycle: args.hue_cycle.unwrap_or(0.0), 
+        steps_per_frame: args.steps_per_frame, 
+        hud: args.hud, 
+        debug_panel: args.debug_panel, 
+        fade: args.fade_style, 
+        fade_duration: args.fade_duration.map(Into::into), 
+        walk: args.walk, 
+        runner_speed: args.runner_speed, 
+        walkers: args.walkers as usize, 
+        celebrate: !args.no_celebration, 
+        pulses: args.pulses, 
+        outro: args.outro, 
+        mark_heads: args.mark_heads, 
+        fog: args.fog, 
+        symbols: args.symbols, 
+        layout: args.layout, 
+        glyphs: args.glyphs.unwrap_or_default(), 
+        cell: args.cell.clone(), 
+        title: args.title, 
+        options: args.algo_opt.iter().fold(Options::default(), |options, &given| options.or(given)), 
+        endpoints: args.endpoints, 
+        diameter: args.diameter, 
+        sources: args.starts.clone(), 
+        portals: args.portals, 
+        one_way: args.one_way, 
+        shift_every: args.shift_every, 
+        ice: args.ice, 
+        elevation: args.elevation, 
+        f_scores: args.f_scores, 
+        ..args.colours.terminal_settings(seed)
+    };
+    if args.reduced_motion {
+        settings = settings.with_reduced_motion();
+    }
+    let state = match &args.dump_frames {
+        Some(dir) => dump_frames(dir, pipeline(settings)?)?.0, 
+        None => {
+            let inline = settings.inline;
+            // the HUD is drawn on the row below the maze
+            let rows = rows + args.hud as u16;
+            if let Some(path) = &args.record_ansi {
+                // frames are drawn on legacy consoles by issuing commands, leaving no escape sequences to record
+                if console::is_legacy() {
+                    let message = "--record-ansi isn't supported on legacy Windows consoles".to_owned();
+                    return Err(Error::InvalidArguments(message))
+                }
+                settings.capture = Some(capture(path, inline, rows, !args.strip_timing)?);
+            }
+            let capture = settings.capture.clone();
+            let result = setup(inline, rows).map_err(Error::from).and_then(|_| {
+                if args.detect_background {
+                    settings.light = detect_light_background()?.unwrap_or(settings.light);
+                }
+                pipeline(settings)?.run()
+            });
+            reset(inline);
+            if let Some(capture) = capture {
+                capture.finish()?;
+            }
+            // the run is summarised once the screen is restored, such that it's left behind in the terminal
+            let (state, summary) = result?;
+            eprint!("{summary}");
+            state
+        }, 
+    };
+    if let Some(output) = output {
+        output.write(&state)?;
+    }
+    args.solution.write(&state)?;
+    if args.analysis.stats {
+        eprint!("{}", Analysis::new(&state.maze, state.phase.solution().as_ref()));
+    }
+    Ok(())
+}
+
+/// Creates a file capturing the frames of a run (see `--record-ansi`), starting with what's needed to draw
+/// them as they are in the terminal once set up: the screen cleared, or in inline mode `rows` lines reserved
+/// below the cursor (see [`setup`]). 
+fn capture(path: &Path, inline: bool, rows: u16, timing: bool) -> io::Result<Capture> {
+    let mut file = BufWriter::new(File::create(path)?);
+    match inline {
+        true => {
+            crossterm::queue!(file, MoveToColumn(0), Print("\n".repeat(rows as usize - 1)))?;
+            if rows > 1 {
+                crossterm::queue!(file, MoveToPreviousLine(rows - 1))?;
+            }
+            crossterm::queue!(file, SavePosition)?;
+        }, 
+        false => crossterm::queue!(file, Clear(ClearType::All))?, 
+    }
+    Ok(Capture::new(file, timing))
+}
+
+/// Plays back a stream of escape sequences written by `--record-ansi`, pausing for the delays embedded in it. 
+/// Any key skips the remaining delays, drawing the rest of the stream (and so the final frame) at once. 
+fn play(args: PlayArgs) -> Result<(), Error> {
+    let stream = fs::read_to_string(&args.file)?;
     crossterm::terminal::enable_raw_mode()?;
-    crossterm::execute!{
-        std::io::stdout(), 
-        EnterAlternateScreen, 
-        Hide, 
-    }?;
-    let prev = std::panic::take_hook();
-    std::panic::set_hook(Box::new(move |info| {
-        reset();
-        prev(info);
-    }));
+    crossterm::execute!(io::stdout(), Hide)?;
+    let result = play_stream(&stream);
+    // the stream ends on the line below the final frame, so only the cursor and raw mode are reset
+    let _ = crossterm::execute!(io::stdout(), Show);
+    let _ = crossterm::terminal::disable_raw_mode();
+    Ok(result?)
+}
+
+/// Writes the chunks of a stream to stdout after their delays, until a key is pressed. 
+fn play_stream(stream: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    let mut skipping = false;
+    for (delay, chunk) in capture::chunks(stream) {
+        if !skipping && event::poll(delay)? {
+            event::read()?;
+            skipping = true;
+        }
+        stdout.write_all(chunk.as_bytes())?;
+        stdout.flush()?;
+    }
     Ok(())
 }
 
-/// Resets the terminal environment. 
-fn reset() {
-    let _ = crossterm::execute!{
-        std::io::stdout(), 
-        LeaveAlternateScreen, 
-        Show, 
+/// Runs a pipeline without a terminal, writing each frame to a numbered file in a directory, returning the
+/// final state and a summary of the run. 
+fn dump_frames(dir: &Path, pipeline: Overlook) -> Result<(walk::State, Summary), Error> {
+    fs::create_dir_all(dir)?;
+    let mut index = 0;
+    pipeline.run_frames(|frame| {
+        fs::write(dir.join(format!("{index:06}.txt")), frame.to_string())?;
+        index += 1;
+        Ok(())
+    })
+}
+
+/// Generates and solves a maze (or solves a loaded one), animated, recording the run if requested. 
+fn run(mut args: RunArgs) -> Result<(), Error> {
+    if args.looping {
+        return run_loop(args)
+    }
+    args.apply_code();
+    let recorder = args.record_run.is_some().then(Recorder::default);
+    let pinned = run_with(&args, recorder.as_ref())?;
+
+    let (Some(path), Some(recorder)) = (&args.record_run, recorder) else {
+        return Ok(())
     };
-    let _ = crossterm::terminal::disable_raw_mode();
+    let args = std::env::args_os()
+        .skip(1)
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .chain(pinned)
+        .collect();
+    Recording{ args, events: recorder.events() }.write(path)
+}
+
+/// Runs mazes one after the other until a key is pressed, each with a new seed, and the generator and solver
+/// chosen at random (weighted as given) unless given. 
+fn run_loop(mut args: RunArgs) -> Result<(), Error> {
+    let (generator, solver) = (args.generator, args.solver);
+    let weights = args.weights.take().unwrap_or_default();
+    // seeds are derived from the given one, such that the whole series is reproducible
+    let mut rng = StdRng::seed_from_u64(args.maze.seed());
+    loop {
+        args.generator = generator.or_else(|| generate::GENERATORS.choose(&mut rng, |name| weights.weight(name)));
+        args.solver = solver.or_else(|| solve::SOLVERS.choose(&mut rng, |name| weights.weight(name)));
+        if args.generator.is_none() || args.solver.is_none() {
+            return Err(Error::InvalidArguments("the weights of all generators or solvers are zero".to_owned()))
+        }
+        args.maze.seed = Some(rng.next_u64());
+        match run_with(&args, None) {
+            // a key press is the only way out of the loop, so it ends it as usual
+            Err(Error::Break) => return Ok(()), 
+            result => result?, 
+        };
+    }
+}
+
+/// Plays back a recorded run, failing if it diverges from the recording. 
+fn replay(args: ReplayArgs) -> Result<(), Error> {
+    let recording = Recording::read(&args.file)?;
+    let command = Cli::parse_command_from(["overlook".to_owned()].into_iter().chain(recording.args));
+    let Ok(Command::Run(mut run)) = command else {
+        return Err(Error::InvalidArguments(format!("{} doesn't record the arguments of a run", args.file.display())))
+    };
+    run.record_run = None;
+    run.apply_code();
+    if let Some(delay) = args.delay {
+        run.animation.delay = delay;
+    }
+    if args.output.is_some() {
+        run.animation.output = args.output;
+        run.animation.output_format = args.output_format;
+    }
+
+    let recorder = Recorder::default();
+    run_with(&run, Some(&recorder))?;
+    let events = recorder.events();
+    let diverged = events
+        .iter()
+        .zip(&recording.events)
+        .position(|(event, recorded)| event != recorded)
+        .or((events.len() != recording.events.len()).then(|| usize::min(events.len(), recording.events.len())));
+    match diverged {
+        Some(i) => Err(Error::InvalidArguments(format!("the replay diverged from the recording at event {i}"))), 
+        None => Ok(()), 
+    }
 }
 
-#[derive(Parser)]
-#[clap(disable_help_flag = true)]
-struct Cli {
-    /// Maze width in nodes. 
-    #[arg(long, short)]
-    #[arg(value_parser = clap::value_parser!(u16).range(2..))]
-    width: Option<u16>, 
+/// Runs as given by the arguments, reporting all events to the recorder (if any). Returns the arguments
+/// pinning down what was chosen at random or to fit the terminal (the seed and the dimensions), such that
+/// the run may be reproduced. 
+fn run_with(args: &RunArgs, recorder: Option<&Recorder>) -> Result<Vec<String>, Error> {
+    let seed = args.maze.seed();
+    let mut pinned = match args.maze.seed {
+        Some(_) => Vec::new(), 
+        None => vec!["--seed".to_owned(), seed.to_string()], 
+    };
+    let solver = args.solver.expect("solver is required unless given a code");
+    if let Some(path) = &args.load {
+        solve_file(path, solver, seed, (None, None), &args.animation)?;
+        return Ok(pinned)
+    }
+    let generator = args.generator.expect("generator is required unless loading");
+    let save = args.save.clone().map(|target| target.with_format(None)).transpose()?;
+    let terminal_size = match args.animation.dump_frames {
+        // frames may be dumped without a terminal
+        Some(_) => crossterm::terminal::size().unwrap_or((80, 24)), 
+        None => crossterm::terminal::size()?, 
+    };
+    // the HUD is drawn on the row below the maze, so the maze fits in the rows above it
+    let area = (terminal_size.0, terminal_size.1.saturating_sub(args.animation.hud as u16));
+    let (layout, pitch) = (args.animation.layout, render::pitch(&args.animation.cell));
+    let (width, height) = args.maze.dimensions_in(area, layout, pitch)?;
+    let height = match args.animation.inline {
+        // clamp the height since scrolling the terminal would break the inline drawing
+        Some(inline_rows) => {
+            let rows = u16::min(inline_rows.unwrap_or(terminal_size.1 / 2), terminal_size.1);
+            let fitting = layout.fitting((0, rows as usize), pitch).1 as u16;
+            args.maze.height().unwrap_or(fitting).min(fitting)
+        }
+        None => height, 
+    };
 
-    /// Maze height in nodes. 
-    #[arg(long, short)]
-    #[arg(value_parser = clap::value_parser!(u16).range(2..))]
-    height: Option<u16>, 
+    if args.maze.width().is_none() {
+        pinned.extend(["--width".to_owned(), width.to_string()]);
+    }
+    if args.maze.height().is_none() {
+        pinned.extend(["--height".to_owned(), height.to_string()]);
+    }
 
-    #[arg(long, short)]
-    generator: Generator, 
+    // fall back to denser layouts for mazes too large to fit as laid out
+    let layout = layout.fit((width as usize, height as usize), (area.0 as usize, area.1 as usize), pitch);
+    let origin = centre_in((width, height), area, layout, pitch);
+    let rows = layout.extent((width as usize, height as usize), pitch).1 as u16;
+    animate(&args.animation, rows, seed, |mut settings| {
+        if let Some(recorder) = recorder {
+            recorder.attach(&mut settings.hooks);
+        }
+        settings.origin = origin;
+        settings.layout = layout;
+        Overlook::builder()
+            .size(width, height)
+            .generator(generator)
+            .solver(solver)
+            .settings(settings)
+            .verify(args.maze.verify)
+            .save(save)
+            .min_difficulty(args.difficulty.min_difficulty)
+            .chase(args.chase)
+            .build()
+    })?;
+    Ok(pinned)
+}
+
+/// Solves a maze loaded from a file, animated. 
+fn solve(args: SolveArgs) -> Result<(), Error> {
+    let seed = args.seed.unwrap_or_else(rand::random);
+    solve_file(&args.file, args.solver, seed, (args.start, args.goal), &args.animation)
+}
 
-    #[arg(long, short)]
+/// Loads a maze from a file and solves it, animated, from and to the given endpoints (if any) rather than
+/// those marked in the file or placed as set by `--endpoints`. 
+fn solve_file(
+    path: &Path, 
     solver: Solver, 
+    seed: u64, 
+    (start, goal): (Option<Node>, Option<Node>), 
+    args: &AnimationArgs, 
+) -> Result<(), Error> {
+    let mut maze = import::read(path)?;
+    let within = |node: Option<Node>, flag: &str| match node {
+        Some(Node(x, y)) => maze
+            .node(x, y)
+            .map(Some)
+            .ok_or_else(|| Error::InvalidArguments(format!("{flag} {x}:{y} is outside the maze"))), 
+        None => Ok(None), 
+    };
+    let (start, goal) = (within(start, "--start")?, within(goal, "--goal")?);
+    maze.start = start.or(maze.start);
+    maze.goal = goal.or(maze.goal);
+    let (columns, terminal_rows) = crossterm::terminal::size().unwrap_or((80, 24));
+    // the HUD is drawn on the row below the maze
+    let area = (columns, terminal_rows.saturating_sub(args.hud as u16));
+    // fall back to denser layouts for mazes too large to fit as laid out
+    let pitch = render::pitch(&args.cell);
+    let layout = args.layout.fit((maze.width, maze.height), (area.0 as usize, area.1 as usize), pitch);
+    let rows = layout.extent((maze.width, maze.height), pitch).1 as u16;
+    let origin = centre_in((maze.width as u16, maze.height as u16), area, layout, pitch);
 
-    /// Animation timestep. 
-    #[arg(long, short, default_value="60ms")]
-    delay: humantime::Duration, 
+    animate(args, rows, seed, |settings| Overlook::builder()
+        .maze(maze)
+        .solver(solver)
+        .settings(Settings{ origin, layout, ..settings })
+        .build()
+    )
+}
 
-    /// Renders the maze using only standard ANSI colours. 
-    #[arg(long, short)]
-    ansi: bool, 
+/// Generates a maze without animating it and writes it to the output. 
+fn generate(args: GenerateArgs) -> Result<(), Error> {
+    let (width, height) = args.maze.dimensions_headless()?;
+    let mut settings = Settings {
+        verify: args.maze.verify, 
+        ..ColourArgs::default().settings(args.maze.seed())
+    };
+    let parallel = args.tiling.parallel;
+    if let Some(min_difficulty) = args.difficulty.min_difficulty {
+        let size = (width, height);
+        settings.seed = generate::seed_with_difficulty(args.generator, size, parallel, &settings, min_difficulty)?;
+    }
+    let mut state = generate::state(Maze::new(width, height), settings);
+    match parallel {
+        true => generate::run_parallel(args.generator, &mut state), 
+        false => args.generator.run_headless(&mut state), 
+    };
+    generate::verify(&state)?;
+    args.output.output()?.write(&state)?;
+    if args.analysis.stats {
+        eprint!("{}", Analysis::new(&state.maze, solve::shortest(&state.maze).as_ref()));
+    }
+    Ok(())
+}
 
-    /// Print help. 
-    #[arg(long, action=ArgAction::HelpLong)]
-    help: Option<bool>, 
+/// Generates and solves a maze without animating it and writes the solved maze to the output. 
+fn export(args: ExportArgs) -> Result<(), Error> {
+    let (width, height) = args.maze.dimensions_headless()?;
+    let (state, _) = Overlook::builder()
+        .size(width, height)
+        .generator(args.generator)
+        .solver(args.solver)
+        .parallel(args.tiling.parallel)
+        .settings(args.colours.settings(args.maze.seed()))
+        .verify(args.maze.verify)
+        .min_difficulty(args.difficulty.min_difficulty)
+        .build()?
+        .run_headless()?;
+    args.output.output()?.write(&state)?;
+    args.solution.write(&state)?;
+    if args.analysis.stats {
+        eprint!("{}", Analysis::new(&state.maze, state.phase.solution().as_ref()));
+    }
+    Ok(())
 }
 
-pub struct Settings {
-    pub delay: Duration, 
-    pub ansi: bool, 
-    pub hue_shift: f64, 
+/// Generates and solves mazes repeatedly with each combination of generator and solver without animating
+/// them, printing a table comparing their averaged statistics. 
+fn bench(args: BenchArgs) -> Result<(), Error> {
+    let (width, height) = args.maze.dimensions_headless()?;
+    // each run gets its own seed, derived from the given one such that the whole benchmark is reproducible.
+    // the same seeds are used for all combinations, such that generators are compared on the same mazes
+    let mut seeds = StdRng::seed_from_u64(args.maze.seed());
+    let seeds: Vec<u64> = (0..args.runs).map(|_| seeds.next_u64()).collect();
+
+    let (mut rows, mut records) = (Vec::new(), Vec::new());
+    for generator in args.generators() {
+        for solver in args.solvers() {
+            let mut stats = Stats::default();
+            for &seed in &seeds {
+                let settings = Settings{ verify: args.maze.verify, ..ColourArgs::default().settings(seed) };
+                let run = Measurement::run(generator, solver, args.tiling.parallel, (width, height), settings)?;
+                stats.add(run);
+                records.push(Record::new(seed, generator, solver, (width, height), run));
+            }
+            let mean = stats.mean();
+            rows.push([
+                generator.name().to_owned(), 
+                solver.name().to_owned(), 
+                mean.generate_steps.to_string(), 
+                format!("{:.2?}", mean.generate_time), 
+                mean.solve_steps.to_string(), 
+                format!("{:.2?}", mean.solve_time), 
+                mean.visited.to_string(), 
+                mean.path_length.to_string(), 
+            ]);
+        }
+    }
+
+    if let Some(format) = args.format {
+        bench::write_records(io::stdout().lock(), format, &records)?;
+        return Ok(())
+    }
+    println!("maze size: {width}x{height}, runs: {}", args.runs);
+    println!();
+    let header = ["generator", "solver", "gen steps", "gen time", "solve steps", "solve time", "visited", "path length"];
+    print_table(header, &rows, 2);
+    Ok(())
 }
 
-fn main() {
-    fn inner() -> Result<(), Error> {
-        let cli = Cli::parse();
+/// Generates a single maze and solves it with each solver, either without animating or animating one after
+/// the other, printing a table comparing them. 
+fn compare(args: CompareArgs) -> Result<(), Error> {
+    let (width, height) = match args.animate {
+        true => args.maze.dimensions(crossterm::terminal::size()?)?, 
+        false => args.maze.dimensions_headless()?, 
+    };
+    let settings = Settings {
+        delay: args.delay.into(), 
+        verify: args.maze.verify, 
+        ..args.colours.terminal_settings(args.maze.seed())
+    };
+    let mut state = generate::state(Maze::new(width, height), settings.clone());
+    args.generator.run_headless(&mut state);
+    generate::verify(&state)?;
+    let maze = state.maze;
+
+    if args.animate {
+        setup(false, 2 * height - 1)?;
+    }
+    let result = args.solvers().into_iter().try_fold(Vec::new(), |mut rows, solver| {
+        // each solver gets the same maze and the same seed
+        let mut state = solve::given(maze.clone(), settings.clone());
+        let steps = match args.animate {
+            true => {
+                let steps = solver.run(&mut state)?;
+                fade::out(&mut state)?;
+                steps
+            }, 
+            false => solver.run_headless(&mut state), 
+        };
+        let path_length = state.phase.solution().map_or(0, |solution| solution.path.len());
+        rows.push([
+            solver.name().to_owned(), 
+            steps.to_string(), 
+            state.visited_count.to_string(), 
+            path_length.to_string(), 
+        ]);
+        Ok::<_, Error>(rows)
+    });
+    if args.animate {
+        reset(false);
+    }
+
+    let rows = result?;
+    println!("generator: {}, maze size: {width}x{height}", args.generator.name());
+    println!();
+    print_table(["solver", "steps", "visited", "path length"], &rows, 1);
+    Ok(())
+}
+
+/// Generates a maze (or takes that of a ghost) and races the user against a solver through it, printing the
+/// results once either of them reached the goal, and recording the race if requested. 
+fn race(mut args: RaceArgs) -> Result<(), Error> {
+    let ghost = args.ghost.take().map(|path| ghost(&path, &mut args)).transpose()?;
+    let generator = args.generator.expect("generator is required unless racing a ghost");
+    let terminal_size = crossterm::terminal::size()?;
+    let (width, height) = args.maze.dimensions(terminal_size)?;
+    let seed = args.maze.seed();
+    let mut settings = Settings {
+        delay: args.delay.into(), 
+        verify: args.maze.verify, 
+        origin: centre((width, height), terminal_size), 
+        ..args.colours.terminal_settings(seed)
+    };
+    let recorder = args.record_run.is_some().then(Recorder::default);
+    if let Some(recorder) = &recorder {
+        recorder.attach(&mut settings.hooks);
+    }
+    let mut state = generate::state(Maze::new(width, height), settings);
+    generator.run_headless(&mut state);
+    generate::verify(&state)?;
+    let mut state = solve::state(state);
 
-        setup()?;
+    setup(false, 2 * height - 1)?;
+    let result = game::race(args.solver, ghost, &mut state);
+    reset(false);
+    print!("{}", result?);
 
-        let terminal_size = crossterm::terminal::size()?;
-        let width = cli.width.unwrap_or(terminal_size.0 / 4);
-        let height = cli.height.unwrap_or(terminal_size.1 / 2);
+    let (Some(path), Some(recorder)) = (&args.record_run, recorder) else {
+        return Ok(())
+    };
+    // the arguments are pinned down in full rather than taken as given, such that a race against a ghost is
+    // recorded as standing on its own
+    let args = [
+        "race", "--generator", generator.name(), "--solver", args.solver.name(), 
+        "--seed", &seed.to_string(), "--width", &width.to_string(), "--height", &height.to_string(), 
+        "--delay", &args.delay.to_string(), 
+    ];
+    Recording{ args: args.map(str::to_owned).to_vec(), events: recorder.events() }.write(path)
+}
+
+/// Reads a recording to race as a ghost, taking the maze to race through from it. Returns the moves the ghost
+/// replays: those of the player of a recorded race, or the nodes visited by the solver of a recorded run. 
+fn ghost(path: &Path, args: &mut RaceArgs) -> Result<Vec<(i64, Node)>, Error> {
+    let recording = Recording::read(path)?;
+    let recorded = recording.args.iter().cloned();
+    let command = Cli::parse_command_from(["overlook".to_owned()].into_iter().chain(recorded));
+    match command {
+        Ok(Command::Race(race)) => {
+            (args.maze, args.generator) = (race.maze, race.generator);
+            // replayed at the pace it was raced at, such that the ghost moves as quickly as the player did
+            args.delay = race.delay;
+            Ok(recording.moves())
+        }, 
+        Ok(Command::Run(mut run)) if run.load.is_none() => {
+            run.apply_code();
+            (args.maze, args.generator) = (run.maze, run.generator);
+            Ok(recording.visits())
+        }, 
+        _ => Err(Error::InvalidArguments(format!("{} doesn't record a race or a run", path.display()))), 
+    }
+}
+
+/// Races two users through copies of a generated maze side by side, printing the outcome once either reached
+/// their goal or gave up. 
+fn duel(args: DuelArgs) -> Result<(), Error> {
+    let (width, height, settings) = side_by_side(&args.maze, &args.colours, GAME_DELAY)?;
+    let mut state = generate::state(Maze::new(width, height), settings);
+    args.generator.run_headless(&mut state);
+    generate::verify(&state)?;
+
+    setup(false, 2 * height - 1)?;
+    let result = duel::play(&state.maze, state.settings.clone());
+    reset(false);
+    print!("{}", result?);
+    Ok(())
+}
+
+/// Gets the dimensions of each of two mazes drawn side by side, filling half the terminal (less the column of
+/// nodes between them) for those not specified, along with the settings drawing the pair centred in the
+/// terminal at the given delay between frames. 
+fn side_by_side(maze: &MazeArgs, colours: &ColourArgs, delay: Duration) -> Result<(u16, u16, Settings), Error> {
+    let terminal_size = crossterm::terminal::size()?;
+    let half = ((terminal_size.0 / 2).saturating_sub(2), terminal_size.1);
+    let (width, height) = maze.dimensions(half)?;
+    let settings = Settings {
+        delay, 
+        verify: maze.verify, 
+        origin: centre((2 * width + 1, height), terminal_size), 
+        ..colours.terminal_settings(maze.seed())
+    };
+    Ok((width, height, settings))
+}
+
+/// Generates a maze and waits for another instance to join, racing them through it over the network and
+/// printing the outcome once either reached the goal or left. 
+fn host(args: HostArgs) -> Result<(), Error> {
+    let (width, height) = args.maze.dimensions(crossterm::terminal::size()?)?;
+    let offer = Offer {
+        width, 
+        height, 
+        generator: args.generator, 
+        seed: args.maze.seed(), 
+    };
+    println!("waiting for an opponent on port {} (`overlook join <address>:{}`)", args.port, args.port);
+    let connection = Connection::host(args.port, offer)?;
+    remote_race(connection, offer, &args.colours, args.maze.verify)
+}
+
+/// Joins a race hosted by another instance, printing the outcome once either reached the goal or left. 
+fn join(args: JoinArgs) -> Result<(), Error> {
+    let address = match args.address.contains(':') {
+        true => args.address, 
+        false => format!("{}:{}", args.address, net::DEFAULT_PORT), 
+    };
+    let (connection, offer) = Connection::join(address)?;
+    remote_race(connection, offer, &args.colours, false)
+}
+
+/// Generates the maze offered for a network race and races the other instance through it. 
+fn remote_race(connection: Connection, offer: Offer, colours: &ColourArgs, verify: bool) -> Result<(), Error> {
+    let terminal_size = crossterm::terminal::size()?;
+    // checked before generating, since the offered dimensions may be far larger than the terminal
+    net::fits((offer.width, offer.height), terminal_size)?;
+    let settings = Settings {
+        delay: GAME_DELAY, 
+        verify, 
+        origin: centre((offer.width, offer.height), terminal_size), 
+        ..colours.terminal_settings(offer.seed)
+    };
+    let mut state = generate::state(Maze::new(offer.width, offer.height), settings);
+    offer.generator.run_headless(&mut state);
+    generate::verify(&state)?;
+    let mut state = solve::state(state);
+
+    setup(false, 2 * offer.height - 1)?;
+    let result = net::race(connection, &mut state);
+    reset(false);
+    print!("{}", result?);
+    Ok(())
+}
+
+/// Compares two generators upon mazes of the same size, animated side by side, printing a table comparing
+/// them once both are done. 
+fn versus(args: VersusArgs) -> Result<(), Error> {
+    let (width, height, settings) = side_by_side(&args.maze, &args.colours, args.delay.into())?;
+
+    setup(false, 2 * height - 1)?;
+    let result = versus::play([args.left, args.right], (width, height), settings);
+    reset(false);
+    println!("maze size: {width}x{height}");
+    println!();
+    print!("{}", result?);
+    Ok(())
+}
+
+/// Presents mazes of increasing size for the user to solve one after the other, up to the given dimensions,
+/// printing a table of their scores once finished or given up. 
+fn challenge(args: ChallengeArgs) -> Result<(), Error> {
+    let terminal_size = crossterm::terminal::size()?;
+    let (width, height) = args.maze.dimensions(terminal_size)?;
+    // each maze gets its own seed, derived from the given one such that the whole series is reproducible
+    let mut seeds = StdRng::seed_from_u64(args.maze.seed());
+    let mut scores = Vec::new();
+
+    setup(false, 2 * height - 1)?;
+    let result = (1..=args.rounds).try_for_each(|round| {
+        let scale = |x: u16| u16::max((x as u32 * round / args.rounds) as u16, 2);
+        let (width, height) = (scale(width), scale(height));
         let settings = Settings {
-            delay: cli.delay.into(), 
-            hue_shift: rand::thread_rng().gen_range(0.0..360.0), 
-            ansi: cli.ansi, 
+            delay: GAME_DELAY, 
+            verify: args.maze.verify, 
+            // the mazes grow each round, so each covers the one before it
+            origin: centre((width, height), terminal_size), 
+            ..args.colours.terminal_settings(seeds.next_u64())
         };
-        let maze = Maze::new(width, height);
-
-        // generate maze
-        let mut state = generate::state(maze, settings);
-        cli.generator.run(&mut state)?;
-
-        // solve maze
-        let mut state = fade::flash_between(state, solve::state)?;
-        cli.solver.run(&mut state)?;
-        
-        // walk maze backward
-        let mut state = walk::state(state);
-        fade::out(&mut state)?;
-        Walker::run(&mut state)?;
-        
-        // delay and exit
-        fade::out(&mut state)
-    }
-
-    match inner() {
-        Ok(_) => (),
-        Err(Error::Break) => (),
-        Err(Error::Io(e)) => eprintln!("{e}"), 
-    }
-    reset();
+        let mut state = generate::state(Maze::new(width, height), settings);
+        args.generator.run_headless(&mut state);
+        generate::verify(&state)?;
+        scores.push(game::solo(&mut solve::state(state))?);
+        Ok::<_, Error>(())
+    });
+    reset(false);
+
+    let rows: Vec<_> = scores
+        .iter()
+        .enumerate()
+        .map(|(i, score)| [
+            (i + 1).to_string(), 
+            format!("{}x{}", score.size.0, score.size.1), 
+            score.moves.to_string(), 
+            score.hints.to_string(), 
+            score.shortest.to_string(), 
+            format!("{:.1?}", score.time), 
+            score.points().to_string(), 
+        ])
+        .collect();
+    print_table(["round", "size", "moves", "hints", "shortest", "time", "score"], &rows, 0);
+    println!();
+    println!("total score: {}", scores.iter().map(game::Score::points).sum::<u32>());
+    result
+}
+
+/// Prints a table with a header and aligned columns. The first `names` columns are left-aligned, and the
+/// others (holding numbers) are right-aligned. 
+fn print_table<const N: usize>(header: [&str; N], rows: &[[String; N]], names: usize) {
+    let header = header.map(str::to_owned);
+    let widths: Vec<usize> = (0..N)
+        .map(|i| rows.iter().chain([&header]).map(|row| row[i].len()).max().unwrap_or(0))
+        .collect();
+    for row in [&header].into_iter().chain(rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(i, (cell, &width))| match i < names {
+                true => format!("{cell:<width$}"), 
+                false => format!("{cell:>width$}"), 
+            })
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+}
+
+/// Prints the artefact requested by a subcommand to stdout. 
+fn generate_artefact(command: Command) -> Result<(), Error> {
+    let mut cli = Cli::command();
+    let name = cli.get_name().to_owned();
+    match command {
+        Command::Completions{ shell } => clap_complete::generate(shell, &mut cli, name, &mut io::stdout()), 
+        Command::Manpage => clap_mangen::Man::new(cli).render(&mut io::stdout())?, 
+        _ => unreachable!("not an artefact subcommand"), 
+    };
+    Ok(())
+}
+
+fn main() {
+    let result = match Cli::parse_command() {
+        Command::Run(args) => run(*args), 
+        Command::Generate(args) => generate(args), 
+        Command::Solve(args) => solve(*args), 
+        Command::Bench(args) => bench(args), 
+        Command::Export(args) => export(args), 
+        Command::Replay(args) => replay(args), 
+        Command::Play(args) => play(args), 
+        Command::Race(args) => race(args), 
+        Command::Duel(args) => duel(args), 
+        Command::Host(args) => host(args), 
+        Command::Join(args) => join(args), 
+        Command::Versus(args) => versus(args), 
+        Command::Challenge(args) => challenge(args), 
+        Command::Compare(args) => compare(args), 
+        command @ (Command::Completions{ .. } | Command::Manpage) => generate_artefact(command), 
+    };
+
+    let Err(e) = result else {
+        return
+    };
+    // breaking is deliberate, so only the exit code tells it apart from finishing
+    if !matches!(e, Error::Break) {
+        eprintln!("error: {e}");
+    }
+    std::process::exit(e.exit_code());
 }
@@ -1,18 +1,30 @@
-use std::{marker::PhantomData, ops::{Index, IndexMut}};
+use std::{collections::VecDeque, hash::{Hash, Hasher}, marker::PhantomData, ops::{Index, IndexMut}};
 use arrayvec::ArrayVec;
 use rand::{seq::{IteratorRandom, SliceRandom}, Rng};
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use crate::Error;
 
 /// Maze being constructed by [generators](crate::generate) and solved by [solvers](crate::solve).
 ///
 /// A maze consists of a lattice of nodes connected by edges. Nodes are always considered open (traversable)
 /// whereas edges can be either open or closed. 
+#[derive(Clone)]
 pub struct Maze {
     /// Whether each edge in the maze is open. There are `(width - 1) * (height - 1)` edges. 
-    pub open: EdgeBuffer<bool>, 
+    pub open: EdgeBits, 
+    /// Whether each edge may only be traversed one way (see [`Maze::one_way`]). 
+    pub one_way: EdgeBits, 
+    /// Whether each one-way edge is traversed toward the west or north, rather than toward the east or south. 
+    pub backward: EdgeBits, 
     /// Width in nodes. 
     pub width: usize, 
     /// Height in nodes. 
     pub height: usize, 
+    /// Node to solve from, or `None` to use the dead end nearest to the top-left corner. 
+    pub start: Option<Node>, 
+    /// Node to solve to, or `None` to use the dead end nearest to the bottom-right corner. 
+    pub goal: Option<Node>, 
 }
 
 impl Maze {
@@ -25,7 +37,11 @@ impl Maze {
         Maze {
             width, 
             height, 
-            open: EdgeBuffer::new_with_size(width, height), 
+            open: EdgeBits::new_with_size(width, height), 
+            one_way: EdgeBits::new_with_size(width, height), 
+            backward: EdgeBits::new_with_size(width, height), 
+            start: None, 
+            goal: None, 
         }
     }
 
@@ -51,8 +67,7 @@ impl Maze {
     }
 
     /// Chooses a random node in the maze. 
-    pub fn random_node(&self) -> Node {
-        let mut rng = rand::thread_rng();
+    pub fn random_node(&self, rng: &mut impl Rng) -> Node {
         Node(
             rng.gen_range(0..self.width), 
             rng.gen_range(0..self.height), 
@@ -60,11 +75,10 @@ impl Maze {
     }
 
     /// Chooses a random node meeting some predicate, if there is one. 
-    pub fn random_node_where(&self, predicate: impl Fn(Node) -> bool) -> Option<Node> {
-        let mut rng = rand::thread_rng();
+    pub fn random_node_where(&self, rng: &mut impl Rng, predicate: impl Fn(Node) -> bool) -> Option<Node> {
         self.nodes_iter()
             .filter(|&node| predicate(node))
-            .choose(&mut rng)
+            .choose(rng)
     }
 
     /// Returns an iterator over all nodes. 
@@ -77,20 +91,18 @@ impl Maze {
     /// Returns an iterator over all edges. 
     pub fn edges_iter(&self) -> impl Iterator<Item = Edge> {
         self.nodes_iter()
-            .map(move |node| [
+            .flat_map(move |node| [
                 self.edge(node, Direction::East), 
                 self.edge(node, Direction::South), 
             ])
             .flatten()
-            .flatten()
     }
 
     /// Returns a list of all neighbours to a node. 
     pub fn neighbours(&self, node: Node) -> Neighbours<true> {
         let neighbours = Direction::ALL
             .into_iter()
-            .map(|d| self.edge(node, d))
-            .flatten()
+            .filter_map(|d| self.edge(node, d))
             .collect();
         Neighbours(neighbours)
     }
@@ -99,21 +111,263 @@ impl Maze {
     pub fn open_neighbours(&self, node: Node) -> Neighbours<false> {
         let neighbours = Direction::ALL
             .into_iter()
-            .map(|d| self.edge(node, d))
-            .flatten()
+            .filter_map(|d| self.edge(node, d))
             .filter(|&e| self.open[e])
             .collect();
         Neighbours(neighbours)
     }
 
+    /// Returns a list of the neighbours to a node that can be moved to, i.e. through open edges that aren't
+    /// one-way against the move (see [`Maze::one_way`]). 
+    pub fn passable_neighbours(&self, node: Node) -> Neighbours<false> {
+        self.open_neighbours(node).filter_edges(|e| self.is_passable(e))
+    }
+
+    /// Whether an edge can be moved along, from its `from` node to its `to` node. 
+    pub fn is_passable(&self, edge: Edge) -> bool {
+        self.open[edge] && self.one_way(edge).is_none_or(|direction| direction == edge.direction)
+    }
+
+    /// Gets the only direction an edge may be traversed in, if it's one-way. 
+    pub fn one_way(&self, edge: Edge) -> Option<Direction> {
+        let forward = match edge.direction {
+            Direction::North | Direction::South => Direction::South, 
+            Direction::East | Direction::West => Direction::East, 
+        };
+        self.one_way[edge].then(|| match self.backward[edge] {
+            true => forward.reverse(), 
+            false => forward, 
+        })
+    }
+
+    /// Makes an edge one-way, such that it may only be traversed from its `from` node to its `to` node. 
+    pub fn set_one_way(&mut self, edge: Edge) {
+        self.one_way.set(edge, true);
+        self.backward.set(edge, matches!(edge.direction, Direction::North | Direction::West));
+    }
+
     /// Returns the top-left and bottom-right nodes. 
     pub fn bounds(&self) -> (Node, Node) {
         (Node(0, 0), Node(self.width - 1, self.height - 1))
     }
+
+    /// Gets the nodes with a single open edge. 
+    pub fn dead_ends(&self) -> impl Iterator<Item = Node> {
+        self.nodes_iter().filter(|&node| self.open_neighbours(node).len() == 1)
+    }
+
+    /// Gets the length of the shortest path (via open edges) from a node to each node, in edges, by
+    /// breadth-first search. Nodes that can't be reached have no distance. 
+    pub fn distances(&self, from: Node) -> NodeBuffer<Option<usize>> {
+        let mut distances = NodeBuffer::new(self);
+        let mut queue = VecDeque::from([from]);
+        distances[from] = Some(0);
+
+        while let Some(node) = queue.pop_front() {
+            let distance = distances[node].expect("queued nodes have a distance");
+            for edge in self.open_neighbours(node) {
+                if distances[edge.to].is_none() {
+                    distances[edge.to] = Some(distance + 1);
+                    queue.push_back(edge.to);
+                }
+            }
+        }
+        distances
+    }
+
+    /// Finds the node farthest from a node (via open edges), along with its distance. 
+    pub fn farthest(&self, from: Node) -> (Node, usize) {
+        let distances = self.distances(from);
+        self.nodes_iter()
+            .filter_map(|node| Some((node, distances[node]?)))
+            .fold((from, 0), |farthest, (node, distance)| match distance > farthest.1 {
+                true => (node, distance), 
+                false => farthest, 
+            })
+    }
+
+    /// Finds a longest shortest path through the maze (its diameter), i.e. the path between the two nodes
+    /// farthest apart. 
+    ///
+    /// The path starts at the node farthest from the top left corner, which is at one end of a longest path,
+    /// and ends at the node farthest from that one. This is exact for perfect mazes, but may fall short of the
+    /// longest for mazes with loops. 
+    pub fn diameter(&self) -> Vec<Node> {
+        let (top_left, _) = self.bounds();
+        let (start, _) = self.farthest(top_left);
+        let distances = self.distances(start);
+        let goal = self.nodes_iter().max_by_key(|&node| distances[node]).unwrap_or(start);
+
+        // trace back from the goal, each step going to a neighbour one edge nearer the start
+        let mut path = vec![goal];
+        let mut node = goal;
+        while let Some(edge) = self.open_neighbours(node)
+            .into_iter()
+            .find(|edge| distances[edge.to].is_some_and(|d| Some(d + 1) == distances[node]))
+        {
+            node = edge.to;
+            path.push(node);
+        }
+        path.reverse();
+        path
+    }
+
+    /// Whether every node can be reached from every other node. 
+    pub fn is_connected(&self) -> bool {
+        self.unreachable_node().is_none()
+    }
+
+    /// Whether there is exactly one path between any two nodes, i.e. the open edges form a spanning tree, as
+    /// all generators are expected to produce. 
+    pub fn is_perfect(&self) -> bool {
+        self.assert_valid().is_ok()
+    }
+
+    /// Checks that the maze is perfect (see [`Maze::is_perfect`]), describing the first violation found. 
+    ///
+    /// Both orientations of an edge share a bit in [`Maze::open`], so an edge can't be opened twice. Instead,
+    /// the duplicate openings checked for are bits set beyond the lattice (e.g. east of the last column),
+    /// which are indexed like edges but connect nothing. 
+    pub fn assert_valid(&self) -> Result<(), Error> {
+        let nodes = self.width * self.height;
+        let open = self.edges_iter().filter(|&e| self.open[e]).count();
+
+        if self.open.count_ones() != open {
+            let stray = self.open.count_ones() - open;
+            return Err(Error::InvalidMaze(format!("{stray} openings are outside of the maze")))
+        }
+        if open != nodes - 1 {
+            let expected = nodes - 1;
+            return Err(Error::InvalidMaze(format!("{open} edges are open, expected {expected} for {nodes} nodes")))
+        }
+        if let Some(Node(x, y)) = self.unreachable_node() {
+            return Err(Error::InvalidMaze(format!("node ({x}, {y}) can't be reached from (0, 0)")))
+        }
+        Ok(())
+    }
+
+    /// Finds a node that can't be reached from the top-left node, if there is one. 
+    fn unreachable_node(&self) -> Option<Node> {
+        let (start, _) = self.bounds();
+        let mut reached = NodeBits::new(self);
+        let mut stack = vec![start];
+        reached.set(start, true);
+
+        while let Some(node) = stack.pop() {
+            for edge in self.open_neighbours(node) {
+                if !reached[edge.to] {
+                    reached.set(edge.to, true);
+                    stack.push(edge.to);
+                }
+            }
+        }
+        self.nodes_iter().find(|&node| !reached[node])
+    }
+}
+
+/// Serialised form of a [`Maze`]: its dimensions, a list of its open edges as pairs of nodes, and optionally
+/// the nodes to solve between and the one-way edges, as pairs of nodes in the direction they're traversed in. 
+///
+/// This is the on-disk schema of saved mazes, so changes must remain readable from older files. Incompatible
+/// changes must bump [`SerialMaze::VERSION`]. 
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct SerialMaze {
+    /// Version of the schema, which is assumed to be the first if missing (as in files predating it). 
+    #[serde(default = "SerialMaze::first_version")]
+    version: u32, 
+    width: usize, 
+    height: usize, 
+    open: Vec<[Node; 2]>, 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start: Option<Node>, 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    goal: Option<Node>, 
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    one_way: Vec<[Node; 2]>, 
+}
+
+#[cfg(feature = "serde")]
+impl SerialMaze {
+    /// Current version of the schema. 
+    const VERSION: u32 = 1;
+
+    fn first_version() -> u32 {
+        1
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Maze {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let open = self
+            .edges_iter()
+            .filter(|&e| self.open[e])
+            .map(|e| [e.from, e.to])
+            .collect();
+        let one_way = self
+            .edges_iter()
+            .filter(|&e| self.open[e])
+            .filter_map(|e| match self.one_way(e)? == e.direction {
+                true => Some([e.from, e.to]), 
+                false => Some([e.to, e.from]), 
+            })
+            .collect();
+        SerialMaze {
+            version: SerialMaze::VERSION, 
+            width: self.width, 
+            height: self.height, 
+            open, 
+            start: self.start, 
+            goal: self.goal, 
+            one_way, 
+        }.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Maze {
+    /// Deserialises a maze, validating its version, its dimensions, that each edge connects adjacent nodes,
+    /// and that the start and goal are within the maze. 
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Maze, D::Error> {
+        let SerialMaze{ version, width, height, open, start, goal, one_way } =
+            SerialMaze::deserialize(deserializer)?;
+        let latest = SerialMaze::VERSION;
+        if version > latest {
+            return Err(de::Error::custom(format!("unsupported maze version {version} (expected at most {latest})")))
+        }
+        let dimension = |x: usize| u16::try_from(x).ok().filter(|&x| x >= 2);
+
+        let (Some(w), Some(h)) = (dimension(width), dimension(height)) else {
+            return Err(de::Error::custom(format!("invalid maze dimensions {width}x{height}")))
+        };
+        let mut maze = Maze::new(w, h);
+
+        let edge = |maze: &Maze, [from, to]: [Node; 2]| Direction::ALL
+            .into_iter()
+            .filter_map(|d| maze.edge(from, d))
+            .find(|e| e.to == to)
+            .ok_or_else(|| de::Error::custom(format!("invalid edge from ({}, {}) to ({}, {})", from.0, from.1, to.0, to.1)));
+        for nodes in open {
+            let edge = edge(&maze, nodes)?;
+            maze.open.set(edge, true);
+        }
+        for nodes in one_way {
+            let edge = edge(&maze, nodes)?;
+            maze.set_one_way(edge);
+        }
+        for node in [start, goal].into_iter().flatten() {
+            if maze.node(node.0, node.1).is_none() {
+                return Err(de::Error::custom(format!("node ({}, {}) is outside of the maze", node.0, node.1)))
+            }
+        }
+        Ok(Maze{ start, goal, ..maze })
+    }
 }
 
 /// Direction relative to a [`Node`]. 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Direction {
     North, 
     South, 
@@ -155,8 +409,9 @@ impl Direction {
 /// List of neighbours to a [`Node`].
 ///
 /// The type state `NON_EMPTY` states whether the list is known to be non-empty, which allows us to guarantee
-/// correct unwrapping for methods like [`Neighbours::choose`]. 
-pub struct Neighbours<const NON_EMPTY: bool>(ArrayVec<Edge, 4>);
+/// correct unwrapping for methods like [`Neighbours::choose`]. There's room for a neighbour beyond the four
+/// of the grid, i.e. the twin of a portal (see [`Portals`](crate::portals::Portals)). 
+pub struct Neighbours<const NON_EMPTY: bool>(ArrayVec<Edge, 5>);
 
 impl<const NON_EMPTY: bool> Neighbours<NON_EMPTY> {
     /// Removes all neighbours not meeting the predicate. 
@@ -165,18 +420,42 @@ impl<const NON_EMPTY: bool> Neighbours<NON_EMPTY> {
         Neighbours(self.0)
     }
 
+    /// Removes all neighbours whose edges don't meet the predicate. 
+    pub fn filter_edges(mut self, predicate: impl Fn(Edge) -> bool) -> Neighbours<false> {
+        self.0.retain(|&mut e| predicate(e));
+        Neighbours(self.0)
+    }
+
+    /// Replaces each neighbour's edge by the result of a function, e.g. leading on to where a slide ends. 
+    pub fn map_edges(mut self, f: impl Fn(Edge) -> Edge) -> Neighbours<NON_EMPTY> {
+        for edge in &mut self.0 {
+            *edge = f(*edge);
+        }
+        self
+    }
+
+    /// Adds a neighbour reached other than through the grid, e.g. the twin of a portal. 
+    pub fn push(&mut self, edge: Edge) {
+        self.0.push(edge);
+    }
+
     /// Gets the number of neighbours. 
     pub fn len(&self) -> usize {
         self.0.len()
     }
+
+    /// Whether there are no neighbours. 
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
 }
 
 impl Neighbours<true> {
     /// Chooses a random neighbour from the non-empty list. 
-    pub fn choose(&self) -> Edge {
+    pub fn choose(&self, rng: &mut impl Rng) -> Edge {
         self.0
             .as_slice()
-            .choose(&mut rand::thread_rng())
+            .choose(rng)
             .copied()
             .unwrap()
     }
@@ -184,17 +463,17 @@ impl Neighbours<true> {
 
 impl Neighbours<false> {
     /// Chooses a random (possibly filtered) neighbour, if one exists. 
-    pub fn choose(&self) -> Option<Edge> {
+    pub fn choose(&self, rng: &mut impl Rng) -> Option<Edge> {
         self.0
             .as_slice()
-            .choose(&mut rand::thread_rng())
+            .choose(rng)
             .copied()
     }
 }
 
 impl<const NON_EMPTY: bool> IntoIterator for Neighbours<NON_EMPTY> {
     type Item = Edge;
-    type IntoIter = <ArrayVec<Edge, 4> as IntoIterator>::IntoIter;
+    type IntoIter = <ArrayVec<Edge, 5> as IntoIterator>::IntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
         self.0.into_iter()
@@ -217,6 +496,7 @@ pub trait MazeIndex {
 /// When constructed by [`Maze`], this is guaranteed to be in bounds. Despite this, the fields are public
 /// (allowing for arbitrary construction) to simplify logic elsewhere. 
 #[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Node(pub usize, pub usize);
 
 impl Node {
@@ -241,11 +521,12 @@ impl MazeIndex for Node {
     }
 }
 
-/// An edge of the maze lattice. May be used to index an [`EdgeBuffer`]. 
+/// An edge of the maze lattice. May be used to index an [`EdgeBits`]. 
 ///
 /// When constructed by [`Maze`], this is guaranteed to be in bounds. Despite this, the fields are public
 /// (allowing for arbitrary construction) to simplify logic elsewhere. 
-#[derive(Clone, Copy, Debug, Hash, Eq)]
+#[derive(Clone, Copy, Debug, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Edge {
     pub from: Node, 
     pub to: Node, 
@@ -278,6 +559,14 @@ impl PartialEq for Edge {
     }
 }
 
+impl Hash for Edge {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // must agree with `PartialEq`, which disregards the orientation of the edge
+        Node::min(self.from, self.to).hash(state);
+        Node::max(self.from, self.to).hash(state);
+    }
+}
+
 impl MazeIndex for Edge {
     fn bound(maze_width: usize, maze_height: usize) -> usize {
         2 * maze_width * maze_height
@@ -303,6 +592,7 @@ impl MazeIndex for Edge {
 /// A buffer indexable by any [`MazeIndex`] storing arbitrary data. 
 ///
 /// Internally, this uses [`MazeIndex::normalise`] to index a linear array, ensuring efficient data layout. 
+#[derive(Clone)]
 pub struct Buffer<T, U> {
     /// Data being stored. 
     data: Vec<U>, 
@@ -343,20 +633,6 @@ impl<T: MazeIndex, U> Buffer<T, U> {
         }
     }
 
-    /// Constructs a buffer with given maze dimensions. This is only used by [`Maze::new`] since we don't
-    /// have a [`Maze`] instance yet. 
-    fn new_with_size(width: usize, height: usize) -> Self
-    where
-        U: Default + Clone
-    {
-        let size = T::bound(width, height);
-        Self {
-            data: vec![U::default();  size], 
-            width,
-            _phantom: PhantomData, 
-        }
-    }
-
     /// Returns an iterator over the value for each element. 
     pub fn iter(&self) -> impl Iterator<Item = &U> {
         self.data.iter()
@@ -393,7 +669,135 @@ impl<T: MazeIndex, U> IndexMut<T> for Buffer<T, U> {
     }
 }
 
+/// A buffer indexable by any [`MazeIndex`] storing a single bit for each element, taking an eighth of the
+/// memory of a [`Buffer`] of `bool`. 
+///
+/// Since single bits can't be referenced, elements are written via [`BitBuffer::set`] rather than
+/// [`IndexMut`], while [`Index`] returns references to constants. 
+#[derive(Clone)]
+pub struct BitBuffer<T> {
+    /// Bits being stored, packed into words. 
+    data: Vec<u64>, 
+    /// Maze width, used by [`MazeIndex::normalise`]. 
+    width: usize, 
+    /// Phandom data for the index type. 
+    _phantom: PhantomData<T>, 
+}
+
+impl<T: MazeIndex> BitBuffer<T> {
+    /// Constructs a buffer with all bits unset. 
+    pub fn new(maze: &Maze) -> Self {
+        Self::new_with_size(maze.width, maze.height)
+    }
+
+    /// Constructs a buffer with given maze dimensions. This is only used by [`Maze::new`] since we don't
+    /// have a [`Maze`] instance yet. 
+    fn new_with_size(width: usize, height: usize) -> Self {
+        let size = T::bound(width, height);
+        Self {
+            data: vec![0; size.div_ceil(u64::BITS as usize)], 
+            width, 
+            _phantom: PhantomData, 
+        }
+    }
+
+    /// Gets the word and bit mask of an element. 
+    fn locate(&self, index: T) -> (usize, u64) {
+        let index = index.normalise(self.width);
+        let bits = u64::BITS as usize;
+        (index / bits, 1 << (index % bits))
+    }
+
+    /// Gets the bit of an element. 
+    pub fn get(&self, index: T) -> bool {
+        let (word, mask) = self.locate(index);
+        self.data[word] & mask != 0
+    }
+
+    /// Sets the bit of an element. 
+    pub fn set(&mut self, index: T, value: bool) {
+        let (word, mask) = self.locate(index);
+        match value {
+            true => self.data[word] |= mask, 
+            false => self.data[word] &= !mask, 
+        }
+    }
+
+    /// Unsets all bits. 
+    pub fn clear(&mut self) {
+        self.data.fill(0);
+    }
+
+    /// Counts the set bits. 
+    pub fn count_ones(&self) -> usize {
+        self.data.iter().map(|word| word.count_ones() as usize).sum()
+    }
+}
+
+impl<T: MazeIndex> Index<T> for BitBuffer<T> {
+    type Output = bool;
+
+    fn index(&self, index: T) -> &bool {
+        match self.get(index) {
+            true => &true, 
+            false => &false, 
+        }
+    }
+}
+
 /// Buffer indexable by [`Node`]. 
 pub type NodeBuffer<T> = Buffer<Node, T>;
-/// Buffer indexable by [`Edge`]. 
-pub type EdgeBuffer<T> = Buffer<Edge, T>;
+/// Bit buffer indexable by [`Node`]. 
+pub type NodeBits = BitBuffer<Node>;
+/// Bit buffer indexable by [`Edge`]. 
+pub type EdgeBits = BitBuffer<Edge>;
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_json() {
+        let mut maze = Maze::new(3, 2);
+        for edge in maze.edges_iter().step_by(2).collect::<Vec<_>>() {
+            maze.open.set(edge, true);
+        }
+        let one_way = maze.edges_iter().find(|&e| maze.open[e] && e.direction == Direction::East).unwrap();
+        maze.set_one_way(one_way.reverse());
+        maze.start = Some(Node(2, 0));
+        maze.goal = Some(Node(0, 1));
+
+        let json = serde_json::to_string(&maze).unwrap();
+        let parsed: Maze = serde_json::from_str(&json).unwrap();
+        assert_eq!((parsed.width, parsed.height), (3, 2));
+        assert_eq!((parsed.start, parsed.goal), (maze.start, maze.goal));
+        for edge in maze.edges_iter() {
+            assert_eq!(parsed.open[edge], maze.open[edge]);
+            assert_eq!(parsed.one_way(edge), maze.one_way(edge));
+        }
+    }
+
+    #[test]
+    fn defaults_to_first_version() {
+        let maze: Maze = serde_json::from_str(r#"{"width": 2, "height": 2, "open": [[[0, 0], [1, 0]]]}"#).unwrap();
+        assert!(maze.open[maze.edge(Node(0, 0), Direction::East).unwrap()]);
+        assert_eq!((maze.start, maze.goal), (None, None));
+    }
+
+    #[test]
+    fn rejects_invalid() {
+        let invalid = [
+            r#"{"version": 2, "width": 2, "height": 2, "open": []}"#, 
+            r#"{"width": 1, "height": 2, "open": []}"#, 
+            r#"{"width": 2, "height": 70000, "open": []}"#, 
+            r#"{"width": 2, "height": 2, "open": [[[0, 0], [1, 1]]]}"#, 
+            r#"{"width": 2, "height": 2, "open": [[[1, 0], [2, 0]]]}"#, 
+            r#"{"width": 2, "height": 2, "open": [], "one_way": [[[0, 0], [0, 0]]]}"#, 
+            r#"{"width": 2, "height": 2, "open": [], "start": [2, 0]}"#, 
+            r#"{"width": 2, "height": 2, "open": [], "goal": [0, 2]}"#, 
+        ];
+        for json in invalid {
+            assert!(serde_json::from_str::<Maze>(json).is_err(), "parsed {json}");
+        }
+    }
+}
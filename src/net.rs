@@ -0,0 +1,363 @@
+//! Race of two users over the network, each through their own instance of the same maze, as played by
+//! `overlook host` and `overlook join`. The host offers the maze to race through, which the instance joining
+//! generates alike, and from then on each sends the other where its player is after every move. The opponent
+//! is drawn as a ghost upon the maze (see [`Phase::ghost`](solve::Phase::ghost)). 
+//!
+//! Messages are lines of text: the offer (see [`Offer`]), followed by a position `X:Y` per move. 
+
+use std::{
+    fmt, 
+    io::{self, BufRead, BufReader, ErrorKind, Read, Write}, 
+    net::{TcpListener, TcpStream, ToSocketAddrs}, 
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use crate::{
+    game::{Player, Race}, 
+    generate::{Generator, GENERATORS}, 
+    maze::Node, 
+    solve, 
+    Animation, Error, Signal, 
+};
+
+/// Port hosted on, unless given otherwise. 
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// Word the offer starts with, such that connecting to anything other than a host fails early. 
+const GREETING: &str = "overlook";
+
+/// Maximum length of a line received, in bytes, such that the other end can't make us buffer without bound. 
+/// Offers and positions are far shorter. 
+const MAX_LINE: usize = 256;
+
+/// The maze raced through, as offered by the host: the instance joining generates it alike. 
+#[derive(Clone, Copy)]
+pub struct Offer {
+    /// Maze width in nodes. 
+    pub width: u16, 
+    /// Maze height in nodes. 
+    pub height: u16, 
+    /// Generator the maze is generated with. 
+    pub generator: Generator, 
+    /// Seed the maze is generated from. 
+    pub seed: u64, 
+}
+
+impl fmt::Display for Offer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{GREETING} {}x{} {} {}", self.width, self.height, self.generator.name(), self.seed)
+    }
+}
+
+impl Offer {
+    /// Parses an offer as sent by the host, or returns `None` if malformed. 
+    fn parse(line: &str) -> Option<Offer> {
+        let mut words = line.split_whitespace();
+        if words.next()? != GREETING {
+            return None
+        }
+        let (width, height) = words.next()?.split_once('x')?;
+        let offer = Offer {
+            width: width.parse().ok().filter(|&width| width >= 2)?, 
+            height: height.parse().ok().filter(|&height| height >= 2)?, 
+            generator: GENERATORS.find(words.next()?)?, 
+            seed: words.next()?.parse().ok()?, 
+        };
+        words.next().is_none().then_some(offer)
+    }
+}
+
+/// Connection to the other instance of a race. Reading never blocks, such that it can be polled in between
+/// timesteps. 
+pub struct Connection {
+    stream: BufReader<TcpStream>, 
+    /// Bytes of the line being received, which may arrive over several polls. 
+    pending: Vec<u8>, 
+}
+
+impl Connection {
+    /// Waits for another instance to connect to a port, and offers it the maze to race through. 
+    pub fn host(port: u16, offer: Offer) -> io::Result<Connection> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        let (stream, _) = listener.accept()?;
+        let mut connection = Connection::new(stream)?;
+        connection.send(&offer)?;
+        Ok(connection)
+    }
+
+    /// Connects to a hosting instance at an address, returning the maze it offers to race through. 
+    pub fn join(address: impl ToSocketAddrs) -> Result<(Connection, Offer), Error> {
+        let mut connection = Connection::new(TcpStream::connect(address)?)?;
+        // the offer is waited for, unlike the positions that follow it. it's read through the connection's own
+        // buffer, since the host may send its first position right after
+        connection.stream.get_ref().set_nonblocking(false)?;
+        let mut line = String::new();
+        (&mut connection.stream).take(MAX_LINE as u64).read_line(&mut line)?;
+        connection.stream.get_ref().set_nonblocking(true)?;
+
+        let offer = Offer::parse(&line).ok_or_else(|| {
+            Error::InvalidArguments("the address is not hosting an overlook race".to_owned())
+        })?;
+        Ok((connection, offer))
+    }
+
+    fn new(stream: TcpStream) -> io::Result<Connection> {
+        // positions are tiny and sent as the player moves, so they shouldn't wait to be batched
+        stream.set_nodelay(true)?;
+        stream.set_nonblocking(true)?;
+        Ok(Connection {
+            stream: BufReader::new(stream), 
+            pending: Vec::new(), 
+        })
+    }
+
+    /// Sends a line to the other instance. 
+    fn send(&mut self, line: &impl fmt::Display) -> io::Result<()> {
+        let stream = self.stream.get_mut();
+        stream.write_all(format!("{line}\n").as_bytes())?;
+        stream.flush()
+    }
+
+    /// Receives the positions the other instance sent since last polled, in order. Returns `None` once it has
+    /// disconnected. 
+    fn receive(&mut self) -> io::Result<Option<Vec<Node>>> {
+        let mut positions = Vec::new();
+        loop {
+            let limit = (MAX_LINE - self.pending.len()) as u64;
+            match (&mut self.stream).take(limit).read_until(b'\n', &mut self.pending) {
+                Ok(0) => return Ok(None), 
+                Ok(_) => {}, 
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return Ok(Some(positions)), 
+                Err(e) => return Err(e), 
+            }
+            if self.pending.len() == MAX_LINE && self.pending.last() != Some(&b'\n') {
+                return Err(io::Error::new(ErrorKind::InvalidData, "overlong line received"))
+            }
+            // a line cut off by the other instance disconnecting is only a partial position
+            if self.pending.last() != Some(&b'\n') {
+                return Ok(None)
+            }
+            let position = str::from_utf8(&self.pending)
+                .ok()
+                .and_then(|line| line.trim().split_once(':'))
+                .and_then(|(x, y)| Some(Node(x.parse().ok()?, y.parse().ok()?)))
+                .ok_or_else(|| io::Error::new(ErrorKind::InvalidData, "malformed position received"))?;
+            positions.push(position);
+            self.pending.clear();
+        }
+    }
+}
+
+/// Race of the user against another over a connection, which ends as soon as either reaches the goal or the
+/// other disconnects. The user plays as in a [`Race`] on their own, while the other is drawn as a ghost
+/// moving as it's told to. 
+///
+/// Each instance decides the outcome from the moves it knows of, so a finish close enough for the moves to
+/// cross on the way may be claimed by both. 
+pub struct Remote {
+    race: Race, 
+    /// Connection to the other instance, unless racing alone. 
+    connection: Option<Connection>, 
+    /// Node last sent as the player's position. 
+    sent: Node, 
+    /// Whether the other instance has disconnected. 
+    left: bool, 
+}
+
+impl Remote {
+    /// Constructs a race against the instance at the other end of a connection. 
+    pub fn new_with(connection: Connection, state: &mut solve::State) -> Remote {
+        // the ghost is moved as told rather than by replaying moves, so it's given none
+        let race = Race::new(state).with_ghost(Vec::new(), state);
+        Remote {
+            race, 
+            connection: Some(connection), 
+            sent: state.start, 
+            left: false, 
+        }
+    }
+
+    /// Sends the player's position to the other instance if it changed since last sent. 
+    fn sync(&mut self, state: &solve::State) -> io::Result<()> {
+        let (Some(connection), Some(player)) = (&mut self.connection, &state.player) else {
+            return Ok(())
+        };
+        if player.position != self.sent {
+            self.sent = player.position;
+            let Node(x, y) = player.position;
+            connection.send(&format_args!("{x}:{y}"))?;
+        }
+        Ok(())
+    }
+
+    /// Moves the ghost to wherever the other instance says their player went. 
+    fn poll(&mut self, state: &mut solve::State) -> io::Result<()> {
+        let Some(connection) = &mut self.connection else {
+            return Ok(())
+        };
+        let Some(positions) = connection.receive()? else {
+            self.left = true;
+            return Ok(())
+        };
+        let Some(ghost) = &mut state.phase.ghost else {
+            return Ok(())
+        };
+        // positions beyond the maze would be from a mismatched maze, and are ignored
+        for position in positions.into_iter().filter_map(|Node(x, y)| state.maze.node(x, y)) {
+            ghost.position = position;
+            ghost.trail.set(position, true);
+            ghost.moves += 1;
+        }
+        Ok(())
+    }
+
+}
+
+/// Gets the outcome of a race so far, if either player has reached the goal. 
+fn outcome(state: &solve::State) -> Option<Outcome> {
+    let at_goal = |player: &Option<Player>| player.as_ref().is_some_and(|player| player.position == state.goal);
+    match (at_goal(&state.player), at_goal(&state.ghost)) {
+        (true, _) => Some(Outcome::Won), 
+        (false, true) => Some(Outcome::Lost), 
+        (false, false) => None, 
+    }
+}
+
+impl Animation for Remote {
+    type Phase = solve::Phase;
+
+    /// Constructs a race without a connection, i.e. the user solving the maze on their own. 
+    fn new(state: &mut solve::State) -> Remote {
+        Remote {
+            race: Race::new(state), 
+            connection: None, 
+            sent: state.start, 
+            left: false, 
+        }
+    }
+
+    fn step(&mut self, state: &mut solve::State) -> Signal {
+        // the race itself ends once the player reaches the goal, which is noticed below
+        self.race.step(state);
+        // a broken connection is treated as the other instance having left
+        if self.sync(state).and_then(|_| self.poll(state)).is_err() {
+            self.left = true;
+        }
+        match (outcome(state), self.left) {
+            (None, false) => Signal::Continue, 
+            _ => Signal::Done, 
+        }
+    }
+
+    fn steps_per_frame(&self, state: &solve::State) -> u32 {
+        self.race.steps_per_frame(state)
+    }
+
+    fn key(&mut self, state: &mut solve::State, key: KeyEvent) -> bool {
+        // the autopilot would make short work of the other user
+        if key.code == KeyCode::Char('a') {
+            return false
+        }
+        self.race.key(state, key)
+    }
+}
+
+/// How a network race ended, from the user's side. 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The user reached the goal first. 
+    Won, 
+    /// The other user reached the goal first. 
+    Lost, 
+    /// The other user disconnected before either reached the goal. 
+    Left, 
+}
+
+/// Outcome of a network race, as shown once it's over (see [`race`]). 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Results {
+    /// How the race ended. 
+    pub outcome: Outcome, 
+    /// Timesteps the race took. 
+    pub steps: usize, 
+    /// Number of moves the user made. 
+    pub moves: usize, 
+    /// Number of moves of the other user received. 
+    pub opponent_moves: usize, 
+    /// Length of the shortest path from the start to the goal, in edges. 
+    pub shortest: Option<usize>, 
+}
+
+impl fmt::Display for Results {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let outcome = match self.outcome {
+            Outcome::Won => "you won", 
+            Outcome::Lost => "you lost", 
+            Outcome::Left => "your opponent left", 
+        };
+        writeln!(f, "{outcome}, after {} timesteps", self.steps)?;
+        writeln!(f, "your moves:     {}", self.moves)?;
+        writeln!(f, "opponent moves: {}", self.opponent_moves)?;
+        if let Some(shortest) = self.shortest {
+            writeln!(f, "shortest path:  {shortest}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Races the user against the instance at the other end of a connection through a maze, animated, returning
+/// the outcome once either reached the goal or the other left (see [`Remote`]). 
+pub fn race(connection: Connection, state: &mut solve::State) -> Result<Results, Error> {
+    let steps = Remote::new_with(connection, state).play(state)?;
+    let moves = |player: &Option<Player>| player.as_ref().map_or(0, |player| player.moves);
+    Ok(Results {
+        // the race only ends short of the goal if the other instance left
+        outcome: outcome(state).unwrap_or(Outcome::Left), 
+        steps, 
+        moves: moves(&state.player), 
+        opponent_moves: moves(&state.ghost), 
+        shortest: solve::shortest(&state.maze).map(|solution| solution.path.len() - 1), 
+    })
+}
+
+/// Checks that a maze of given dimensions fits a terminal of given size, as the instance joining a race has
+/// no say in the size of the maze. 
+pub fn fits((width, height): (u16, u16), (columns, rows): (u16, u16)) -> Result<(), Error> {
+    match 4 * width as usize <= columns as usize + 2 && 2 * height as usize <= rows as usize + 1 {
+        true => Ok(()), 
+        false => Err(Error::TerminalTooSmall{ columns, rows }), 
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_offer() {
+        for generator in GENERATORS.entries() {
+            let offer = Offer{ width: 40, height: 12, generator, seed: u64::MAX };
+            let parsed = Offer::parse(&offer.to_string()).unwrap();
+            assert_eq!((parsed.width, parsed.height, parsed.seed), (40, 12, u64::MAX));
+            assert_eq!(parsed.generator.name(), generator.name());
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_offer() {
+        let malformed = [
+            "", 
+            "overlook", 
+            "hello 40x12 dfs 1", 
+            "overlook 40 dfs 1", 
+            "overlook 1x12 dfs 1", 
+            "overlook 40x1 dfs 1", 
+            "overlook 40x12 unregistered 1", 
+            "overlook 40x12 dfs", 
+            "overlook 40x12 dfs -1", 
+            "overlook 40x12 dfs 1 more", 
+        ];
+        for line in malformed {
+            assert!(Offer::parse(line).is_none(), "parsed `{line}`");
+        }
+    }
+}
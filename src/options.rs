@@ -0,0 +1,135 @@
+//! Tuning parameters of individual algorithms, as given by `--algo-opt` (see
+//! [`Settings::options`](crate::Settings::options)). 
+//!
+//! Options are keyed by the name of the algorithm they tune, e.g. `prim.frontier=newest`, and are ignored by
+//! all other algorithms. 
+
+use std::str::FromStr;
+use clap::ValueEnum;
+use rand::Rng;
+use crate::maze::{Maze, Node};
+
+/// Which edge of the frontier Prim's algorithm carves next, as given by `prim.frontier`. 
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Frontier {
+    /// Any edge, at random, which grows the maze evenly in all directions. 
+    #[default]
+    Random, 
+    /// The edge added last, which carves long winding corridors like depth-first search. 
+    Newest, 
+    /// The edge added first, which grows the maze in rings around the start. 
+    Oldest, 
+}
+
+/// Node Wilson's algorithm starts the maze from, as given by `wilson.start`. Walks from all other nodes end
+/// once they reach the maze, so they converge upon the start. 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Start {
+    /// A node at random. 
+    #[default]
+    Random, 
+    /// The node in the middle of the maze. 
+    Centre, 
+    /// The top left corner. 
+    Corner, 
+    /// The node at given coordinates, as given by `X:Y`, moved within the maze if beyond it. 
+    At(usize, usize), 
+}
+
+impl Start {
+    /// Gets the start node within a maze, drawing it at random if so chosen. 
+    pub fn node(self, maze: &Maze, rng: &mut impl Rng) -> Node {
+        match self {
+            Start::Random => maze.random_node(rng), 
+            Start::Centre => Node(maze.width / 2, maze.height / 2), 
+            Start::Corner => Node(0, 0), 
+            Start::At(x, y) => Node(x.min(maze.width - 1), y.min(maze.height - 1)), 
+        }
+    }
+}
+
+impl FromStr for Start {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Start, ()> {
+        match s {
+            "random" => Ok(Start::Random), 
+            "centre" | "center" => Ok(Start::Centre), 
+            "corner" => Ok(Start::Corner), 
+            _ => {
+                let (x, y) = s.split_once(':').ok_or(())?;
+                Ok(Start::At(x.parse().map_err(|_| ())?, y.parse().map_err(|_| ())?))
+            }, 
+        }
+    }
+}
+
+/// Options tuning the algorithms, each `None` unless given. 
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Options {
+    /// Which edge of the frontier Prim's algorithm carves next (`prim.frontier`). 
+    pub prim_frontier: Option<Frontier>, 
+    /// Maximum number of edges a walk of Wilson's algorithm takes before it's abandoned for a new one from
+    /// another node (`wilson.walk-cap`). 
+    pub wilson_walk_cap: Option<usize>, 
+    /// Node Wilson's algorithm starts the maze from (`wilson.start`). 
+    pub wilson_start: Option<Start>, 
+    /// Probability of the mouse returning to the start at each step, from 0 up to but excluding 1
+    /// (`mouse.restart`). Since the mouse must go the whole way to the goal without restarting, values much
+    /// above 0.05 effectively never reach it in mazes of any size. 
+    pub mouse_restart: Option<f64>, 
+}
+
+impl Options {
+    /// Combines the options with those given later, which take precedence. 
+    pub fn or(self, later: Options) -> Options {
+        Options {
+            prim_frontier: later.prim_frontier.or(self.prim_frontier), 
+            wilson_walk_cap: later.wilson_walk_cap.or(self.wilson_walk_cap), 
+            wilson_start: later.wilson_start.or(self.wilson_start), 
+            mouse_restart: later.mouse_restart.or(self.mouse_restart), 
+        }
+    }
+}
+
+impl FromStr for Options {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Options, String> {
+        let mut options = Options::default();
+
+        for pair in s.split(',') {
+            let Some((key, value)) = pair.split_once('=') else {
+                return Err(format!("expected key=value, got `{pair}`"))
+            };
+            let invalid = |expected: &str| format!("expected {expected} for `{key}`, got `{value}`");
+            match key {
+                "prim.frontier" => {
+                    let frontier = Frontier::from_str(value, true);
+                    options.prim_frontier = Some(frontier.map_err(|_| invalid("random, newest, or oldest"))?);
+                }, 
+                "wilson.walk-cap" => {
+                    let cap = value.parse().ok().filter(|&cap| cap >= 1).ok_or_else(|| invalid("at least 1"))?;
+                    options.wilson_walk_cap = Some(cap);
+                }, 
+                "wilson.start" => {
+                    let start = value.parse().map_err(|_| invalid("random, centre, corner, or X:Y"))?;
+                    options.wilson_start = Some(start);
+                }, 
+                "mouse.restart" => {
+                    let probability = value
+                        .parse()
+                        .ok()
+                        .filter(|p| (0.0..1.0).contains(p))
+                        .ok_or_else(|| invalid("a probability from 0 up to but excluding 1"))?;
+                    options.mouse_restart = Some(probability);
+                }, 
+                _ => {
+                    let keys = "prim.frontier, wilson.walk-cap, wilson.start, or mouse.restart";
+                    return Err(format!("expected one of {keys}, got `{key}`"))
+                }, 
+            }
+        }
+        Ok(options)
+    }
+}
@@ -0,0 +1,179 @@
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "terminal")]
+use std::marker::PhantomData;
+use clap::ValueEnum;
+#[cfg(feature = "terminal")]
+use rand::seq::SliceRandom;
+use crate::{
+    events::{Event, Hooks}, 
+    maze::Edge, 
+};
+#[cfg(feature = "terminal")]
+use crate::{
+    state::{Fog, Phase, State}, 
+    Animation, Error, Signal, 
+};
+
+/// Number of timesteps the maze takes to collapse (see [`collapse`]). 
+#[cfg(feature = "terminal")]
+const COLLAPSE_STEPS: f64 = 40.0;
+
+/// Finale played once the solution is walked, as given by `--outro`. 
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outro {
+    /// Walls close in random order. 
+    Random, 
+    /// Walls close in the reverse of the order they were opened, un-generating the maze. Loaded mazes weren't
+    /// generated, so their walls close in random order instead. 
+    Reverse, 
+    /// The rows of the maze fall away from the bottom up, instead of fading out (see [`collapse`]). 
+    #[value(help = "The rows of the maze fall away from the bottom up, instead of fading out")]
+    Collapse, 
+}
+
+/// Edges opened while generating, in the order they were opened, as recorded from the hooks. 
+#[derive(Clone, Debug, Default)]
+pub struct Openings(Arc<Mutex<Option<Vec<Edge>>>>);
+
+impl Openings {
+    /// Starts recording the edges opened (and closed again, e.g. by Wilson's algorithm) as reported to the
+    /// hooks. 
+    pub fn record(hooks: &mut Hooks) -> Openings {
+        let openings = Openings(Arc::new(Mutex::new(Some(Vec::new()))));
+        let recording = openings.clone();
+        hooks.register(move |event| {
+            let mut edges = recording.0.lock().expect("the lock isn't poisoned");
+            let Some(edges) = edges.as_mut() else {
+                return
+            };
+            match *event {
+                Event::EdgeOpened(edge) => edges.push(edge), 
+                Event::EdgeClosed(edge) => edges.retain(|&e| e != edge), 
+                _ => (), 
+            }
+        });
+        openings
+    }
+
+    /// Stops recording, returning the edges that are still open in the order they were opened. 
+    pub fn finish(&self) -> Vec<Edge> {
+        self.0.lock().expect("the lock isn't poisoned").take().unwrap_or_default()
+    }
+}
+
+/// Plays the finale, closing all open edges until the grid is solid: in the reverse of the order they were
+/// opened if given (see [`Openings`]), and in random order otherwise. Nodes light up as their walls close. 
+#[cfg(feature = "terminal")]
+pub fn close_walls<T: Phase>(state: &mut State<T>, opened: Option<Vec<Edge>>) -> Result<(), Error> {
+    match opened {
+        Some(queue) => CloseWalls{ queue, _phase: PhantomData }.play(state)?, 
+        None => CloseWalls::<T>::run(state)?, 
+    };
+    Ok(())
+}
+
+/// Plays the finale collapsing the maze, with its rows falling away one after the other from the bottom up,
+/// faster and faster as if pulled by gravity. Fallen rows are hidden by a fog of war (see [`Fog`]), which is
+/// lifted again once finished, such that the state still holds the whole maze. 
+#[cfg(feature = "terminal")]
+pub fn collapse<T: Phase>(state: &mut State<T>) -> Result<(), Error> {
+    state.fog = Some(Fog::new(&state.maze, 0.0));
+    Collapse::<T>::run(state)?;
+    state.fog = None;
+    Ok(())
+}
+
+/// Animation collapsing the maze, hiding a row once its time to fall has come. 
+#[cfg(feature = "terminal")]
+struct Collapse<T> {
+    /// Timestep at which each row falls, from the bottom row up. 
+    falls: Vec<usize>, 
+    step: usize, 
+    _phase: PhantomData<T>, 
+}
+
+#[cfg(feature = "terminal")]
+impl<T: Phase> Animation for Collapse<T> {
+    type Phase = T;
+
+    fn new(state: &mut State<T>) -> Self {
+        // falling from rest, the distance fallen grows with the square of the time, so the rows fall ever
+        // more quickly after each other
+        let rows = state.maze.height as f64;
+        let falls = (0..state.maze.height)
+            .map(|row| (COLLAPSE_STEPS * (row as f64 / rows).sqrt()).round() as usize)
+            .collect();
+        Collapse {
+            falls, 
+            step: 0, 
+            _phase: PhantomData, 
+        }
+    }
+
+    fn step(&mut self, state: &mut State<T>) -> Signal {
+        let fallen = self.falls.iter().filter(|&&fall| fall <= self.step).count();
+        let Some(fog) = &mut state.fog else {
+            return Signal::Done
+        };
+        for node in state.maze.nodes_iter() {
+            fog.set_visible(node, node.1 + fallen < state.maze.height);
+        }
+        // keep the empty screen for a moment once all rows have fallen
+        if self.step > COLLAPSE_STEPS as usize + 5 {
+            return Signal::Done
+        }
+        self.step += 1;
+        Signal::Continue
+    }
+
+    fn steps_per_frame(&self, _state: &State<T>) -> u32 {
+        // finales are timed independently of the algorithms
+        1
+    }
+
+    fn finishes_phase(&self) -> bool {
+        false
+    }
+}
+
+/// Animation closing one edge per timestep, from the back of a queue of edges (in random order unless
+/// given). 
+#[cfg(feature = "terminal")]
+struct CloseWalls<T> {
+    queue: Vec<Edge>, 
+    _phase: PhantomData<T>, 
+}
+
+#[cfg(feature = "terminal")]
+impl<T: Phase> Animation for CloseWalls<T> {
+    type Phase = T;
+
+    fn new(state: &mut State<T>) -> Self {
+        let mut queue: Vec<Edge> = state.maze.edges_iter().filter(|&e| state.maze.open[e]).collect();
+        queue.shuffle(&mut state.rng);
+        CloseWalls {
+            queue, 
+            _phase: PhantomData, 
+        }
+    }
+
+    fn step(&mut self, state: &mut State<T>) -> Signal {
+        loop {
+            let Some(edge) = self.queue.pop() else {
+                return Signal::Done
+            };
+            if !state.maze.open[edge] {
+                continue
+            }
+            state.close(edge);
+            // relit without being reported as visited again, like the celebration
+            state.visited_at.set(edge.from, state.time);
+            state.visited_at.set(edge.to, state.time);
+            return Signal::Continue
+        }
+    }
+
+    fn finishes_phase(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,409 @@
+use std::{fmt, time::Duration};
+use crate::{
+    code::RunCode, 
+    events::Event, 
+    export::Output, 
+    frame::Frame, 
+    generate::{self, Generator, GENERATORS}, 
+    maze::Maze, 
+    solve::{self, Solver, SOLVERS}, 
+    state::{Phase, State}, 
+    walk, 
+    Error, Settings, Signal, Stepper, 
+};
+#[cfg(feature = "terminal")]
+use crate::{
+    celebrate, 
+    chase::Chase, 
+    diameter, 
+    fade, 
+    outro::{self, Openings, Outro}, 
+    title, 
+    Animation, 
+};
+
+/// The complete sequence of generating a maze, solving it, and walking the solution, as constructed by
+/// [`Overlook::builder`]. 
+pub struct Overlook {
+    maze: Source, 
+    generator: Generator, 
+    solver: Solver, 
+    parallel: bool, 
+    settings: Settings, 
+    save: Option<Output>, 
+    min_difficulty: Option<f64>, 
+    #[cfg(feature = "terminal")]
+    chase: bool, 
+}
+
+/// Where the maze of a pipeline comes from. 
+enum Source {
+    /// Generated with given dimensions. 
+    Generate(u16, u16), 
+    /// Given up front (e.g. loaded from a file), skipping the generate phase. 
+    Given(Maze), 
+}
+
+impl Overlook {
+    /// Starts building a pipeline, by default generating a 40x20 maze with `dfs` and solving it with `a-star`. 
+    pub fn builder() -> Builder {
+        Builder {
+            maze: Source::Generate(40, 20), 
+            generator: GENERATORS.find("dfs").expect("dfs is a built-in generator"), 
+            solver: SOLVERS.find("a-star").expect("a-star is a built-in solver"), 
+            parallel: false, 
+            settings: Settings::default(), 
+            save: None, 
+            min_difficulty: None, 
+            #[cfg(feature = "terminal")]
+            chase: false, 
+        }
+    }
+
+    /// Runs all phases animated in the terminal, with fades between them and the finale closing the walls (if
+    /// enabled, see [`Settings::outro`]), returning the final state and a summary of the run. As with [`Animation::run`](crate::Animation::run), the terminal is expected to have
+    /// been set up by the caller. 
+    #[cfg(feature = "terminal")]
+    pub fn run(self) -> Result<(walk::State, Summary), Error> {
+        let generator = self.generator();
+        let mut settings = self.resolved_settings(false)?;
+        if settings.title {
+            let (width, height) = match &self.maze {
+                &Source::Generate(width, height) => (width, height), 
+                Source::Given(maze) => (maze.width as u16, maze.height as u16), 
+            };
+            title::play(width, height, &settings)?;
+        }
+        // the order edges are opened in is only needed to un-generate the maze in the finale
+        let openings = (settings.outro == Some(Outro::Reverse)).then(|| Openings::record(&mut settings.hooks));
+
+        // generate maze, unless given, in which case solving starts straight away
+        let (mut state, generate_steps) = match self.maze {
+            Source::Generate(width, height) => {
+                let mut state = generate::state(Maze::new(width, height), settings);
+                let steps = match self.chase {
+                    true => Chase::new_with(self.generator, &mut state).play(&mut state)?, 
+                    false => self.generator.run(&mut state)?, 
+                };
+                generated(&state, self.save.as_ref())?;
+                if state.settings.diameter {
+                    diameter::trace(&mut state)?;
+                }
+                (fade::between(state, solve::state)?, steps)
+            }, 
+            Source::Given(maze) => (solve::given(maze, settings), 0), 
+        };
+        let opened = openings.map(|openings| openings.finish()).filter(|_| generator.is_some());
+
+        // solve maze
+        let solve_steps = solve::run(self.solver, &mut state)?;
+        if state.settings.celebrate {
+            celebrate::ring(&mut state)?;
+        }
+
+        // walk maze
+        let mut state = walk::state(state);
+        fade::out(&mut state)?;
+        state.settings.walk.walker().run(&mut state)?;
+        walk::pulse(&mut state)?;
+
+        // delay, play the finale (if enabled) and exit
+        match state.settings.outro {
+            Some(Outro::Collapse) => outro::collapse(&mut state)?, 
+            Some(_) => {
+                fade::out(&mut state)?;
+                // the maze is restored afterwards, such that the solved maze is returned
+                let maze = state.maze.clone();
+                outro::close_walls(&mut state, opened)?;
+                fade::out(&mut state)?;
+                state.maze = maze;
+            }, 
+            None => fade::out(&mut state)?, 
+        }
+        let summary = Summary::new(&state, generator, self.solver, generate_steps, solve_steps);
+        Ok((state, summary))
+    }
+
+    /// Runs all phases without rendering anything, returning the final state and a summary of the run. Only
+    /// fails if the generated maze fails verification (see [`Builder::verify`]). 
+    pub fn run_headless(self) -> Result<(walk::State, Summary), Error> {
+        let generator = self.generator();
+        let settings = self.resolved_settings(self.parallel)?;
+        let (mut state, generate_steps) = match self.maze {
+            Source::Generate(width, height) => {
+                let mut state = generate::state(Maze::new(width, height), settings);
+                let steps = match self.parallel {
+                    true => generate::run_parallel(self.generator, &mut state), 
+                    false => self.generator.run_headless(&mut state), 
+                };
+                generated(&state, self.save.as_ref())?;
+                (solve::state(state), steps)
+            }, 
+            Source::Given(maze) => (solve::given(maze, settings), 0), 
+        };
+        let solve_steps = solve::run_headless(self.solver, &mut state);
+
+        let mut state = walk::state(state);
+        state.settings.walk.walker().run_headless(&mut state);
+        let summary = Summary::new(&state, generator, self.solver, generate_steps, solve_steps);
+        Ok((state, summary))
+    }
+
+    /// Runs all phases without a terminal, passing a snapshot of each timestep to a callback (as yielded by
+    /// [`Animation::frames`](crate::Animation::frames)), and returning the final state and a summary of the
+    /// run. Unlike [`Overlook::run`], there are no fades between phases. With a fixed seed, the frames are the
+    /// same each time, and may be compared against those of earlier runs. 
+    pub fn run_frames(
+        self, 
+        mut frame: impl FnMut(Frame) -> Result<(), Error>, 
+    ) -> Result<(walk::State, Summary), Error> {
+        let generator = self.generator();
+        let settings = self.resolved_settings(false)?;
+        let (mut state, generate_steps) = match self.maze {
+            Source::Generate(width, height) => {
+                let mut state = generate::state(Maze::new(width, height), settings);
+                let step = self.generator.stepper(&mut state);
+                let steps = capture(&mut state, step, &mut frame)?;
+                generated(&state, self.save.as_ref())?;
+                (solve::state(state), steps)
+            }, 
+            Source::Given(maze) => (solve::given(maze, settings), 0), 
+        };
+        let step = solve::stepper(self.solver, &mut state);
+        let solve_steps = capture(&mut state, step, &mut frame)?;
+
+        let mut state = walk::state(state);
+        let step = state.settings.walk.walker().stepper(&mut state);
+        capture(&mut state, step, &mut frame)?;
+        let summary = Summary::new(&state, generator, self.solver, generate_steps, solve_steps);
+        Ok((state, summary))
+    }
+
+    /// Gets the settings, with the seed replaced by one generating a maze of the minimum difficulty (if any)
+    /// when generated in parallel or not (see [`generate::seed_with_difficulty`]). 
+    fn resolved_settings(&self, parallel: bool) -> Result<Settings, Error> {
+        let (Some(min_difficulty), &Source::Generate(width, height)) = (self.min_difficulty, &self.maze) else {
+            return Ok(self.settings.clone())
+        };
+        let seed = generate::seed_with_difficulty(
+            self.generator, 
+            (width, height), 
+            parallel, 
+            &self.settings, 
+            min_difficulty, 
+        )?;
+        Ok(Settings{ seed, ..self.settings.clone() })
+    }
+
+    /// Gets the generator, unless the maze is given. 
+    fn generator(&self) -> Option<Generator> {
+        match self.maze {
+            Source::Generate(..) => Some(self.generator), 
+            Source::Given(_) => None, 
+        }
+    }
+}
+
+/// Summary of a finished pipeline, e.g. to be printed once the terminal is restored. 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Summary {
+    /// Width of the maze in nodes. 
+    pub width: usize, 
+    /// Height of the maze in nodes. 
+    pub height: usize, 
+    /// Seed the run was started from (see [`Settings::seed`]). 
+    pub seed: u64, 
+    /// Name of the generator, or `None` if the maze was given. 
+    pub generator: Option<&'static str>, 
+    /// Name of the solver. 
+    pub solver: &'static str, 
+    /// Timesteps taken by the generator. 
+    pub generate_steps: usize, 
+    /// Timesteps taken by the solver. 
+    pub solve_steps: usize, 
+    /// Number of nodes visited (i.e. expanded) by the solver. 
+    pub expanded: usize, 
+    /// Number of nodes along the solution, including the start and the goal, if one was found. 
+    pub solution_length: Option<usize>, 
+}
+
+impl Summary {
+    /// Summarises the final state of a pipeline. 
+    fn new(
+        state: &walk::State, 
+        generator: Option<Generator>, 
+        solver: Solver, 
+        generate_steps: usize, 
+        solve_steps: usize, 
+    ) -> Summary {
+        Summary {
+            width: state.maze.width, 
+            height: state.maze.height, 
+            seed: state.settings.seed, 
+            generator: generator.map(|generator| generator.name()), 
+            solver: solver.name(), 
+            generate_steps, 
+            solve_steps, 
+            expanded: state.visited_count, 
+            solution_length: state.phase.solution().map(|solution| solution.path.len()), 
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "maze:       {}x{} (seed {})", self.width, self.height, self.seed)?;
+        match self.generator {
+            Some(generator) => writeln!(f, "generator:  {generator}, {} steps", self.generate_steps)?, 
+            None => writeln!(f, "generator:  none (loaded)")?, 
+        }
+        writeln!(f, "solver:     {}, {} steps, {} nodes expanded", self.solver, self.solve_steps, self.expanded)?;
+        match self.solution_length {
+            Some(length) => writeln!(f, "solution:   {length} nodes")?, 
+            None => writeln!(f, "solution:   none found")?, 
+        }
+        if let Some(code) = RunCode::new(self) {
+            writeln!(f, "code:       {code}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Verifies the generated maze (if enabled) and saves it (if requested). 
+fn generated(state: &generate::State, save: Option<&Output>) -> Result<(), Error> {
+    generate::verify(state)?;
+    if let Some(output) = save {
+        output.write(state)?;
+    }
+    Ok(())
+}
+
+/// Steps an algorithm until it's done, passing a snapshot of each timestep to a callback, and returning the
+/// number of timesteps taken. 
+fn capture<T: Phase>(
+    state: &mut State<T>, 
+    mut step: Stepper<T>, 
+    frame: &mut impl FnMut(Frame) -> Result<(), Error>, 
+) -> Result<usize, Error> {
+    let mut steps = 0;
+    while let Signal::Continue = step(state) {
+        frame(Frame::capture(state))?;
+        state.step();
+        steps += 1;
+    }
+    state.emit(Event::PhaseFinished(T::NAME));
+    Ok(steps)
+}
+
+/// Builder for an [`Overlook`] pipeline. 
+pub struct Builder {
+    maze: Source, 
+    generator: Generator, 
+    solver: Solver, 
+    parallel: bool, 
+    settings: Settings, 
+    save: Option<Output>, 
+    min_difficulty: Option<f64>, 
+    #[cfg(feature = "terminal")]
+    chase: bool, 
+}
+
+impl Builder {
+    /// Sets the dimensions of the generated maze, in nodes, each of which must be at least 2 (checked by
+    /// [`Builder::build`]). 
+    pub fn size(self, width: u16, height: u16) -> Builder {
+        Builder{ maze: Source::Generate(width, height), ..self }
+    }
+
+    /// Solves a given maze instead of generating one. 
+    pub fn maze(self, maze: Maze) -> Builder {
+        Builder{ maze: Source::Given(maze), ..self }
+    }
+
+    /// Sets the algorithm generating the maze. Ignored for given mazes. 
+    pub fn generator(self, generator: Generator) -> Builder {
+        Builder{ generator, ..self }
+    }
+
+    /// Sets the algorithm solving the maze. 
+    pub fn solver(self, solver: Solver) -> Builder {
+        Builder{ solver, ..self }
+    }
+
+    /// Generates the maze in tiles in parallel when running headless (see [`generate::run_parallel`]). 
+    pub fn parallel(self, parallel: bool) -> Builder {
+        Builder{ parallel, ..self }
+    }
+
+    /// Replaces all settings. 
+    pub fn settings(self, settings: Settings) -> Builder {
+        Builder{ settings, ..self }
+    }
+
+    /// Sets the delay between frames of animated runs (see [`Settings::delay`]). 
+    pub fn delay(mut self, delay: Duration) -> Builder {
+        self.settings.delay = delay;
+        self
+    }
+
+    /// Sets the seed of the run, such that the same seed generates and solves the same maze each time (see
+    /// [`Settings::seed`]). 
+    pub fn seed(mut self, seed: u64) -> Builder {
+        self.settings.seed = seed;
+        self
+    }
+
+    /// Checks that the generated maze is perfect before solving it (see [`generate::verify`]). 
+    pub fn verify(mut self, verify: bool) -> Builder {
+        self.settings.verify = verify;
+        self
+    }
+
+    /// Writes the generated maze to an output (if any) before solving it, e.g. to be loaded again later. 
+    pub fn save(self, save: Option<Output>) -> Builder {
+        Builder{ save, ..self }
+    }
+
+    /// Regenerates the maze until it has at least the given difficulty (if any), by trying other seeds (see
+    /// [`generate::seed_with_difficulty`]). Ignored for given mazes. 
+    pub fn min_difficulty(self, min_difficulty: Option<f64>) -> Builder {
+        Builder{ min_difficulty, ..self }
+    }
+
+    /// Chases the generator with a search while it's generating the maze (see [`Chase`]). Only affects
+    /// animated runs (see [`Overlook::run`]). 
+    #[cfg(feature = "terminal")]
+    pub fn chase(self, chase: bool) -> Builder {
+        Builder{ chase, ..self }
+    }
+
+    /// Builds the pipeline, ready to be run. Fails if the maze to be generated is smaller than 2x2 nodes. 
+    pub fn build(self) -> Result<Overlook, Error> {
+        if let Source::Generate(width, height) = self.maze && (width < 2 || height < 2) {
+            let message = format!("a maze of {width}x{height} nodes is too small, it must be at least 2x2");
+            return Err(Error::InvalidArguments(message))
+        }
+        let Builder {
+            maze, 
+            generator, 
+            solver, 
+            parallel, 
+            settings, 
+            save, 
+            min_difficulty, 
+            #[cfg(feature = "terminal")]
+            chase, 
+        } = self;
+        Ok(Overlook {
+            maze, 
+            generator, 
+            solver, 
+            parallel, 
+            settings, 
+            save, 
+            min_difficulty, 
+            #[cfg(feature = "terminal")]
+            chase, 
+        })
+    }
+}
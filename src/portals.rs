@@ -0,0 +1,89 @@
+//! Pairs of teleporting nodes placed in a maze once generated, as given by `--portals` (see
+//! [`Settings::portals`](crate::Settings::portals)). Entering either node of a pair moves straight on to its
+//! twin, which solvers consider a neighbour of it (see [`solve::Phase::open_neighbours`]). 
+
+use rand::{seq::SliceRandom, Rng};
+use crate::{
+    colour::Hsl, 
+    maze::{Direction, Edge, Maze, Node, NodeBuffer}, 
+    solve, 
+    state::CellOverlay, 
+};
+
+/// Glyphs drawn upon the nodes of each pair, such that twins are drawn alike. 
+const GLYPHS: &[u8; 26] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Portals within a maze, each linking two dead ends. 
+pub struct Portals {
+    /// The twin of each node that is a portal. 
+    twins: NodeBuffer<Option<Node>>, 
+    /// Index of the pair of each node that is a portal, which picks its glyph. 
+    pairs: NodeBuffer<Option<usize>>, 
+    /// Colour of the glyphs, contrasting with the colours of the solver. 
+    colour: Hsl, 
+}
+
+impl Portals {
+    /// Places up to a given number of pairs upon the dead ends of a maze at random, other than those to be
+    /// kept clear (e.g. the start and the goal). Fewer pairs are placed if the maze has too few dead ends. 
+    pub fn place(maze: &Maze, pairs: usize, keep_clear: &[Node], rng: &mut impl Rng, colour: Hsl) -> Portals {
+        let dead_ends: Vec<Node> = maze.dead_ends().filter(|node| !keep_clear.contains(node)).collect();
+        let chosen: Vec<Node> = dead_ends.choose_multiple(rng, 2 * pairs).copied().collect();
+
+        let mut portals = Portals {
+            twins: NodeBuffer::new(maze), 
+            pairs: NodeBuffer::new(maze), 
+            colour, 
+        };
+        for (i, pair) in chosen.chunks_exact(2).enumerate() {
+            let (a, b) = (pair[0], pair[1]);
+            portals.twins[a] = Some(b);
+            portals.twins[b] = Some(a);
+            portals.pairs[a] = Some(i);
+            portals.pairs[b] = Some(i);
+        }
+        portals
+    }
+
+    /// Gets the twin a node leads to, if it's a portal. 
+    pub fn twin(&self, node: Node) -> Option<Node> {
+        self.twins[node]
+    }
+
+    /// Gets the edge from a portal to its twin, if the node is one. The nodes aren't adjacent, so the
+    /// direction of the edge is meaningless, and it can't index the open edges of the maze. 
+    pub fn edge(&self, node: Node) -> Option<Edge> {
+        self.twin(node).map(|twin| Edge {
+            from: node, 
+            to: twin, 
+            direction: Direction::North, 
+        })
+    }
+
+    /// Gets the glyph drawn upon a portal: the letter of its pair. 
+    pub(crate) fn overlay(&self, node: Node) -> Option<CellOverlay> {
+        let pair = self.pairs[node]?;
+        Some(CellOverlay {
+            glyph: GLYPHS[pair % GLYPHS.len()] as char, 
+            colour: self.colour, 
+            intensity: 1.0, 
+        })
+    }
+}
+
+/// Places portals in a maze about to be solved, as many pairs as given by the settings, kept clear of the
+/// nodes searched from and to. Returns `None` if none are to be placed. 
+pub fn place(state: &mut solve::State) -> Option<Portals> {
+    if state.settings.portals == 0 {
+        return None
+    }
+    let mut keep_clear = vec![state.start, state.goal];
+    keep_clear.extend(state.starts.iter().flat_map(|starts| starts.nodes.iter().copied()));
+    // a quarter turn from the youngest nodes in hue, unlike the player, and as light as possible
+    let colour = state.colours.palette.young.shift_h(90.0).with_s(1.0);
+    let colour = colour.with_l(match state.settings.light {
+        true => 0.35, 
+        false => 0.85, 
+    });
+    Some(Portals::place(&state.maze, state.settings.portals, &keep_clear, &mut state.rng, colour))
+}
@@ -0,0 +1,112 @@
+use std::{
+    fs, 
+    path::Path, 
+    sync::{Arc, Mutex}, 
+};
+use overlook::{events::Hooks, maze::Node, Error};
+
+/// Recording of a run, as written by `--record-run` and played back by `overlook replay`. 
+///
+/// Runs are reproducible given their arguments and seed, so these are what's recorded. Every event emitted
+/// (i.e. every decision of the algorithms) is recorded too, such that a replay can tell if it diverged,
+/// e.g. since an algorithm changed between versions. 
+///
+/// Recordings are plain text, starting with a header line followed by one line per argument and per event: 
+///
+/// ```text
+/// overlook-recording 1
+/// arg --generator
+/// arg dfs
+/// arg --seed
+/// arg 42
+/// event visit 0 0
+/// event open 0 0 1 0
+/// ```
+pub struct Recording {
+    /// Arguments of the run, excluding the program name. 
+    pub args: Vec<String>, 
+    /// Every event of the run, formatted as text. 
+    pub events: Vec<String>, 
+}
+
+impl Recording {
+    /// Header identifying recordings and the version of their format. 
+    const HEADER: &str = "overlook-recording 1";
+
+    /// Reads a recording from a file. 
+    pub fn read(path: &Path) -> Result<Recording, Error> {
+        let text = fs::read_to_string(path)?;
+        let mut lines = text.lines();
+        if lines.next() != Some(Recording::HEADER) {
+            return Err(Error::InvalidArguments(format!("{} is not a recording of a run", path.display())))
+        }
+
+        let mut recording = Recording{ args: Vec::new(), events: Vec::new() };
+        for line in lines {
+            match line.split_once(' ') {
+                Some(("arg", arg)) => recording.args.push(arg.to_owned()), 
+                Some(("event", event)) => recording.events.push(event.to_owned()), 
+                _ => return Err(Error::InvalidArguments(format!("invalid line in recording: `{line}`"))), 
+            }
+        }
+        Ok(recording)
+    }
+
+    /// Gets the moves of the player recorded in a race, each with the timestep it was made at. 
+    pub fn moves(&self) -> Vec<(i64, Node)> {
+        self.events
+            .iter()
+            .filter_map(|event| {
+                let (time, node) = event.strip_prefix("move ")?.split_once(' ')?;
+                let [x, y] = numbers(node)?;
+                Some((time.parse().ok()?, Node(x, y)))
+            })
+            .collect()
+    }
+
+    /// Gets the nodes visited by the solver of a run, one per timestep, as if moved to by a player. 
+    pub fn visits(&self) -> Vec<(i64, Node)> {
+        self.events
+            .iter()
+            .skip_while(|&event| event != "finish generate")
+            .filter_map(|event| event.strip_prefix("visit ").and_then(numbers))
+            .zip(0..)
+            .map(|([x, y], time)| (time, Node(x, y)))
+            .collect()
+    }
+
+    /// Writes the recording to a file. Arguments containing line breaks can't be recorded. 
+    pub fn write(&self, path: &Path) -> Result<(), Error> {
+        if self.args.iter().any(|arg| arg.contains('\n')) {
+            return Err(Error::InvalidArguments("arguments containing line breaks can't be recorded".to_owned()))
+        }
+        let args = self.args.iter().map(|arg| format!("arg {arg}\n"));
+        let events = self.events.iter().map(|event| format!("event {event}\n"));
+        let text: String = [format!("{}\n", Recording::HEADER)].into_iter().chain(args).chain(events).collect();
+        fs::write(path, text)?;
+        Ok(())
+    }
+}
+
+/// Parses the numbers of an event separated by spaces, e.g. the coordinates of a node. 
+fn numbers<const N: usize>(text: &str) -> Option<[usize; N]> {
+    let numbers: Vec<usize> = text.split(' ').map(|number| number.parse().ok()).collect::<Option<_>>()?;
+    numbers.try_into().ok()
+}
+
+/// Collects the events of a run, formatted as text. 
+#[derive(Clone, Default)]
+pub struct Recorder(Arc<Mutex<Vec<String>>>);
+
+impl Recorder {
+    /// Registers the recorder as a hook. 
+    pub fn attach(&self, hooks: &mut Hooks) {
+        let events = Arc::clone(&self.0);
+        hooks.register(move |event| events.lock().unwrap().push(event.to_string()));
+    }
+
+    /// Gets the events recorded so far. 
+    pub fn events(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::RwLock;
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
+use crate::{
+    state::{Phase, State}, 
+    Animation, Stepper, 
+};
+#[cfg(feature = "terminal")]
+use crate::Error;
+
+/// An [`Animation`] registered under a name, such that it may be selected at runtime (e.g., via
+/// `--generator` or `--solver`). 
+pub struct Entry<T> {
+    name: &'static str, 
+    #[cfg(feature = "terminal")]
+    run: fn(&mut State<T>) -> Result<usize, Error>, 
+    run_headless: fn(&mut State<T>) -> usize, 
+    stepper: fn(&mut State<T>) -> Stepper<T>, 
+}
+
+impl<T: Phase> Entry<T> {
+    /// Constructs an entry for an animation of the phase. 
+    pub fn new<A: Animation<Phase = T> + 'static>(name: &'static str) -> Entry<T> {
+        Entry {
+            name, 
+            #[cfg(feature = "terminal")]
+            run: A::run, 
+            run_headless: A::run_headless, 
+            stepper: A::stepper, 
+        }
+    }
+
+    /// Gets the name of the animation. 
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Runs the animation, returning the number of timesteps taken (see [`Animation::run`]). 
+    #[cfg(feature = "terminal")]
+    pub fn run(&self, state: &mut State<T>) -> Result<usize, Error> {
+        (self.run)(state)
+    }
+
+    /// Runs the animation without rendering, returning the number of timesteps taken (see
+    /// [`Animation::run_headless`]). 
+    pub fn run_headless(&self, state: &mut State<T>) -> usize {
+        (self.run_headless)(state)
+    }
+
+    /// Constructs the animation to be stepped by the caller (see [`Animation::stepper`]). 
+    pub fn stepper(&self, state: &mut State<T>) -> Stepper<T> {
+        (self.stepper)(state)
+    }
+}
+
+// manual implementations since deriving would require `T: Clone`
+impl<T> Clone for Entry<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Entry<T> {}
+
+/// The set of animations registered for a phase. 
+pub struct Registry<T> {
+    entries: RwLock<Vec<Entry<T>>>, 
+}
+
+impl<T: Phase> Registry<T> {
+    /// Constructs a registry with given initial entries. 
+    pub fn new(entries: impl IntoIterator<Item = Entry<T>>) -> Registry<T> {
+        let entries = RwLock::new(entries.into_iter().collect());
+        Registry{ entries }
+    }
+
+    /// Registers an animation, replacing any previously registered under the same name. 
+    pub fn register(&self, entry: Entry<T>) {
+        let mut entries = self.entries.write().unwrap();
+        match entries.iter_mut().find(|e| e.name == entry.name) {
+            Some(existing) => *existing = entry, 
+            None => entries.push(entry), 
+        }
+    }
+
+    /// Finds the animation registered under a name. 
+    pub fn find(&self, name: &str) -> Option<Entry<T>> {
+        self.entries
+            .read()
+            .unwrap()
+            .iter()
+            .find(|e| e.name == name)
+            .copied()
+    }
+
+    /// Gets all registered animations, in the order of registration. 
+    pub fn entries(&self) -> Vec<Entry<T>> {
+        self.entries.read().unwrap().clone()
+    }
+
+    /// Chooses an animation at random, each with a probability proportional to its weight, or `None` if the
+    /// weights are all zero. 
+    pub fn choose(&self, rng: &mut impl Rng, weight: impl Fn(&str) -> f64) -> Option<Entry<T>> {
+        let entries = self.entries();
+        let index = WeightedIndex::new(entries.iter().map(|entry| weight(entry.name))).ok()?;
+        Some(entries[index.sample(rng)])
+    }
+}
@@ -0,0 +1,694 @@
+//! Backends drawing the cells of a [`State`](crate::state::State) (see
+//! [`State::draw`](crate::state::State::draw)). 
+//!
+//! The backends included are [`Ansi`] rendering into a string of escape sequences (or of plain characters,
+//! see [`symbol`], which the `Display` impl of the state uses), [`Terminal`] writing those to the terminal,
+//! [`Console`](crate::console::Console) drawing to legacy Windows consoles, and
+//! [`Frame`](crate::frame::Frame) capturing the RGB colour of each cell. 
+
+use std::{fmt::Write as _, io, str::FromStr, time::Duration};
+#[cfg(feature = "terminal")]
+use std::{
+    io::{BufWriter, Write}, 
+    sync::{
+        mpsc::{self, Receiver, SyncSender, TrySendError}, 
+        Arc, Mutex, 
+    }, 
+    thread::{self, JoinHandle}, 
+    time::Instant, 
+};
+use clap::ValueEnum;
+#[cfg(feature = "terminal")]
+use crossterm::{
+    cursor::{MoveTo, RestorePosition}, 
+    terminal::{BeginSynchronizedUpdate, Clear, ClearType, EndSynchronizedUpdate}, 
+    Command, 
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+#[cfg(feature = "terminal")]
+use crate::capture::Capture;
+use crate::{
+    colour::{Lut, Paint, Swatch}, 
+    state::{CellOverlay, Marker}, 
+    viewport::Minimap, 
+};
+
+/// How the maze is laid out in cells, as given by `--layout` and toggled with `z` while animating. Layouts
+/// are declared from the least dense to the most, each fitting larger mazes in the same area (see
+/// [`Layout::fit`]). 
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Layout {
+    /// Each node and each edge is a cell two columns wide, or one if the cell contents are (see
+    /// [`Settings::cell`](crate::Settings::cell)). 
+    #[default]
+    #[value(help = "Each node and each edge is a cell two columns wide, or one if --cell is one column wide")]
+    Cells, 
+    /// Each node and each edge is half of a cell one column wide, split into a top and a bottom half, such
+    /// that mazes twice as wide and twice as tall fit. 
+    #[value(name = "half-block")]
+    HalfBlock, 
+    /// Each node is a cell one column wide, with the walls east and south of it drawn as thin lines upon it,
+    /// such that mazes four times as wide and twice as tall fit. 
+    Compact, 
+    /// Each node and each edge is a dot of a braille pattern two dots wide and four tall, drawn in a single
+    /// colour per cell, such that mazes four times as wide and four times as tall fit. 
+    Braille, 
+}
+
+impl Layout {
+    /// Number of columns each cell is wide, given the pitch of cells as laid out by default (i.e. the width of
+    /// their contents, see [`Settings::pitch`](crate::Settings::pitch)). 
+    pub fn columns(self, pitch: usize) -> usize {
+        match self {
+            Layout::Cells => pitch, 
+            Layout::HalfBlock | Layout::Compact | Layout::Braille => 1, 
+        }
+    }
+
+    /// Number of cells as laid out by default (see [`Layout::Cells`]) that each cell spans across and down. 
+    pub fn span(self) -> (usize, usize) {
+        match self {
+            Layout::Cells => (1, 1), 
+            Layout::HalfBlock => (1, 2), 
+            Layout::Compact => (2, 2), 
+            Layout::Braille => (2, 4), 
+        }
+    }
+
+    /// Gets the number of columns and rows a maze of given dimensions takes up, given the pitch of cells. 
+    pub fn extent(self, (width, height): (usize, usize), pitch: usize) -> (usize, usize) {
+        let (span_x, span_y) = self.span();
+        let columns = (2 * width - 1).div_ceil(span_x) * self.columns(pitch);
+        (columns, (2 * height - 1).div_ceil(span_y))
+    }
+
+    /// Gets the dimensions of the largest maze fitting within a number of columns and rows, given the pitch of
+    /// cells. 
+    pub fn fitting(self, (columns, rows): (usize, usize), pitch: usize) -> (usize, usize) {
+        // a maze of n nodes is 2n - 1 cells across as laid out by default
+        let (span_x, span_y) = self.span();
+        let columns = columns / self.columns(pitch);
+        ((span_x * columns).div_ceil(2), (span_y * rows).div_ceil(2))
+    }
+
+    /// Gets this layout if a maze of given dimensions fits within a number of columns and rows as laid out,
+    /// or else the least dense of the denser layouts that it fits, or the densest if it fits none. 
+    pub fn fit(self, dimensions: (usize, usize), (columns, rows): (usize, usize), pitch: usize) -> Layout {
+        let layouts = Layout::value_variants().iter().skip_while(|&&layout| layout != self);
+        let fits = |layout: &&Layout| {
+            let (width, height) = layout.extent(dimensions, pitch);
+            width <= columns && height <= rows
+        };
+        layouts.clone().find(fits).or_else(|| layouts.last()).copied().unwrap_or(self)
+    }
+
+    /// Gets the layout toggled to from this one, which is the next denser one, or the least dense after the
+    /// densest. 
+    pub fn next(self) -> Layout {
+        let layouts = Layout::value_variants();
+        let index = layouts.iter().position(|&layout| layout == self).unwrap_or(0);
+        layouts[(index + 1) % layouts.len()]
+    }
+}
+
+/// Glyphs drawn upon special cells in place of their colour alone, as given by `--glyphs`, e.g. 
+/// `start=🐭,goal=🧀`. Glyphs may be two columns wide (such as most emoji), filling a cell as laid out by
+/// default, but are left out of layouts whose cells are narrower than them. 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Glyphs {
+    /// Glyph drawn upon the nodes solved from (`start`). 
+    pub start: Option<char>, 
+    /// Glyph drawn upon the goal (`goal`). 
+    pub goal: Option<char>, 
+    /// Glyph drawn upon the nodes of the path once found (`path`). 
+    pub path: Option<char>, 
+}
+
+impl Glyphs {
+    /// Gets the glyph drawn upon a kind of special node, if any. 
+    pub fn get(self, marker: Marker) -> Option<char> {
+        match marker {
+            Marker::Start => self.start, 
+            Marker::Goal => self.goal, 
+            Marker::Path => self.path, 
+        }
+    }
+}
+
+impl FromStr for Glyphs {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Glyphs, String> {
+        let mut glyphs = Glyphs::default();
+
+        for pair in s.split(',') {
+            let Some((key, value)) = pair.split_once('=') else {
+                return Err(format!("expected key=glyph, got `{pair}`"))
+            };
+            let mut chars = value.chars();
+            let (Some(glyph), None) = (chars.next(), chars.next()) else {
+                return Err(format!("expected a single character for `{key}`, got `{value}`"))
+            };
+            let slot = match key {
+                "start" => &mut glyphs.start, 
+                "goal" => &mut glyphs.goal, 
+                "path" => &mut glyphs.path, 
+                _ => return Err(format!("unknown key `{key}`, expected start, goal, or path")), 
+            };
+            *slot = Some(glyph);
+        }
+        Ok(glyphs)
+    }
+}
+
+/// Gets the number of columns cells as laid out by default are wide given their contents, which is one or
+/// two. 
+pub fn pitch(cell: &str) -> usize {
+    cell.width().clamp(1, 2)
+}
+
+/// Gets a glyph padded with spaces to fill a number of columns, or `None` if it's wider than that. 
+pub(crate) fn padded(glyph: char, columns: usize) -> Option<String> {
+    // control characters have no width of their own
+    let width = glyph.width().unwrap_or(1);
+    let padding = columns.checked_sub(width)?;
+    Some(format!("{glyph}{:padding$}", ""))
+}
+
+/// A backend that cells are drawn to. 
+pub trait Renderer {
+    /// Gets the size of the drawing area in cells, or `None` if it's unbounded. 
+    fn size(&self) -> Option<(usize, usize)>;
+    /// Gets how the maze is laid out in cells (see [`Layout`]). Renderers drawing cells of fixed size draw
+    /// each node and edge as a cell. 
+    fn layout(&self) -> Layout {
+        Layout::Cells
+    }
+    /// Changes how the maze is laid out in cells from the next frame on, e.g. once toggled while animating. 
+    /// Ignored by renderers drawing cells of fixed size. 
+    fn set_layout(&mut self, _layout: Layout) {}
+    /// Draws a cell in the colour of a swatch, with an overlay on top if any. A frame is drawn cell by cell in
+    /// row-major order, starting at the top left. 
+    fn draw_cell(&mut self, x: usize, y: usize, swatch: Swatch, overlay: Option<CellOverlay>, colours: &Lut);
+    /// Draws a cell split into a top and a bottom half, each in the colour of a swatch (see
+    /// [`Layout::HalfBlock`]). Renderers not supporting this draw the whole cell as its top half. 
+    fn draw_halves(&mut self, x: usize, y: usize, halves: [(Swatch, &Lut); 2]) {
+        let [(swatch, colours), _] = halves;
+        self.draw_cell(x, y, swatch, None, colours);
+    }
+    /// Draws a cell as a braille pattern, given by the bits set for its dots, in the colour of a swatch upon
+    /// walls (see [`Layout::Braille`]). Renderers not supporting this draw the whole cell in the swatch. 
+    fn draw_dots(&mut self, x: usize, y: usize, _dots: u8, swatch: Swatch, colours: &Lut) {
+        self.draw_cell(x, y, swatch, None, colours);
+    }
+    /// Draws a line of text below the cells of the frame (see [`Phase::status`](crate::state::Phase::status)). 
+    /// Ignored by renderers not supporting text. 
+    fn draw_status(&mut self, _status: &str) {}
+    /// Draws lines of text in a panel beside the cells of the frame, one per row from the top (see
+    /// [`Animation::debug_info`](crate::Animation::debug_info)). Ignored by renderers not supporting text. 
+    fn draw_panel(&mut self, _lines: &[String]) {}
+    /// Draws a minimap of the whole maze in the top right corner of the cells of the frame, while only those
+    /// within a viewport are drawn (see [`viewport`](crate::viewport)). Ignored by renderers not supporting
+    /// text. 
+    fn draw_minimap(&mut self, _minimap: &Minimap, _colours: &Lut) {}
+    /// Presents the cells drawn since the previous call as a complete frame. 
+    fn present(&mut self) -> io::Result<()>;
+    /// Gets the time taken writing the latest frame written, e.g. to skip drawing frames the output couldn't
+    /// keep up with anyway. Zero for renderers not writing frames anywhere. 
+    fn write_time(&self) -> Duration {
+        Duration::ZERO
+    }
+}
+
+/// Escape sequence clearing the rest of the line from the cursor. 
+const CLEAR_LINE: &str = "\x1b[K";
+
+/// Renders a frame into a string of escape sequences, with rows separated by `\n\r`. 
+#[derive(Clone, Debug)]
+pub struct Ansi {
+    pub text: String, 
+    /// Whether cells are drawn as characters rather than colours (see [`symbol`]). 
+    pub symbols: bool, 
+    /// Column each row after the first starts at, such that frames may be drawn away from the left edge. 
+    pub indent: u16, 
+    /// How the maze is laid out in cells. 
+    pub layout: Layout, 
+    /// Number of columns cells as laid out by default are wide, being that of the escape sequences drawing
+    /// them (see [`Settings::pitch`](crate::Settings::pitch)). 
+    pub pitch: usize, 
+    /// Number of columns and rows of cells drawn, such that a panel may be drawn beside them. 
+    cells: (usize, usize), 
+    /// Number of lines started since the beginning of the frame. 
+    lines: usize, 
+}
+
+impl Default for Ansi {
+    fn default() -> Ansi {
+        Ansi {
+            text: String::new(), 
+            symbols: false, 
+            indent: 0, 
+            layout: Layout::default(), 
+            pitch: 2, 
+            cells: (0, 0), 
+            lines: 0, 
+        }
+    }
+}
+
+impl Ansi {
+    /// Number of columns each cell is wide. 
+    fn columns(&self) -> usize {
+        self.layout.columns(self.pitch)
+    }
+
+    /// Starts a new line at the indent. 
+    fn line_break(&mut self) {
+        self.lines += 1;
+        self.text.push_str("\n\r");
+        if self.indent > 0 {
+            // formatting into a string can't fail
+            let _ = write!(self.text, "\x1b[{}G", self.indent + 1);
+        }
+    }
+
+    /// Starts a cell, on a new line if it's the first of a row after the first. 
+    fn start_cell(&mut self, x: usize, y: usize) {
+        if x == 0 && y != 0 {
+            self.line_break();
+        }
+        self.cells = (self.cells.0.max(x + 1), self.cells.1.max(y + 1));
+    }
+
+    /// Writes over the frame drawn so far from its top row down, each row starting at a column, and moves back
+    /// to the line the frame ended on (e.g. that of the status). 
+    fn overwrite(&mut self, column: u16, rows: usize, mut write: impl FnMut(&mut String, usize)) {
+        // formatting into a string can't fail
+        if self.lines > 0 {
+            let _ = write!(self.text, "\x1b[{}A", self.lines);
+        }
+        for row in 0..rows {
+            if row > 0 {
+                self.text.push_str("\x1b[1B");
+            }
+            let _ = write!(self.text, "\x1b[{}G", column + 1);
+            write(&mut self.text, row);
+        }
+        let below = self.lines.saturating_sub(rows.saturating_sub(1));
+        if below > 0 {
+            let _ = write!(self.text, "\x1b[{below}B");
+        }
+    }
+}
+
+impl Renderer for Ansi {
+    fn size(&self) -> Option<(usize, usize)> {
+        None
+    }
+
+    fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.layout = layout;
+    }
+
+    fn draw_cell(&mut self, x: usize, y: usize, swatch: Swatch, overlay: Option<CellOverlay>, colours: &Lut) {
+        self.start_cell(x, y);
+        // glyphs too wide for the cell are left out
+        let overlay = overlay.filter(|overlay| overlay.glyph.width().unwrap_or(1) <= self.columns());
+        if self.columns() == 1 {
+            // cells are a single column, so symbols are drawn once rather than twice
+            let glyph = overlay.map(|overlay| overlay.glyph);
+            let glyph = glyph.or_else(|| symbol(swatch).chars().next()).unwrap_or(' ');
+            let background = colours.colour(swatch);
+            // formatting into a string can't fail
+            let _ = match (overlay, self.symbols) {
+                (_, true) => write!(self.text, "{glyph}"), 
+                (Some(overlay), false) => {
+                    write!(self.text, "{}", glyph.with(overlay.foreground()).on(background))
+                }, 
+                // the escapes draw the cell contents, which are only as narrow for cells as laid out by default
+                (None, false) if self.layout == Layout::Cells => {
+                    self.text.write_str(colours.escape_at(swatch, x, y))
+                }, 
+                (None, false) => write!(self.text, "{}", ' '.on(background)), 
+            };
+            return
+        }
+        match overlay {
+            Some(overlay) if self.symbols => {
+                self.text.push_str(&padded(overlay.glyph, 2).unwrap_or_default());
+            }, 
+            None if self.symbols => self.text.push_str(symbol(swatch)), 
+            Some(overlay) => {
+                // formatting into a string can't fail
+                let _ = write!(self.text, "{}", overlay.styled(colours.colour(swatch)));
+            }, 
+            None => self.text.push_str(colours.escape_at(swatch, x, y)), 
+        }
+    }
+
+    fn draw_halves(&mut self, x: usize, y: usize, halves: [(Swatch, &Lut); 2]) {
+        self.start_cell(x, y);
+        let [(top, top_colours), (bottom, bottom_colours)] = halves;
+        let glyph = match self.symbols {
+            true => half_block(top, bottom), 
+            false => '▀', 
+        };
+        // formatting into a string can't fail
+        let _ = match self.symbols {
+            true => write!(self.text, "{glyph}"), 
+            false => {
+                let (top, bottom) = (top_colours.colour(top), bottom_colours.colour(bottom));
+                write!(self.text, "{}", glyph.with(top).on(bottom))
+            }, 
+        };
+    }
+
+    fn draw_dots(&mut self, x: usize, y: usize, dots: u8, swatch: Swatch, colours: &Lut) {
+        self.start_cell(x, y);
+        let glyph = char::from_u32(0x2800 + dots as u32).expect("braille patterns are valid characters");
+        // formatting into a string can't fail
+        let _ = match self.symbols {
+            true => write!(self.text, "{glyph}"), 
+            false => {
+                let (colour, wall) = (colours.colour(swatch), colours.colour(Swatch::Wall));
+                write!(self.text, "{}", glyph.with(colour).on(wall))
+            }, 
+        };
+    }
+
+    fn draw_status(&mut self, status: &str) {
+        // clear the rest of the line, since the previous status may have been longer
+        self.line_break();
+        self.text.push_str(status);
+        self.text.push_str(CLEAR_LINE);
+    }
+
+    fn draw_panel(&mut self, lines: &[String]) {
+        let (columns, rows) = self.cells;
+        // the panel is set two columns apart from the cells
+        let column = (self.indent as usize + self.columns() * columns + 2) as u16;
+        // each row is cleared, such that shorter lines replace longer ones
+        self.overwrite(column, rows, |text, row| {
+            text.push_str(lines.get(row).map_or("", String::as_str));
+            text.push_str(CLEAR_LINE);
+        });
+    }
+
+    fn draw_minimap(&mut self, minimap: &Minimap, colours: &Lut) {
+        let (columns, rows) = self.cells;
+        let Some(column) = (self.columns() * columns).checked_sub(minimap.width()) else {
+            return
+        };
+        let column = (self.indent as usize + column) as u16;
+        let symbols = self.symbols;
+        // drawn upon walls, such that the minimap stands out from the cells around it
+        let background = colours.colour(Swatch::Wall);
+        self.overwrite(column, minimap.rows.len().min(rows), |text, row| {
+            for &(glyph, swatch) in &minimap.rows[row] {
+                // formatting into a string can't fail
+                let _ = match symbols {
+                    true => write!(text, "{glyph}"), 
+                    false => write!(text, "{}", glyph.with(colours.colour(swatch)).on(background)), 
+                };
+            }
+        });
+    }
+
+    fn present(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Gets the characters drawing a cell of a swatch without colour, such that frames are meaningful on
+/// monochrome terminals and when copied as text. Nodes fade from `█` to `░` as they age, and walls are blank. 
+pub fn symbol(swatch: Swatch) -> &'static str {
+    match swatch {
+        Swatch::Age(age) => ["██", "▓▓", "▒▒", "░░"][age as usize / 64], 
+        Swatch::Unvisited => "· ", 
+        Swatch::Special => "**", 
+        Swatch::Wall => "  ", 
+        Swatch::Head => "@@", 
+        Swatch::Fog => "::", 
+    }
+}
+
+/// Gets the block drawing a cell split into halves of two swatches without colour, filled where they aren't
+/// walls. 
+pub(crate) fn half_block(top: Swatch, bottom: Swatch) -> char {
+    match (top != Swatch::Wall, bottom != Swatch::Wall) {
+        (false, false) => ' ', 
+        (true, false) => '▀', 
+        (false, true) => '▄', 
+        (true, true) => '█', 
+    }
+}
+
+/// Renders to the terminal, writing frames on a separate thread such that slow terminal IO doesn't stall the
+/// algorithm. 
+///
+/// Each frame is rendered into a single buffer which is written in one go, and buffers are sent back to be
+/// reused for later frames. 
+#[cfg(feature = "terminal")]
+pub struct Terminal {
+    /// Frame currently being drawn. 
+    frame: Ansi, 
+    /// Whether frames are drawn at the saved cursor position rather than the top left (see
+    /// [`Settings::inline`](crate::Settings::inline)). 
+    inline: bool, 
+    /// Whether frames are wrapped in synchronized updates (see
+    /// [`Settings::synchronized`](crate::Settings::synchronized)). 
+    synchronized: bool, 
+    /// Terminal column and row the top left of the frame is drawn at, unless drawn inline. 
+    origin: (u16, u16), 
+    frames: SyncSender<String>, 
+    recycled: Receiver<String>, 
+    thread: JoinHandle<io::Result<()>>, 
+    /// Time the thread took writing the latest frame it wrote. 
+    write_time: Arc<Mutex<Duration>>, 
+    /// Latest frame, if it couldn't be sent since the thread was still busy with the previous one. 
+    dropped: Option<String>, 
+    /// Whether a panel was drawn in the frame being drawn, and in any sent since the panel was last cleared. 
+    panel: (bool, bool), 
+}
+
+#[cfg(feature = "terminal")]
+impl Terminal {
+    /// Spawns a thread writing frames to stdout until the renderer is finished or writing fails. 
+    pub fn spawn(inline: bool) -> Terminal {
+        Terminal::spawn_capturing(inline, None)
+    }
+
+    /// Spawns a thread writing frames to stdout as [`Terminal::spawn`] does, capturing each frame written (if
+    /// given a capture). 
+    pub fn spawn_capturing(inline: bool, capture: Option<Capture>) -> Terminal {
+        Terminal::writing(io::stdout(), inline, capture)
+    }
+
+    /// Spawns a thread writing frames to a given writer (e.g. a pty or socket) until the renderer is finished
+    /// or writing fails. 
+    pub fn spawn_with(writer: impl Write + Send + 'static, inline: bool) -> Terminal {
+        Terminal::writing(writer, inline, None)
+    }
+
+    /// Spawns the thread writing frames, capturing them as written (if given a capture). 
+    fn writing(writer: impl Write + Send + 'static, inline: bool, capture: Option<Capture>) -> Terminal {
+        let (frames, receiver) = mpsc::sync_channel::<String>(1);
+        let (recycler, recycled) = mpsc::channel();
+        let write_time = Arc::new(Mutex::new(Duration::ZERO));
+
+        let written = Arc::clone(&write_time);
+        let thread = thread::spawn(move || {
+            let mut writer = BufWriter::new(writer);
+            for frame in receiver {
+                let start = Instant::now();
+                writer.write_all(frame.as_bytes())?;
+                writer.flush()?;
+                *written.lock().expect("renderer panicked") = start.elapsed();
+                if let Some(capture) = &capture {
+                    capture.frame(&frame)?;
+                }
+                let _ = recycler.send(frame);
+            }
+            Ok(())
+        });
+        let mut terminal = Terminal {
+            frame: Ansi::default(), 
+            inline, 
+            synchronized: false, 
+            origin: (0, 0), 
+            frames, 
+            recycled, 
+            thread, 
+            write_time, 
+            dropped: None, 
+            panel: (false, false), 
+        };
+        terminal.begin(String::new());
+        terminal
+    }
+
+    /// Starts a new frame in the given buffer, moving the cursor to where the frame is drawn. 
+    fn begin(&mut self, mut buffer: String) {
+        buffer.clear();
+        // formatting into a string can't fail
+        let _ = match self.inline {
+            true => RestorePosition.write_ansi(&mut buffer), 
+            false => MoveTo(self.origin.0, self.origin.1).write_ansi(&mut buffer), 
+        };
+        if self.synchronized {
+            let _ = BeginSynchronizedUpdate.write_ansi(&mut buffer);
+        }
+        self.frame.text = buffer;
+        self.frame.lines = 0;
+        self.frame.cells = (0, 0);
+    }
+
+    /// Wraps each frame in a synchronized update, such that terminals supporting it show the frame at once. 
+    pub fn with_synchronized(mut self, synchronized: bool) -> Terminal {
+        self.synchronized = synchronized;
+        // the first frame was already started without beginning the update
+        let buffer = std::mem::take(&mut self.frame.text);
+        self.begin(buffer);
+        self
+    }
+
+    /// Draws cells as characters rather than colours (see [`symbol`]). 
+    pub fn with_symbols(mut self, symbols: bool) -> Terminal {
+        self.frame.symbols = symbols;
+        self
+    }
+
+    /// Draws cells as laid out by default a number of columns wide, being that of the escape sequences drawing
+    /// them (see [`Settings::pitch`](crate::Settings::pitch)). 
+    pub fn with_pitch(mut self, pitch: usize) -> Terminal {
+        self.frame.pitch = pitch;
+        self
+    }
+
+    /// Lays out the maze in cells as given (see [`Layout`]). 
+    pub fn with_layout(mut self, layout: Layout) -> Terminal {
+        self.set_layout(layout);
+        self
+    }
+
+    /// Draws frames with their top left at a terminal column and row, e.g. such that they're centred. Ignored
+    /// when drawn inline, since frames then start at the saved cursor position. 
+    pub fn with_origin(mut self, origin: (u16, u16)) -> Terminal {
+        if !self.inline {
+            self.origin = origin;
+            self.frame.indent = origin.0;
+            // the first frame was already started at the top left
+            let buffer = std::mem::take(&mut self.frame.text);
+            self.begin(buffer);
+        }
+        self
+    }
+
+    /// Sends the latest frame if it was dropped and waits for the thread to write it. 
+    pub fn finish(self) -> io::Result<()> {
+        if let Some(frame) = self.dropped {
+            let _ = self.frames.send(frame);
+        }
+        drop(self.frames);
+        self.thread.join().expect("renderer panicked")
+    }
+}
+
+#[cfg(feature = "terminal")]
+impl Renderer for Terminal {
+    fn size(&self) -> Option<(usize, usize)> {
+        // cells start at the origin
+        let (columns, rows) = crossterm::terminal::size().ok()?;
+        let (columns, rows) = (columns.saturating_sub(self.origin.0), rows.saturating_sub(self.origin.1));
+        Some((columns as usize / self.frame.columns(), rows as usize))
+    }
+
+    fn layout(&self) -> Layout {
+        self.frame.layout
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        if layout != self.frame.layout {
+            self.frame.layout = layout;
+            // the frame starts where the previous one did, which may have covered more of the terminal
+            let _ = Clear(ClearType::FromCursorDown).write_ansi(&mut self.frame.text);
+        }
+    }
+
+    fn draw_cell(&mut self, x: usize, y: usize, swatch: Swatch, overlay: Option<CellOverlay>, colours: &Lut) {
+        self.frame.draw_cell(x, y, swatch, overlay, colours);
+    }
+
+    fn draw_halves(&mut self, x: usize, y: usize, halves: [(Swatch, &Lut); 2]) {
+        self.frame.draw_halves(x, y, halves);
+    }
+
+    fn draw_dots(&mut self, x: usize, y: usize, dots: u8, swatch: Swatch, colours: &Lut) {
+        self.frame.draw_dots(x, y, dots, swatch, colours);
+    }
+
+    fn draw_status(&mut self, status: &str) {
+        self.frame.draw_status(status);
+    }
+
+    fn draw_minimap(&mut self, minimap: &Minimap, colours: &Lut) {
+        self.frame.draw_minimap(minimap, colours);
+    }
+
+    /// Draws a panel as [`Ansi`] does, with lines cut off at the edge of the terminal. The panel is skipped if
+    /// the cells leave no room for it. 
+    fn draw_panel(&mut self, lines: &[String]) {
+        let (columns, _) = crossterm::terminal::size().unwrap_or((u16::MAX, 0));
+        let cells = self.frame.columns() * self.frame.cells.0;
+        let room = (columns as usize).saturating_sub(self.frame.indent as usize + cells + 2);
+        if room == 0 {
+            return
+        }
+        let lines: Vec<String> = lines.iter().map(|line| line.chars().take(room).collect()).collect();
+        self.frame.draw_panel(&lines);
+        self.panel.0 = true;
+    }
+
+    /// Sends the frame to be written. If the thread is still busy with the previous frame, the frame is
+    /// dropped (and sent on [`Terminal::finish`] unless superseded). Fails if the thread has stopped due to
+    /// an error, which is then returned by [`Terminal::finish`]. 
+    fn present(&mut self) -> io::Result<()> {
+        // a panel no longer drawn (e.g. once toggled off) is cleared, since the cells don't cover it
+        let (drawn, shown) = std::mem::take(&mut self.panel);
+        if shown && !drawn {
+            self.frame.draw_panel(&[]);
+        }
+        if self.synchronized {
+            let _ = EndSynchronizedUpdate.write_ansi(&mut self.frame.text);
+        }
+        let buffer = self.dropped
+            .take()
+            .or_else(|| self.recycled.try_recv().ok())
+            .unwrap_or_default();
+        let frame = std::mem::take(&mut self.frame.text);
+        self.begin(buffer);
+
+        match self.frames.try_send(frame) {
+            Ok(_) => {
+                self.panel.1 = drawn;
+                Ok(())
+            }, 
+            Err(TrySendError::Full(frame)) => {
+                // the frame may be superseded without ever being written, so a panel shown before is still
+                // to be cleared
+                self.panel.1 = shown || drawn;
+                self.dropped = Some(frame);
+                Ok(())
+            }, 
+            Err(TrySendError::Disconnected(_)) => Err(io::ErrorKind::BrokenPipe.into()), 
+        }
+    }
+
+    fn write_time(&self) -> Duration {
+        *self.write_time.lock().expect("renderer panicked")
+    }
+}
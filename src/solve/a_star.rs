@@ -5,7 +5,11 @@ use crate::{
 };
 use super::{State, Phase};
 
-/// Search guided by Euclidian distance. 
+/// Number of entries at the top of the heap shown by the panel of internals (see [`Animation::debug_info`]). 
+const HEAP_TOP: usize = 5;
+
+/// Search guided by Euclidian distance. Moves are weighed by their cost, e.g. uphill moves costing more (see
+/// [`Phase::cost`]). 
 /// 
 /// This implementation is simplified from canonical ones since we can assume our mazes are free from loops. 
 pub struct AStar {
@@ -29,6 +33,20 @@ impl Animation for AStar {
     }
 
     fn step(&mut self, state: &mut State) -> Signal {
+        if super::replan(state) {
+            self.heap.clear();
+            if let Some(f_scores) = &mut state.phase.f_scores {
+                f_scores.scores.fill(None);
+            }
+            // nodes visited by the searches from other starts are left to them
+            let frontier = super::frontier(state).into_iter().filter(|e| self.g_score[e.from] != usize::MAX);
+            for edge in frontier.collect::<Vec<_>>() {
+                state.parents[edge.to] = Some(edge.from);
+                let g_score = self.g_score[edge.from] + state.cost(edge);
+                self.g_score[edge.to] = g_score;
+                self.heap.push(entry(g_score, edge.to, state));
+            }
+        }
         let Some((_, head)) = self.heap.pop() else {
             return Signal::Done
         };
@@ -38,21 +56,61 @@ impl Animation for AStar {
             return Signal::Done
         }
 
-        let neighbours = state.maze
-            .open_neighbours(head)
+        let neighbours = state.phase
+            .open_neighbours(&state.maze, head)
             .filter(|n| !state.is_visited(n));
 
         for edge in neighbours {
             let neighbour = edge.to;
             state.parents[neighbour] = Some(head);
 
-            let g_score = self.g_score[head] + 1;
+            let g_score = self.g_score[head] + state.cost(edge);
             self.g_score[neighbour] = g_score;
             self.heap.push(entry(g_score, neighbour, state));
         }
+        self.mark(state, head);
         Signal::Continue
     }
 
+    fn frontier(&self) -> Option<usize> {
+        Some(self.heap.len())
+    }
+
+    fn heads(&self) -> Vec<Node> {
+        // the most promising candidate, which is expanded next
+        self.heap.peek().map(|&(_, node)| node).into_iter().collect()
+    }
+
+    fn debug_info(&self) -> Vec<String> {
+        let mut info = vec![format!("heap: {} nodes", self.heap.len()), "top:".to_owned()];
+        // sorted by ascending `Reverse`, i.e. with the lowest f-score last
+        let sorted = self.heap.clone().into_sorted_vec();
+        info.extend(sorted.iter().rev().take(HEAP_TOP).map(|&(Reverse(f_score), node)| {
+            let Node(x, y) = node;
+            format!("  f {f_score} (g {}) at {x}:{y}", self.g_score[node])
+        }));
+        info
+    }
+}
+
+impl AStar {
+    /// Notes the `f`-scores of the queued nodes, with that of a node just expanded removed, for them to be
+    /// drawn if shown (see [`FScores`](super::FScores)). 
+    fn mark(&self, state: &mut State, expanded: Node) {
+        let start = Node::manhattan(state.start, state.goal);
+        let Some(f_scores) = &mut state.phase.f_scores else {
+            return
+        };
+        f_scores.scores[expanded] = None;
+        for &(Reverse(f_score), node) in &self.heap {
+            f_scores.scores[node] = Some(f_score);
+        }
+        // the lowest and highest scores queued are rarely more than a step apart, so the colours span from
+        // the lowest possible score (that of the start) to the highest queued yet, showing how far the search
+        // strays
+        let highest = self.heap.iter().map(|&(Reverse(f_score), _)| f_score).max().unwrap_or(start);
+        f_scores.range = (start, highest.max(f_scores.range.1));
+    }
 }
 
 fn entry(g_score: usize, node: Node, state: &mut State) -> (Reverse<usize>, Node) {
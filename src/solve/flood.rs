@@ -1,6 +1,6 @@
 use std::collections::VecDeque;
 use crate::{
-    maze::Edge, 
+    maze::{Edge, Node}, 
     Animation, Signal, 
 };
 use super::{State, Phase};
@@ -28,33 +28,58 @@ impl Animation for Flood {
     }
 
     fn step(&mut self, state: &mut State) -> Signal {
-        let Some(head) = self.queue_a.pop_front() else {
-            return match self.queue_b.is_empty() {
-                true => Signal::Done,
-                false => {
-                    std::mem::swap(&mut self.queue_a, &mut self.queue_b);
-                    Signal::Continue
-                }
-            }
-        };
+        if super::replan(state) {
+            // the layers searched so far no longer hold, so the search goes on from all nodes on its edge
+            self.queue_a = super::frontier(state).into();
+            self.queue_b.clear();
+        }
+        while let Some(head) = self.queue_a.pop_front() {
+            state.visit(head.to);
+            state.parents[head.to] = Some(head.from);
 
-        state.visit(head.to);
-        state.parents[head.to] = Some(head.from);
+            if head.to == state.goal {
+                return Signal::Done
+            }
 
-        if head.to == state.goal {
-            return Signal::Done
+            let open_neighbours = state.phase
+                .open_neighbours(&state.maze, head.to)
+                .into_iter()
+                .filter(|&e| !state.is_visited(e.to));
+            self.queue_b.extend(open_neighbours);
         }
 
-        let open_neighbours = state.maze
-            .open_neighbours(head.to)
-            .into_iter()
-            .filter(|&e| !state.is_visited(e.to));
-        self.queue_b.extend(open_neighbours);
-
-        self.step(state)
+        match self.queue_b.is_empty() {
+            true => Signal::Done, 
+            false => {
+                std::mem::swap(&mut self.queue_a, &mut self.queue_b);
+                Signal::Continue
+            }
+        }
     }
 
     fn timescale(&self) -> u32 {
         75
     }
+
+    fn frontier(&self) -> Option<usize> {
+        Some(self.queue_a.len() + self.queue_b.len())
+    }
+
+    fn heads(&self) -> Vec<Node> {
+        // the frontier spreads in all directions, so the queued node nearest to its centre is marked
+        let count = self.queue_a.len().max(1) as f64;
+        let (x, y) = self.queue_a
+            .iter()
+            .fold((0.0, 0.0), |(x, y), edge| (x + edge.to.0 as f64, y + edge.to.1 as f64));
+        let (x, y) = (x / count, y / count);
+        self.queue_a
+            .iter()
+            .map(|edge| edge.to)
+            .min_by(|a, b| {
+                let distance = |node: &Node| f64::hypot(node.0 as f64 - x, node.1 as f64 - y);
+                distance(a).total_cmp(&distance(b))
+            })
+            .into_iter()
+            .collect()
+    }
 }
@@ -1,17 +1,38 @@
-use std::collections::VecDeque;
+use std::{cmp::Ordering, collections::VecDeque, fmt::Write, iter, mem, str::FromStr, sync::LazyLock};
 use clap::ValueEnum;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use crate::{
-    colour::Palette, 
+    analysis::Difficulty, 
+    colour::{Hsl, Lut}, 
+    elevation::Elevation, 
+    events::Hooks, 
+    game::Player, 
     generate, 
-    maze::{Edge, Maze, Node, NodeBuffer}, 
-    state, 
-    Animation, Error, 
+    maze::{Edge, Maze, Neighbours, Node, NodeBits, NodeBuffer}, 
+    ice::{self, Ice}, 
+    portals::{self, Portals}, 
+    registry::{Entry, Registry}, 
+    state::{self, CellOverlay, Fog, Marker, Visits}, 
+    Animation, Settings, Signal, Stepper, 
 };
+#[cfg(feature = "terminal")]
+use crate::Error;
 
 mod a_star;
 mod flood;
 mod mouse;
 mod right_hand;
+mod shift;
+mod sources;
+
+/// Factor the intensity of the ghost's glyphs is scaled by, such that it's told apart from the player. 
+const GHOST_INTENSITY: f64 = 0.5;
+
+/// Hues of the lowest and highest `f`-scores marked (see [`FScores`]): blue and red, in Okhsl. 
+const COLD_HUE: f64 = 264.0;
+const HOT_HUE: f64 = 29.0;
 
 /// State for the solve phase. 
 pub struct Phase {
@@ -21,80 +42,772 @@ pub struct Phase {
     pub goal: Node, 
     /// The parent of each visited node. 
     pub parents: NodeBuffer<Option<Node>>, 
+    /// Difficulty of the maze (see [`Difficulty::score`]), measured up front if shown by the HUD. 
+    pub difficulty: Option<f64>, 
+    /// The user racing the solver, if any (see [`Race`](crate::game::Race)). 
+    pub player: Option<Player>, 
+    /// Another player whose progress is shown alongside the user's, drawn dimmer, if any (see
+    /// [`Race::with_ghost`](crate::game::Race::with_ghost) and [`Remote`](crate::net::Remote)). 
+    pub ghost: Option<Player>, 
+    /// Nodes searched from at once, if more than the start (see [`Settings::sources`]). 
+    pub starts: Option<Starts>, 
+    /// Pairs of nodes teleporting to each other, if placed (see [`Settings::portals`]). 
+    pub portals: Option<Portals>, 
+    /// Slippery nodes, if placed (see [`Settings::ice`]). 
+    pub ice: Option<Ice>, 
+    /// Elevation making uphill moves cost more, if raised (see [`Settings::elevation`]). 
+    pub elevation: Option<Elevation>, 
+    /// `f`-scores of the nodes queued by A*, if shown (see [`Settings::f_scores`]). 
+    pub f_scores: Option<FScores>, 
+    /// Edges closed or opened by the maze shifting in the current timestep, if it shifts (see
+    /// [`Settings::shift_every`]), for the solver to [`replan`] around. 
+    pub changed: Vec<Edge>, 
+}
+
+/// Several nodes searched from at once, each by its own instance of the solver, toward the same goal (see
+/// [`run`]). Nodes are drawn in the colours of the search that visited them. 
+pub struct Starts {
+    /// Nodes searched from, the first being the start until another reaches the goal. 
+    pub nodes: Vec<Node>, 
+    /// Index of the node whose search visited each node. 
+    pub owners: NodeBuffer<Option<usize>>, 
+    /// Index of the node whose search is being stepped. 
+    pub current: usize, 
+    /// Colours of each search, with hues shifted evenly around the colour wheel. 
+    colours: Vec<Lut>, 
+}
+
+impl Starts {
+    /// Sets up searches from given nodes, deriving the colours of each from those of the state. 
+    fn new(nodes: Vec<Node>, maze: &Maze, colours: &Lut, settings: &Settings) -> Starts {
+        let colours = (0..nodes.len())
+            .map(|i| {
+                let hue_shift = settings.hue_shift + 360.0 * i as f64 / nodes.len() as f64;
+                colours.palette.into_lut(&Settings{ hue_shift, ..settings.clone() })
+            })
+            .collect();
+        Starts {
+            owners: NodeBuffer::new(maze), 
+            nodes, 
+            current: 0, 
+            colours, 
+        }
+    }
+}
+
+/// The `f`-scores of the nodes queued by A* (i.e. the length of the shortest path to the goal through each, as
+/// estimated), which are marked by dots from blue for the lowest to red for the highest. This shows how the
+/// heuristic draws the search toward the goal. 
+pub struct FScores {
+    /// `f`-score of each queued node. 
+    pub scores: NodeBuffer<Option<usize>>, 
+    /// Lowest `f`-score possible and highest queued yet, which the colours span. 
+    pub range: (usize, usize), 
+    /// Lightness of the dots, contrasting with the background. 
+    lightness: f64, 
+}
+
+impl FScores {
+    fn new(maze: &Maze, settings: &Settings) -> FScores {
+        FScores {
+            scores: NodeBuffer::new(maze), 
+            range: (0, 0), 
+            lightness: match settings.light {
+                true => 0.45, 
+                false => 0.65, 
+            }, 
+        }
+    }
+
+    /// Gets the dot drawn upon a queued node, coloured by its score within the range. 
+    fn overlay(&self, node: Node) -> Option<CellOverlay> {
+        let score = self.scores[node]?;
+        let (low, high) = self.range;
+        let heat = match high > low {
+            true => score.saturating_sub(low) as f64 / (high - low) as f64, 
+            false => 0.0, 
+        };
+        Some(CellOverlay {
+            glyph: '•', 
+            colour: Hsl {
+                hue: COLD_HUE + (HOT_HUE - COLD_HUE) * heat.min(1.0), 
+                saturation: 1.0, 
+                lightness: self.lightness, 
+            }, 
+            intensity: 1.0, 
+        })
+    }
+}
+
+impl Phase {
+    /// Traces the path found from the start to the goal through the parents, if the goal has been reached. 
+    /// When searching from several starts, the path is traced to whichever start it leads to. Nodes slid
+    /// across on ice are included, such that only portals link nodes that aren't adjacent. 
+    pub fn solution(&self) -> Option<Solution> {
+        let mut node = self.goal;
+        let mut path = vec![node];
+        // searches from several starts may overwrite each other's parents, which could form a cycle
+        let nodes = self.parents.iter().count();
+
+        while !self.is_start(node) {
+            let parent = self.parents[node]?;
+            if path.len() > nodes {
+                return None
+            }
+            if !self.is_portal_pair(parent, node) {
+                path.extend(between(node, parent));
+            }
+            path.push(parent);
+            node = parent;
+        }
+        path.reverse();
+        Some(Solution {
+            start: node, 
+            goal: self.goal, 
+            path, 
+        })
+    }
+
+    /// Gets the neighbours a solver may move to from a node: those through open edges that aren't one-way
+    /// against the move (see [`Maze::passable_neighbours`]), and its twin if it's a portal. Moves onto ice
+    /// lead to wherever the slide ends instead (see [`Ice::slide`]). 
+    pub fn open_neighbours(&self, maze: &Maze, node: Node) -> Neighbours<false> {
+        let mut neighbours = maze.passable_neighbours(node);
+        if let Some(ice) = &self.ice {
+            neighbours = neighbours.map_edges(|edge| {
+                let to = ice.slide(maze, edge.to, edge.direction, self.goal).pop().unwrap_or(edge.to);
+                Edge{ to, ..edge }
+            });
+        }
+        if let Some(edge) = self.portals.as_ref().and_then(|portals| portals.edge(node)) {
+            neighbours.push(edge);
+        }
+        neighbours
+    }
+
+    /// Gets the cost of a move, which is higher uphill if the maze has elevation. Only cost-aware solvers
+    /// (i.e. A*) weigh moves up, while the others count every move alike. 
+    pub fn cost(&self, edge: Edge) -> usize {
+        self.elevation.as_ref().map_or(1, |elevation| elevation.cost(edge.from, edge.to))
+    }
+
+    /// Finds the shortest way from a node to the goal by breadth-first search, moving as solvers do (see
+    /// [`Phase::open_neighbours`]), with the nodes slid across on ice included. The search itself is left
+    /// untouched, such that this may be called at any time while solving (e.g. for hints, see
+    /// [`Player::hint`]). 
+    pub fn path_to_goal(&self, maze: &Maze, from: Node) -> Option<Vec<Node>> {
+        let mut parents: NodeBuffer<Option<Node>> = NodeBuffer::new(maze);
+        let mut queue = VecDeque::from([from]);
+        parents[from] = Some(from);
+
+        while let Some(node) = queue.pop_front() {
+            if node == self.goal {
+                break
+            }
+            for edge in self.open_neighbours(maze, node) {
+                if parents[edge.to].is_none() {
+                    parents[edge.to] = Some(node);
+                    queue.push_back(edge.to);
+                }
+            }
+        }
+        let mut node = self.goal;
+        let mut path = vec![node];
+        while node != from {
+            let parent = parents[node]?;
+            if !self.is_portal_pair(parent, node) {
+                path.extend(between(node, parent));
+            }
+            path.push(parent);
+            node = parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Whether two nodes are twin portals, such that moving between them teleports. 
+    fn is_portal_pair(&self, a: Node, b: Node) -> bool {
+        self.portals.as_ref().is_some_and(|portals| portals.twin(a) == Some(b))
+    }
+
+    /// Whether a node is searched from. 
+    fn is_start(&self, node: Node) -> bool {
+        node == self.start || self.starts.as_ref().is_some_and(|starts| starts.nodes.contains(&node))
+    }
 }
 
 impl state::Phase for Phase {
+    const NAME: &str = "solve";
+
+    fn goal(&self) -> Option<Node> {
+        Some(self.goal)
+    }
+
     fn special(&self, node: Node) -> bool {
-        [self.start, self.goal].contains(&node)
+        self.is_start(node) || node == self.goal
+    }
+
+    fn marker(&self, node: Node) -> Option<Marker> {
+        match (self.is_start(node), node == self.goal) {
+            (true, _) => Some(Marker::Start), 
+            (false, true) => Some(Marker::Goal), 
+            (false, false) => None, 
+        }
+    }
+
+    fn focus(&self) -> Option<Node> {
+        self.player.as_ref().map(|player| player.position)
+    }
+
+    fn overlay(&self, node: Node) -> Option<CellOverlay> {
+        // the player is drawn above the ghost, and both above the portal or the ice they're at
+        let player = self.player.as_ref().and_then(|player| player.overlay(node));
+        let ghost = || {
+            let overlay = self.ghost.as_ref()?.overlay(node)?;
+            Some(CellOverlay{ intensity: GHOST_INTENSITY * overlay.intensity, ..overlay })
+        };
+        player
+            .or_else(ghost)
+            .or_else(|| self.portals.as_ref()?.overlay(node))
+            .or_else(|| self.ice.as_ref()?.overlay(node))
+            .or_else(|| self.f_scores.as_ref()?.overlay(node))
+    }
+
+    fn colours(&self, node: Node) -> Option<&Lut> {
+        let owner = self.starts.as_ref().and_then(|starts| starts.owners[node]);
+        match &self.elevation {
+            // shaded by elevation upon the colours of the state, or of the search that visited the node
+            Some(elevation) => elevation.colours(owner.map_or(0, |owner| owner + 1), node), 
+            None => self.starts.as_ref()?.colours.get(owner?), 
+        }
+    }
+
+    fn visited(&mut self, node: Node) {
+        if let Some(starts) = &mut self.starts {
+            starts.owners[node] = Some(starts.current);
+        }
+    }
+
+    fn status(state: &State, frontier: Option<usize>) -> Option<String> {
+        let percent = 100 * state.visited_count / (state.maze.width * state.maze.height);
+        let mut status = format!("visited: {} ({percent}%)", state.visited_count);
+        if let Some(frontier) = frontier {
+            // formatting into a string can't fail
+            let _ = write!(status, " / path candidates: {frontier}");
+        }
+        if let Some(difficulty) = state.difficulty {
+            let _ = write!(status, " / difficulty: {difficulty:.0}");
+        }
+        Some(status)
     }
 }
 
 pub type State = state::State<Phase>;
 
+/// A path through a maze from start to goal, as found by a solver. 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Solution {
+    pub start: Node, 
+    pub goal: Node, 
+    /// Nodes along the path, including the start and the goal. 
+    pub path: Vec<Node>, 
+}
+
+impl Solution {
+    /// Gets the moves along the path as a string of compass directions, e.g. `NNEESW`, with `T` for moves
+    /// through a portal (see [`Portals`]). 
+    pub fn moves(&self) -> String {
+        self.path
+            .windows(2)
+            .map(|pair| {
+                let (Node(x, y), Node(nx, ny)) = (pair[0], pair[1]);
+                if pair[0].manhattan(pair[1]) > 1 {
+                    return 'T'
+                }
+                match (nx.cmp(&x), ny.cmp(&y)) {
+                    (_, Ordering::Less) => 'N', 
+                    (_, Ordering::Greater) => 'S', 
+                    (Ordering::Greater, _) => 'E', 
+                    _ => 'W', 
+                }
+            })
+            .collect()
+    }
+}
+
+/// Constructs the state for solving the maze generated in a previous state. 
 pub fn state(previous: generate::State) -> State {
-    let age = NodeBuffer::new(&previous.maze);
-    let parents = NodeBuffer::new(&previous.maze);
-    let gradient = {
-        let base = previous.colours.palette.young;
-        let unvisited = base
-            .with_s(0.7)
-            .with_l(0.17);
-        let special = base
-            .shift_h(60.0)
-            .with_l(0.7);
-        Palette::from_base(base)
-            .with_unvisited(unvisited)
-            .with_special(special)
-            .into_lut(&previous.settings)
-    };
-    let (top_left, bottom_right) = previous.maze.bounds();
-    let start = find_dead_end(top_left, &previous.maze);
-    let goal = find_dead_end(bottom_right, &previous.maze);
-
-    State {
-        maze: previous.maze, 
-        settings: previous.settings, 
-        age, 
+    let generate::State{ maze, settings, rng, mask, .. } = previous;
+    new_state(maze, settings, rng, mask)
+}
+
+/// Constructs the state for solving a given maze straight away (e.g. one loaded from a file), without a
+/// generate phase before it. The random number generator is seeded from the settings. 
+pub fn given(maze: Maze, settings: Settings) -> State {
+    let rng = StdRng::seed_from_u64(settings.seed);
+    new_state(maze, settings, rng, None)
+}
+
+/// Constructs the state for solving a maze, placing its endpoints and whatever else the settings call for. 
+fn new_state(mut maze: Maze, settings: Settings, mut rng: StdRng, mask: Option<NodeBits>) -> State {
+    place_endpoints(&mut maze, &settings, &mut rng);
+    place_one_way(&mut maze, &settings, &mut rng);
+    let visited_at = Visits::new(&maze);
+    let parents = NodeBuffer::new(&maze);
+    let colours = settings.theme.solve.into_lut(&settings);
+    let (start, goal) = endpoints(&maze);
+    // only shown by the HUD, so it isn't measured otherwise
+    let difficulty = settings.hud
+        .then(|| shortest_path(&maze, start, goal))
+        .flatten()
+        .map(|solution| Difficulty::new(&maze, &solution).score());
+
+    let fog = settings.fog.map(|radius| Fog::new(&maze, radius));
+    let starts = settings.sources
+        .as_ref()
+        .map(|sources| sources.nodes(&maze, goal))
+        .filter(|nodes| nodes.len() > 1)
+        .map(|nodes| Starts::new(nodes, &maze, &colours, &settings));
+    let start = starts.as_ref().map_or(start, |starts| starts.nodes[0]);
+    let elevation = settings.elevation.then(|| {
+        let luts = iter::once(&colours).chain(starts.iter().flat_map(|starts| &starts.colours));
+        Elevation::new(&maze, &mut rng, luts)
+    });
+    let f_scores = settings.f_scores.then(|| FScores::new(&maze, &settings));
+
+    let mut state = State {
+        maze, 
+        settings, 
+        rng, 
+        visited_at, 
         visited_count: 0, 
-        colours: gradient, 
+        time: 0, 
+        colours, 
+        active: Vec::new(), 
+        fog, 
+        mask, 
         phase: Phase {
             start, 
             goal, 
             parents, 
+            difficulty, 
+            player: None, 
+            ghost: None, 
+            starts, 
+            portals: None, 
+            ice: None, 
+            elevation, 
+            f_scores, 
+            changed: Vec::new(), 
+        }, 
+    };
+    state.phase.portals = portals::place(&mut state);
+    state.phase.ice = ice::place(&mut state);
+    state
+}
+
+/// Nodes solved from at once, as given by `--starts`. 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Sources {
+    /// The dead ends nearest to each corner, other than the goal. 
+    Corners, 
+    /// Nodes at given coordinates, moved within the maze if beyond it. 
+    At(Vec<Node>), 
+}
+
+impl Sources {
+    /// Gets the distinct nodes solved from in a maze, other than the goal. 
+    pub fn nodes(&self, maze: &Maze, goal: Node) -> Vec<Node> {
+        let (top_left, bottom_right) = maze.bounds();
+        let candidates = match self {
+            Sources::Corners => [top_left, Node(bottom_right.0, 0), Node(0, bottom_right.1), bottom_right]
+                .map(|corner| find_dead_end(corner, maze))
+                .to_vec(), 
+            Sources::At(nodes) => nodes
+                .iter()
+                .map(|&Node(x, y)| Node(x.min(bottom_right.0), y.min(bottom_right.1)))
+                .collect(), 
+        };
+        let mut nodes: Vec<Node> = Vec::new();
+        for node in candidates {
+            if node != goal && !nodes.contains(&node) {
+                nodes.push(node);
+            }
+        }
+        nodes
+    }
+}
+
+impl FromStr for Sources {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Sources, String> {
+        if s == "corners" {
+            return Ok(Sources::Corners)
+        }
+        let node = |pair: &str| {
+            let node = pair
+                .split_once(':')
+                .and_then(|(x, y)| Some(Node(x.parse().ok()?, y.parse().ok()?)));
+            node.ok_or_else(|| format!("expected corners or X:Y pairs separated by commas, got `{pair}`"))
+        };
+        s.split(',').map(node).collect::<Result<_, _>>().map(Sources::At)
+    }
+}
+
+/// How the endpoints of mazes are placed, unless marked in the maze, as given by `--endpoints`. 
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Endpoints {
+    /// The dead ends nearest to the top left and bottom right corners. 
+    #[default]
+    Corners, 
+    /// The two dead ends farthest apart as the crow flies, i.e. by Manhattan distance. 
+    DeadEnds, 
+    /// The ends of the longest path through the maze (see [`Maze::diameter`]), making for the longest
+    /// solution. 
+    #[value(help = "The ends of the longest path through the maze, making for the longest solution")]
+    Longest, 
+    /// Two nodes at random. 
+    Random, 
+}
+
+/// Marks the endpoints placed as given by the settings in a maze, such that they're used by [`endpoints`] from
+/// then on. Endpoints already marked are kept, and those at the corners are left unmarked since they're found
+/// without. 
+fn place_endpoints(maze: &mut Maze, settings: &Settings, rng: &mut impl Rng) {
+    let (start, goal) = match settings.endpoints {
+        Endpoints::Corners => return, 
+        Endpoints::DeadEnds => farthest_dead_ends(maze), 
+        Endpoints::Longest => {
+            let path = maze.diameter();
+            (path[0], path[path.len() - 1])
+        }, 
+        Endpoints::Random => {
+            let start = maze.random_node(rng);
+            let goal = maze.random_node_where(rng, |node| node != start).unwrap_or(start);
+            (start, goal)
         }, 
+    };
+    maze.start.get_or_insert(start);
+    maze.goal.get_or_insert(goal);
+}
+
+/// Makes a share of the open edges in a maze one-way (as given by the settings), each pointing toward the
+/// goal such that it's still reached from every node. 
+fn place_one_way(maze: &mut Maze, settings: &Settings, rng: &mut impl Rng) {
+    let share = settings.one_way;
+    if share <= 0.0 {
+        return
+    }
+    let (_, goal) = endpoints(maze);
+    let distances = maze.distances(goal);
+    let edges: Vec<Edge> = maze.edges_iter().filter(|&e| maze.open[e]).collect();
+    for edge in edges {
+        if !rng.gen_bool(share) {
+            continue
+        }
+        maze.set_one_way(toward_goal(&distances, edge));
+    }
+}
+
+/// Orients an edge toward the goal, given the distance of each node from it (see [`Maze::distances`]). 
+fn toward_goal(distances: &NodeBuffer<Option<usize>>, edge: Edge) -> Edge {
+    // the nodes on either side of an edge differ by one edge in distance from the goal, so one is nearer
+    match distances[edge.to] < distances[edge.from] {
+        true => edge, 
+        false => edge.reverse(), 
+    }
+}
+
+/// Finds the two dead ends of a maze farthest apart by Manhattan distance, or its corners if it has no dead
+/// ends. 
+fn farthest_dead_ends(maze: &Maze) -> (Node, Node) {
+    let dead_ends: Vec<Node> = maze.dead_ends().collect();
+    let Some(&first) = dead_ends.first() else {
+        return maze.bounds()
+    };
+    // the distance is greatest along either diagonal, so the pair farthest apart has the smallest and largest
+    // coordinate along one of them
+    let extremes = |key: &dyn Fn(Node) -> isize| {
+        let min = dead_ends.iter().copied().min_by_key(|&node| key(node)).unwrap_or(first);
+        let max = dead_ends.iter().copied().max_by_key(|&node| key(node)).unwrap_or(first);
+        (min, max)
+    };
+    let diagonal = extremes(&|Node(x, y)| x as isize + y as isize);
+    let antidiagonal = extremes(&|Node(x, y)| x as isize - y as isize);
+    match diagonal.0.manhattan(diagonal.1) >= antidiagonal.0.manhattan(antidiagonal.1) {
+        true => diagonal, 
+        false => antidiagonal, 
     }
 }
 
-#[derive(ValueEnum, Clone, Copy, PartialEq, Eq)]
-pub enum Solver {
-    AStar, 
-    Flood, 
-    Mouse, 
-    RightHand, 
+/// Gets the nodes a maze is solved from and to: those marked in the maze, or otherwise the dead ends
+/// nearest to its top left and bottom right corners. 
+pub fn endpoints(maze: &Maze) -> (Node, Node) {
+    let (top_left, bottom_right) = maze.bounds();
+    let start = maze.start.unwrap_or_else(|| find_dead_end(top_left, maze));
+    let goal = maze.goal.unwrap_or_else(|| find_dead_end(bottom_right, maze));
+    (start, goal)
 }
 
-impl Solver {
-    pub fn run(self, state: &mut State) -> Result<(), Error> {
-        match self {
-            Solver::AStar => a_star::AStar::run(state), 
-            Solver::Flood => flood::Flood::run(state), 
-            Solver::Mouse => mouse::Mouse::run(state), 
-            Solver::RightHand => right_hand::RightHand::run(state), 
+/// Finds the shortest solution between the endpoints of a maze (see [`endpoints`]) without animating a
+/// solver, or `None` if the goal can't be reached. 
+pub fn shortest(maze: &Maze) -> Option<Solution> {
+    let (start, goal) = endpoints(maze);
+    shortest_path(maze, start, goal)
+}
+
+/// Finds the shortest path between two nodes by breadth-first search, respecting one-way edges. 
+fn shortest_path(maze: &Maze, start: Node, goal: Node) -> Option<Solution> {
+    let mut parents: NodeBuffer<Option<Node>> = NodeBuffer::new(maze);
+    let mut queue = VecDeque::from([start]);
+    parents[start] = Some(start);
+
+    while let Some(node) = queue.pop_front() {
+        if node == goal {
+            break
         }
+        for edge in maze.passable_neighbours(node) {
+            if parents[edge.to].is_none() {
+                parents[edge.to] = Some(node);
+                queue.push_back(edge.to);
+            }
+        }
+    }
+    let mut node = goal;
+    let mut path = vec![node];
+    while node != start {
+        node = parents[node]?;
+        path.push(node);
+    }
+    path.reverse();
+    Some(Solution{ start, goal, path })
+}
+
+/// Runs a solver from the start, or from each of several starts at once (see [`Phase::starts`]), animated,
+/// returning the number of timesteps taken. The maze shifts while solving if so set (see
+/// [`Settings::shift_every`]). 
+#[cfg(feature = "terminal")]
+pub fn run(solver: Solver, state: &mut State) -> Result<usize, Error> {
+    match (state.settings.shift_every, &state.starts) {
+        (Some(every), _) => shift::Shifting::new_with(searches(solver, state), every).play(state), 
+        (None, Some(_)) => sources::Searches::new_with(solver, state).play(state), 
+        (None, None) => solver.run(state), 
     }
 }
 
+/// Runs a solver as [`run`] does, without rendering anything. 
+pub fn run_headless(solver: Solver, state: &mut State) -> usize {
+    match (state.settings.shift_every, &state.starts) {
+        (Some(every), _) => shift::Shifting::new_with(searches(solver, state), every).play_headless(state), 
+        (None, Some(_)) => sources::Searches::new_with(solver, state).play_headless(state), 
+        (None, None) => solver.run_headless(state), 
+    }
+}
+
+/// Constructs a solver as [`run`] runs it, to be stepped by the caller. 
+pub fn stepper(solver: Solver, state: &mut State) -> Stepper<Phase> {
+    let searches = searches(solver, state);
+    match state.settings.shift_every {
+        Some(every) => {
+            let mut shifting = shift::Shifting::new_with(searches, every);
+            Box::new(move |state| shifting.step(state))
+        }, 
+        None => searches, 
+    }
+}
+
+/// Constructs a solver searching from the start, or from each of several starts at once. 
+fn searches(solver: Solver, state: &mut State) -> Stepper<Phase> {
+    match state.starts {
+        Some(_) => {
+            let mut sources = sources::Searches::new_with(solver, state);
+            Box::new(move |state| sources.step(state))
+        }, 
+        None => solver.stepper(state), 
+    }
+}
+
+/// Runs a solver to the end from a given node, as a search of its own, returning the solution it finds (if
+/// any). The search in the state is left as it was, and nothing is reported to the hooks, such that this may
+/// be called at any time while solving (e.g. by the autopilot, see [`Race`](crate::game::Race)). Nodes
+/// visited by the search in the state don't hinder the solver. 
+pub fn search_from(solver: Solver, state: &mut State, from: Node) -> Option<Solution> {
+    // bounded, since some solvers (e.g. the mouse) may wander for a long time
+    let max_steps = 64 * state.maze.width * state.maze.height;
+    let mut scratch = Scratch::new(&state.maze, from);
+    scratch.swap(state);
+    let mut step = solver.stepper(state);
+    let done = (0..max_steps).any(|_| matches!(step(state), Signal::Done));
+    let solution = done.then(|| state.solution()).flatten();
+    scratch.swap(state);
+    solution
+}
+
+/// Parts of a state making up a search, swapped out for those of a search of its own (see [`search_from`]). 
+struct Scratch {
+    start: Node, 
+    parents: NodeBuffer<Option<Node>>, 
+    visited_at: Visits, 
+    visited_count: usize, 
+    active: Vec<Node>, 
+    starts: Option<Starts>, 
+    fog: Option<Fog>, 
+    hooks: Hooks, 
+}
+
+impl Scratch {
+    /// Constructs a search of a maze from a node, with nothing visited yet. 
+    fn new(maze: &Maze, start: Node) -> Scratch {
+        Scratch {
+            start, 
+            parents: NodeBuffer::new(maze), 
+            visited_at: Visits::new(maze), 
+            visited_count: 0, 
+            active: Vec::new(), 
+            starts: None, 
+            fog: None, 
+            hooks: Hooks::default(), 
+        }
+    }
+
+    /// Swaps the search with that of a state, such that swapping twice restores the state. 
+    fn swap(&mut self, state: &mut State) {
+        mem::swap(&mut self.start, &mut state.phase.start);
+        mem::swap(&mut self.parents, &mut state.phase.parents);
+        mem::swap(&mut self.visited_at, &mut state.visited_at);
+        mem::swap(&mut self.visited_count, &mut state.visited_count);
+        mem::swap(&mut self.active, &mut state.active);
+        mem::swap(&mut self.starts, &mut state.phase.starts);
+        mem::swap(&mut self.fog, &mut state.fog);
+        mem::swap(&mut self.hooks, &mut state.settings.hooks);
+    }
+}
+
+/// Forgets the parts of a search cut off from its start by the edges closed as the maze shifted in the
+/// current timestep (see [`Phase::changed`]), such that they're searched again. Solvers call this before each
+/// step, and must re-plan if it returns true, i.e. if the maze has changed (e.g. by queueing the [`frontier`]
+/// anew, or by starting over if they were cut off). 
+pub fn replan(state: &mut State) -> bool {
+    if state.changed.is_empty() {
+        return false
+    }
+    let closed: Vec<Edge> = state.changed.iter().copied().filter(|&e| !state.maze.open[e]).collect();
+    // nodes reached from their parents across a closed edge, which may have slid across it on ice
+    let cut: NodeBits = state.maze
+        .nodes_iter()
+        .filter(|&node| {
+            let Some(parent) = state.parents[node] else {
+                return false
+            };
+            !state.is_start(node)
+                && !state.is_portal_pair(parent, node)
+                && closed.iter().any(|edge| crosses(parent, node, *edge))
+        })
+        .fold(NodeBits::new(&state.maze), |mut cut, node| {
+            cut.set(node, true);
+            cut
+        });
+    prune(state, &cut);
+    true
+}
+
+/// Unvisits the nodes cut off from their parents, and all nodes whose parents lead back to them, forgetting
+/// their parents. 
+fn prune(state: &mut State, cut: &NodeBits) {
+    // whether each node's parents lead back to a cut node, found once per node
+    let mut pruned: NodeBuffer<Option<bool>> = NodeBuffer::new(&state.maze);
+    let nodes: Vec<Node> = state.maze.nodes_iter().collect();
+    for &node in &nodes {
+        let mut chain = Vec::new();
+        let mut head = node;
+        let verdict = loop {
+            if let Some(verdict) = pruned[head] {
+                break verdict
+            }
+            if cut[head] {
+                break true
+            }
+            // parents may form a cycle where searches from several starts overwrote each other's
+            let parent = state.parents[head].filter(|_| !state.is_start(head) && chain.len() < nodes.len());
+            let Some(parent) = parent else {
+                break false
+            };
+            chain.push(head);
+            head = parent;
+        };
+        pruned[head] = Some(verdict);
+        for node in chain {
+            pruned[node] = Some(verdict);
+        }
+    }
+    for node in nodes {
+        if pruned[node] == Some(true) {
+            state.unvisit(node);
+            state.parents[node] = None;
+        }
+    }
+}
+
+/// Gets the nodes strictly between two nodes in the same row or column, from the first toward the second. 
+fn between(from: Node, to: Node) -> Vec<Node> {
+    let (Node(x, y), Node(tx, ty)) = (from, to);
+    match (x == tx, y == ty) {
+        (true, _) if y < ty => (y + 1..ty).map(|y| Node(x, y)).collect(), 
+        (true, _) => (ty + 1..y).rev().map(|y| Node(x, y)).collect(), 
+        (_, true) if x < tx => (x + 1..tx).map(|x| Node(x, y)).collect(), 
+        (_, true) => (tx + 1..x).rev().map(|x| Node(x, y)).collect(), 
+        _ => Vec::new(), 
+    }
+}
+
+/// Whether moving between two nodes in a straight line, e.g. by sliding on ice, crosses an edge. 
+fn crosses(from: Node, to: Node, edge: Edge) -> bool {
+    let on_line = |node: Node| node == from || node == to || between(from, to).contains(&node);
+    on_line(edge.from) && on_line(edge.to) && from != to
+}
+
+/// Gets the edges from visited nodes to the unvisited nodes a solver may move to from them, i.e. the frontier
+/// of a search re-planning after the maze shifted (see [`replan`]). 
+pub fn frontier(state: &State) -> Vec<Edge> {
+    state.maze
+        .nodes_iter()
+        .filter(|&node| state.is_visited(node))
+        .flat_map(|node| state.phase.open_neighbours(&state.maze, node))
+        .filter(|edge| !state.is_visited(edge.to))
+        .collect()
+}
+
+/// A solver registered in [`SOLVERS`]. 
+pub type Solver = Entry<Phase>;
+
+/// All solvers selectable by name. Additional solvers may be registered at runtime. 
+pub static SOLVERS: LazyLock<Registry<Phase>> = LazyLock::new(|| Registry::new([
+    Solver::new::<a_star::AStar>("a-star"), 
+    Solver::new::<flood::Flood>("flood"), 
+    Solver::new::<mouse::Mouse>("mouse"), 
+    Solver::new::<right_hand::RightHand>("right-hand"), 
+]));
+
+/// Finds the dead end nearest to a node, or the node itself if no dead end can be reached from it. 
 fn find_dead_end(from: Node, maze: &Maze) -> Node {
     let mut queue = VecDeque::from([Edge::identity(from)]);
+    let mut visited = NodeBits::new(maze);
+    visited.set(from, true);
 
     while let Some(head) = queue.pop_front() {
         let neighbours = maze.open_neighbours(head.to);
 
         if neighbours.len() == 1 {
             return head.to
-        } else {
-            queue.extend(neighbours.into_iter());
+        }
+        for edge in neighbours {
+            if !visited[edge.to] {
+                visited.set(edge.to, true);
+                queue.push_back(edge);
+            }
         }
     }
-    return from
+    from
 }
@@ -1,3 +1,4 @@
+use rand::Rng;
 use crate::{maze::Node, Animation, Signal};
 use super::{State, Phase};
 
@@ -16,15 +17,26 @@ impl Animation for Mouse {
     }
 
     fn step(&mut self, state: &mut State) -> Signal {
+		// cut off from the start as the maze shifted, the mouse starts over
+		if super::replan(state) && self.head != state.start && state.parents[self.head].is_none() {
+			self.head = state.start;
+		}
 		state.visit(self.head);
 
     	if self.head == state.goal {
     		return Signal::Done
     	}
-    	
-		let edge = state.maze
-			.open_neighbours(self.head)
-			.choose()
+
+		// losing its way, the mouse starts over. the chance isn't drawn unless given, to keep runs reproducible
+		let restart = state.settings.options.mouse_restart.unwrap_or(0.0);
+		if restart > 0.0 && state.rng.gen_bool(restart) {
+			self.head = state.start;
+			return Signal::Continue
+		}
+
+		let edge = state.phase
+			.open_neighbours(&state.maze, self.head)
+			.choose(&mut state.rng)
 			.expect("There are no isolated nodes");
 		let next = edge.to;
 
@@ -33,4 +45,8 @@ impl Animation for Mouse {
 		self.head = next;
 		Signal::Continue
     }
+
+    fn heads(&self) -> Vec<Node> {
+        vec![self.head]
+    }
 }
@@ -20,29 +20,51 @@ impl Animation for RightHand {
     }
 
     fn step(&mut self, state: &mut State) -> Signal {
+        // cut off from the start as the maze shifted, the walls are followed from the start again
+        if super::replan(state) && self.head != state.start && state.parents[self.head].is_none() {
+            self.head = state.start;
+            self.direction = Direction::North;
+        }
         state.visit(self.head);
 
         if self.head == state.goal {
             return Signal::Done
         }
 
-        let next = state.maze
-            .edge(self.head, self.direction)
-            .filter(|&e| state.maze.open[e]);
+        // entering a portal moves straight on to its twin, still facing the same way. following the walls
+        // through portals may go round in circles though, so each is only taken to where it hasn't been yet
+        let twin = state.portals.as_ref().and_then(|portals| portals.twin(self.head));
+        if let Some(twin) = twin && !state.is_visited(twin) {
+            state.parents[twin].get_or_insert(self.head);
+            self.head = twin;
+            return Signal::Continue
+        }
 
-        match next {
-            Some(e) => {
-                self.head = e.to;
-                self.direction = e.direction.clockwise();
+        // turn anti-clockwise until facing an open edge that isn't one-way against us
+        let e = loop {
+            let next = state.maze
+                .edge(self.head, self.direction)
+                .filter(|&e| state.maze.is_passable(e));
 
-                // we have to take care not to introduce a loop
-                state.parents[e.to].get_or_insert(e.from);
-                Signal::Continue
+            match next {
+                Some(e) => break e, 
+                None => self.direction = self.direction.anti_clockwise(), 
             }
-            None => {
-                self.direction = self.direction.anti_clockwise();
-                self.step(state)
-            }
-        }
+        };
+        // ice only carries along corridors, where following the walls goes straight on anyway
+        let slid = state.ice
+            .as_ref()
+            .and_then(|ice| ice.slide(&state.maze, e.to, e.direction, state.goal).pop());
+        let to = slid.unwrap_or(e.to);
+        self.head = to;
+        self.direction = e.direction.clockwise();
+
+        // we have to take care not to introduce a loop
+        state.parents[to].get_or_insert(e.from);
+        Signal::Continue
+    }
+
+    fn heads(&self) -> Vec<Node> {
+        vec![self.head]
     }
 }
@@ -0,0 +1,90 @@
+use rand::seq::IteratorRandom;
+use crate::{
+    maze::{Edge, NodeBits}, 
+    Animation, Signal, Stepper, 
+};
+use super::{flood::Flood, toward_goal, Phase, State};
+
+/// Timesteps between shifts of the maze, unless given by the settings. 
+const EVERY: u32 = 10;
+
+/// Shifts the maze every so many timesteps while a solver runs, closing an open edge and opening a wall in its
+/// place such that every node can still be reached. The edges changed are noted in [`Phase::changed`] for the
+/// timestep, such that the solver may re-plan (see [`replan`](super::replan)). 
+pub struct Shifting {
+    /// Solver running upon the shifting maze. 
+    solver: Stepper<Phase>, 
+    /// Timesteps between shifts. 
+    every: u32, 
+    /// Timesteps taken so far. 
+    steps: u32, 
+}
+
+impl Shifting {
+    /// Constructs a solver running upon a maze shifting every given number of timesteps. 
+    pub fn new_with(solver: Stepper<Phase>, every: u32) -> Shifting {
+        Shifting {
+            solver, 
+            every, 
+            steps: 0, 
+        }
+    }
+}
+
+impl Animation for Shifting {
+    type Phase = Phase;
+
+    /// Constructs a breadth-first search upon the shifting maze. 
+    fn new(state: &mut State) -> Shifting {
+        let every = state.settings.shift_every.unwrap_or(EVERY);
+        Shifting::new_with(Flood::stepper(state), every)
+    }
+
+    fn step(&mut self, state: &mut State) -> Signal {
+        self.steps += 1;
+        if self.steps.is_multiple_of(self.every) {
+            shift(state);
+        }
+        let signal = (self.solver)(state);
+        state.changed.clear();
+        signal
+    }
+}
+
+/// Closes an open edge of the maze at random, and opens a wall reconnecting the two parts it splits the maze
+/// into, such that a perfect maze stays perfect. One-way edges are turned toward the goal again. 
+fn shift(state: &mut State) {
+    let Some(closed) = state.maze.edges_iter().filter(|&e| state.maze.open[e]).choose(&mut state.rng) else {
+        return
+    };
+    state.close(closed);
+
+    // the nodes still reached from one side of the closed edge, which are cut off from the other side
+    let mut side = NodeBits::new(&state.maze);
+    let mut stack = vec![closed.from];
+    side.set(closed.from, true);
+    while let Some(node) = stack.pop() {
+        for edge in state.maze.open_neighbours(node) {
+            if !side[edge.to] {
+                side.set(edge.to, true);
+                stack.push(edge.to);
+            }
+        }
+    }
+    let opened = state.maze
+        .edges_iter()
+        .filter(|&e| !state.maze.open[e] && e != closed && side[e.from] != side[e.to])
+        .choose(&mut state.rng)
+        .unwrap_or(closed);
+    state.open(opened);
+    if opened == closed {
+        return
+    }
+    state.changed = vec![closed, opened];
+
+    let distances = state.maze.distances(state.goal);
+    let one_way: Vec<Edge> = state.maze.edges_iter().filter(|&e| state.maze.one_way(e).is_some()).collect();
+    for edge in one_way {
+        state.maze.set_one_way(toward_goal(&distances, edge));
+    }
+}
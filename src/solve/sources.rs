@@ -0,0 +1,74 @@
+use crate::{maze::Node, Animation, Signal, Stepper};
+use super::{flood::Flood, Phase, Solver, State};
+
+/// Searches from several starts at once, stepping each search in turn until one of them reaches the goal. 
+/// The searches share the nodes visited, so each claims the part of the maze it reaches first. 
+pub struct Searches {
+    /// Search from each start, until it's done. 
+    searches: Vec<Option<Stepper<Phase>>>, 
+}
+
+impl Searches {
+    /// Constructs searches by a solver from each start. 
+    pub fn new_with(solver: Solver, state: &mut State) -> Searches {
+        Searches::with(state, |state| solver.stepper(state))
+    }
+
+    /// Constructs a search from each start, with the start (and owner of the nodes visited) set to that of
+    /// the search. 
+    fn with(state: &mut State, mut search: impl FnMut(&mut State) -> Stepper<Phase>) -> Searches {
+        let nodes = state.starts.as_ref().map_or_else(|| vec![state.start], |starts| starts.nodes.clone());
+        let searches = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, &start)| {
+                select(state, i, start);
+                Some(search(state))
+            })
+            .collect();
+        select(state, 0, nodes[0]);
+        Searches{ searches }
+    }
+}
+
+impl Animation for Searches {
+    type Phase = Phase;
+
+    /// Constructs breadth-first searches from each start. 
+    fn new(state: &mut State) -> Searches {
+        Searches::with(state, Flood::stepper)
+    }
+
+    fn step(&mut self, state: &mut State) -> Signal {
+        for (i, search) in self.searches.iter_mut().enumerate() {
+            let Some(step) = search else {
+                continue
+            };
+            let start = state.starts.as_ref().map_or(state.start, |starts| starts.nodes[i]);
+            select(state, i, start);
+            if let Signal::Done = step(state) {
+                *search = None;
+            }
+            // the search reaching the goal wins. its path may lead through nodes another search got to first, so
+            // the start is whichever the solution is traced back to
+            if state.is_visited(state.goal) {
+                if let Some(solution) = state.solution() {
+                    state.start = solution.start;
+                }
+                return Signal::Done
+            }
+        }
+        match self.searches.iter().all(Option::is_none) {
+            true => Signal::Done, 
+            false => Signal::Continue, 
+        }
+    }
+}
+
+/// Sets the start of the search about to be stepped, such that the nodes it visits are noted as its own. 
+fn select(state: &mut State, index: usize, start: Node) {
+    state.start = start;
+    if let Some(starts) = &mut state.starts {
+        starts.current = index;
+    }
+}
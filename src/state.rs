@@ -1,8 +1,11 @@
 use std::{fmt, ops::{Deref, DerefMut}};
-use crossterm::style::{StyledContent, Stylize};
+use rand::rngs::StdRng;
 use crate::{
-    colour::Lut, 
-    maze::{Direction, Maze, Node, NodeBuffer}, 
+    colour::{Colour, Hsl, Lut, Paint, Painted, Swatch}, 
+    events::Event, 
+    maze::{Direction, Edge, Maze, Node, NodeBits, NodeBuffer}, 
+    render::{padded, Ansi, Layout, Renderer}, 
+    viewport::{braille_bit, Minimap, Viewport}, 
     Settings, 
 };
 
@@ -12,12 +15,26 @@ pub struct State<T> {
     pub maze: Maze, 
     /// Settings used. 
     pub settings: Settings, 
-    /// The age of each visited [`Node`]. This is set by [`State::visit`] and incremented by [`State::step`]. 
-    pub age: NodeBuffer<Option<u8>>, 
+    /// Source of randomness for the algorithms, seeded by [`Settings::seed`] such that runs are reproducible. 
+    pub rng: StdRng, 
+    /// The timestep at which each [`Node`] was visited, as set by [`State::visit`]. Ages are derived from
+    /// this when rendering (see [`State::age`]). 
+    pub visited_at: Visits, 
     /// Current number of visited nodes. 
     pub visited_count: usize, 
+    /// Current timestep, incremented by [`State::step`]. 
+    pub time: i64, 
     /// Node colour lookup. 
     pub colours: Lut, 
+    /// Nodes drawn as the heads of the running algorithm, as set while animating if enabled (see
+    /// [`Settings::mark_heads`]). 
+    pub active: Vec<Node>, 
+    /// Fog of war hiding the maze but around the nodes being visited, if enabled for the phase (see
+    /// [`Settings::fog`]). 
+    pub fog: Option<Fog>, 
+    /// Nodes drawn if the maze is masked to a shape (e.g. the letters of the [`title`](crate::title)), with all
+    /// others drawn as walls. 
+    pub mask: Option<NodeBits>, 
     /// State specific to each [`Phase`]. 
     pub phase: T, 
 }
@@ -25,8 +42,19 @@ pub struct State<T> {
 impl<T: Phase> State<T> {
     /// Marks the given node as visited, with custom age. 
     pub fn set_age(&mut self, node: Node, age: u8) {
-        if let None = self.age[node].replace(age) {
+        // round up such that the node is at least as old as requested
+        let elapsed = (age as u32 * self.settings.trail).div_ceil(u8::MAX as u32) as i64;
+        if let Some(fog) = &mut self.fog {
+            fog.reveal(&self.maze, node);
+        }
+        if self.visited_at.set(node, self.time - elapsed) {
             self.visited_count += 1;
+            self.phase.visited(node);
+            self.emit(Event::NodeVisited(node));
+
+            if self.phase.goal() == Some(node) {
+                self.emit(Event::GoalReached(node));
+            }
         }
     }
     
@@ -37,14 +65,32 @@ impl<T: Phase> State<T> {
 
     /// Unmarks the given node as visited. 
     pub fn unvisit(&mut self, node: Node) {
-        if let Some(_) = self.age[node].take() {
+        if self.visited_at.unset(node) {
             self.visited_count -= 1;
         }
     }
 
+    /// Opens an edge of the maze. 
+    pub fn open(&mut self, edge: Edge) {
+        self.maze.open.set(edge, true);
+        self.emit(Event::EdgeOpened(edge));
+    }
+
+    /// Closes an edge of the maze, which is no longer one-way once opened again. 
+    pub fn close(&mut self, edge: Edge) {
+        self.maze.open.set(edge, false);
+        self.maze.one_way.set(edge, false);
+        self.emit(Event::EdgeClosed(edge));
+    }
+
+    /// Reports an event to the hooks in the settings. 
+    pub fn emit(&self, event: Event) {
+        self.settings.hooks.emit(event);
+    }
+
     /// Returns whether the given node is visited. 
     pub fn is_visited(&self, node: Node) -> bool {
-        self.age[node].is_some()
+        self.visited_at.is_visited(node)
     }
 
     /// Whether all nodes of the maze have been visited. 
@@ -52,21 +98,274 @@ impl<T: Phase> State<T> {
         self.visited_count == self.maze.width * self.maze.height
     }
 
-    /// Increments the ages of all visited nodes. 
-    ///
-    /// This is a bit of a hack given that it iterates over all nodes each timestep, but I can't figure out a
-    /// better way to handle aging cells without overflow issues. 
+    /// Advances to the next timestep, aging all visited nodes. 
     pub fn step(&mut self) {
-        for age in self.age.iter_mut().flatten() {
-            *age = age.saturating_add(1);
+        self.time += 1;
+        if let Some(fog) = &mut self.fog {
+            fog.visible.clear();
+        }
+    }
+
+    /// Gets the age of a node (if visited), which reaches the maximum age [`Settings::trail`] timesteps after
+    /// it was visited. 
+    pub fn age(&self, node: Node) -> Option<u8> {
+        let visited_at = self.visited_at.get(node, self.time)?;
+        let age = (self.time - visited_at) * u8::MAX as i64 / self.settings.trail as i64;
+        Some(age.clamp(0, u8::MAX as i64) as u8)
+    }
+
+    /// Rotates the hue of all colours by the given number of degrees, regenerating the LUT from its palette. 
+    pub fn shift_hue(&mut self, degrees: f64) {
+        self.settings.hue_shift += degrees;
+        self.colours = self.colours.palette.into_lut(&self.settings);
+    }
+
+    /// Dimensions of the maze as laid out on screen, in cells. Each node and each edge occupies one cell, 
+    /// with gaps filling the spaces between diagonally adjacent nodes. 
+    pub fn grid_size(&self) -> (usize, usize) {
+        (2 * self.maze.width - 1, 2 * self.maze.height - 1)
+    }
+
+    /// Gets the node the running algorithm is at, which a scrolled viewport follows (see [`Viewport`]): the
+    /// first of its heads if marked, else that given by the phase, else the node visited last. 
+    pub fn focus(&self) -> Node {
+        let latest = || {
+            let visited = self.maze.nodes_iter().filter_map(|node| Some((self.visited_at.get(node, self.time)?, node)));
+            visited.max().map_or(Node(0, 0), |(_, node)| node)
+        };
+        self.active.first().copied().or_else(|| self.phase.focus()).unwrap_or_else(latest)
+    }
+
+    /// Draws all cells as laid out on screen (see [`State::grid_size`]) to a renderer, without presenting
+    /// them. If the renderer is too small to fit them, only those within a viewport following the running
+    /// algorithm are drawn, along with a minimap of the whole maze (see [`Viewport`]). 
+    pub fn draw(&self, renderer: &mut impl Renderer) {
+        let layout = renderer.layout();
+        // cells as laid out on screen, each spanning a block of cells as laid out by default
+        let (span_x, span_y) = layout.span();
+        let (width, height) = self.grid_size();
+        let grid = (width.div_ceil(span_x), height.div_ceil(span_y));
+        let scrolled = renderer.size().and_then(|(columns, rows)| {
+            // the status line is drawn below the cells
+            let rows = match self.settings.hud {
+                true => rows.saturating_sub(1), 
+                false => rows, 
+            };
+            let focus = self.focus();
+            let area = (columns.max(1), rows.max(1));
+            let viewport = Viewport::new(grid, area, (2 * focus.0 / span_x, 2 * focus.1 / span_y))?;
+            Some((viewport, focus))
+        });
+        let ((left, top), (columns, rows)) = scrolled.map_or(((0, 0), grid), |(v, _)| (v.offset, v.size));
+
+        for y in top..top + rows {
+            for x in left..left + columns {
+                let (column, row) = (x - left, y - top);
+                match layout {
+                    Layout::Cells => {
+                        let (swatch, overlay, colours) = self.cell(x, y);
+                        renderer.draw_cell(column, row, swatch, overlay, colours);
+                    }, 
+                    Layout::Compact => {
+                        let (swatch, overlay, colours) = self.compact_cell(Node(x, y));
+                        renderer.draw_cell(column, row, swatch, overlay, colours);
+                    }, 
+                    Layout::HalfBlock => match self.cell(x, 2 * y) {
+                        (swatch, Some(overlay), colours) => {
+                            renderer.draw_cell(column, row, swatch, Some(overlay), colours);
+                        }, 
+                        (top, None, top_colours) => {
+                            // the bottom half of the last row is below the maze
+                            let bottom = match 2 * y + 1 < height {
+                                true => self.cell(x, 2 * y + 1), 
+                                false => (Swatch::Wall, None, &self.colours), 
+                            };
+                            renderer.draw_halves(column, row, [(top, top_colours), (bottom.0, bottom.2)]);
+                        }, 
+                    }, 
+                    Layout::Braille => match self.braille_cell(x, y) {
+                        (swatch, Some(overlay), _, colours) => {
+                            renderer.draw_cell(column, row, swatch, Some(overlay), colours);
+                        }, 
+                        (swatch, None, dots, colours) => {
+                            renderer.draw_dots(column, row, dots, swatch, colours);
+                        }, 
+                    }, 
+                }
+            }
+        }
+        if let Some((_, focus)) = scrolled {
+            // the minimap takes up at most a quarter of the viewport's width and a third of its height
+            let size = (columns * layout.columns(self.settings.pitch()) / 4, rows / 3);
+            // the nodes at the corners of the viewport, the last of which may only be partly within it
+            let first = Node(left * span_x / 2, top * span_y / 2);
+            let (right, bottom) = ((left + columns) * span_x - 1, (top + rows) * span_y - 1);
+            let last = Node((right / 2).min(self.maze.width - 1), (bottom / 2).min(self.maze.height - 1));
+            let corners = (first, last);
+            renderer.draw_minimap(&Minimap::new(self, size, corners, focus), &self.colours);
+        }
+    }
+
+    /// Gets the kind of colour a cell is drawn in (as laid out on screen, see [`State::grid_size`]), the glyph
+    /// drawn upon it if any, and the colours it's looked up in. 
+    fn cell(&self, x: usize, y: usize) -> (Swatch, Option<CellOverlay>, &Lut) {
+        let node = Node(x / 2, y / 2);
+        let swatch = self.swatch(x, y);
+        let colours = match swatch {
+            Swatch::Age(_) => self.phase.colours(node).unwrap_or(&self.colours), 
+            _ => &self.colours, 
+        };
+        let overlay = match (x % 2, y % 2) {
+            (0, 0) => self.phase.overlay(node).or_else(|| self.marker(node, swatch, colours)), 
+            (1, 0) => self.arrow(node, Direction::East, swatch, colours), 
+            (0, 1) => self.arrow(node, Direction::South, swatch, colours), 
+            _ => None, 
+        };
+        (swatch, overlay, colours)
+    }
+
+    /// Gets a node drawn as a single cell (see [`Layout::Compact`]), with the walls east and south of it drawn
+    /// as thin lines in the colour of walls unless the phase draws a glyph upon it. 
+    fn compact_cell(&self, node: Node) -> (Swatch, Option<CellOverlay>, &Lut) {
+        let (x, y) = (2 * node.0, 2 * node.1);
+        let (swatch, overlay, colours) = self.cell(x, y);
+        if overlay.is_some() || swatch == Swatch::Wall {
+            return (swatch, overlay, colours)
+        }
+        // the edges of the maze are walls anyway
+        let (width, height) = self.grid_size();
+        let wall = |x: usize, y: usize| x < width && y < height && self.swatch(x, y) == Swatch::Wall;
+        let glyph = match (wall(x + 1, y), wall(x, y + 1)) {
+            (false, false) => return (swatch, None, colours), 
+            (true, false) => '▕', 
+            (false, true) => '▁', 
+            (true, true) => '⌟', 
+        };
+        // walls in the terminal's default colour are as light as its background
+        let colour = self.colours.rgb(Swatch::Wall).map_or_else(
+            || Hsl{ hue: 0.0, saturation: 0.0, lightness: self.settings.light as u8 as f64 }, 
+            Hsl::from_rgb, 
+        );
+        (swatch, Some(CellOverlay{ glyph, colour, intensity: 1.0 }), colours)
+    }
+
+    /// Gets a block of cells drawn as a single cell of braille dots (see [`Layout::Braille`]), with a dot for
+    /// each cell that isn't a wall, in the swatch standing out the most among them: a head, else special, else
+    /// the youngest age, else fog. If the phase draws a glyph upon one of the nodes, that's drawn instead. 
+    fn braille_cell(&self, x: usize, y: usize) -> (Swatch, Option<CellOverlay>, u8, &Lut) {
+        let (width, height) = self.grid_size();
+        let rank = |swatch: Swatch| match swatch {
+            Swatch::Head => 4 << 8, 
+            Swatch::Special => 3 << 8, 
+            Swatch::Age(age) => (2 << 8) + u8::MAX as u32 - age as u32, 
+            Swatch::Fog => 1 << 8, 
+            Swatch::Unvisited | Swatch::Wall => 0, 
+        };
+        let (mut dots, mut shown) = (0, (Swatch::Wall, &self.colours));
+        for (dx, dy) in (0..2).flat_map(|dx| (0..4).map(move |dy| (dx, dy))) {
+            let (x, y) = (2 * x + dx, 4 * y + dy);
+            if x >= width || y >= height {
+                continue
+            }
+            let (swatch, overlay, colours) = self.cell(x, y);
+            if let (Some(overlay), 0, 0) = (overlay, x % 2, y % 2) {
+                return (swatch, Some(overlay), 0, colours)
+            }
+            if swatch != Swatch::Wall {
+                dots |= braille_bit(dx, dy) as u8;
+            }
+            if shown.0 == Swatch::Wall || rank(swatch) > rank(shown.0) {
+                shown = (swatch, colours);
+            }
         }
+        (shown.0, None, dots, shown.1)
     }
 
-    fn format_coloured(&self, age: Option<u8>, special: bool) -> StyledContent<&str> {
-        let colour = special
-            .then_some(self.colours.special)
-            .unwrap_or_else(|| self.colours.sample(age));
-        "  ".on(colour)
+    /// Gets the arrow drawn upon the edge in a direction from a node if it's one-way (see [`Maze::one_way`]),
+    /// in black or white depending on how light the cell is drawn. Hidden edges aren't given away. 
+    fn arrow(&self, node: Node, direction: Direction, swatch: Swatch, colours: &Lut) -> Option<CellOverlay> {
+        if matches!(swatch, Swatch::Wall | Swatch::Fog) {
+            return None
+        }
+        let edge = self.maze.edge(node, direction).filter(|&e| self.maze.open[e])?;
+        let glyph = match self.maze.one_way(edge)? {
+            Direction::North => '↑', 
+            Direction::South => '↓', 
+            Direction::East => '→', 
+            Direction::West => '←', 
+        };
+        Some(self.contrasting(glyph, swatch, colours))
+    }
+
+    /// Gets the glyph given for the kind of special node a node is (see [`Phase::marker`]), if any, unless
+    /// hidden by the fog. 
+    fn marker(&self, node: Node, swatch: Swatch, colours: &Lut) -> Option<CellOverlay> {
+        if matches!(swatch, Swatch::Wall | Swatch::Fog) {
+            return None
+        }
+        let glyph = self.settings.glyphs.get(self.phase.marker(node)?)?;
+        Some(self.contrasting(glyph, swatch, colours))
+    }
+
+    /// Gets a glyph drawn in black or white depending on how light the cell it's drawn upon is. 
+    fn contrasting(&self, glyph: char, swatch: Swatch, colours: &Lut) -> CellOverlay {
+        // cells in the terminal's default colour are as light as its background
+        let light = colours.rgb(swatch).map_or(self.settings.light, |rgb| Hsl::from_rgb(rgb).lightness > 0.5);
+        let lightness = match light {
+            true => 0.0, 
+            false => 1.0, 
+        };
+        CellOverlay {
+            glyph, 
+            colour: Hsl{ hue: 0.0, saturation: 0.0, lightness }, 
+            intensity: 1.0, 
+        }
+    }
+
+    /// Gets the kind of colour a cell is drawn in, as masked and obscured by the fog of war (if any). 
+    fn swatch(&self, x: usize, y: usize) -> Swatch {
+        // edges leaving the mask are never opened, so only the nodes need to be checked
+        if self.mask.as_ref().is_some_and(|mask| !mask[Node(x / 2, y / 2)]) {
+            return Swatch::Wall
+        }
+        let swatch = self.clear_swatch(x, y);
+        let Some(fog) = &self.fog else {
+            return swatch
+        };
+        match fog.sight(&self.maze, x, y) {
+            Sight::Visible => swatch, 
+            Sight::Seen if swatch != Swatch::Wall => Swatch::Fog, 
+            _ => Swatch::Wall, 
+        }
+    }
+
+    /// Gets the kind of colour a cell is drawn in without fog. Cells that aren't nodes or open edges are
+    /// walls. 
+    fn clear_swatch(&self, x: usize, y: usize) -> Swatch {
+        let node = Node(x / 2, y / 2);
+        let direction = match (x % 2, y % 2) {
+            (0, 0) if self.active.contains(&node) => return Swatch::Head, 
+            (0, 0) => return self.node_swatch(self.age(node), self.special(node)), 
+            (1, 0) => Direction::East, 
+            (0, 1) => Direction::South, 
+            _ => return Swatch::Wall, 
+        };
+        let Some(edge) = self.maze.edge(node, direction).filter(|&e| self.maze.open[e]) else {
+            return Swatch::Wall
+        };
+        self.node_swatch(
+            // if either node is unvisited, draw as unvisited. otherwise, draw oldest age
+            Option::zip(self.age(edge.from), self.age(edge.to)).map(|(a, b)| u8::max(a, b)), 
+            // draw special if both nodes are special
+            self.special(edge.from) && self.special(edge.to), 
+        )
+    }
+
+    fn node_swatch(&self, age: Option<u8>, special: bool) -> Swatch {
+        match special {
+            true => Swatch::Special, 
+            false => Swatch::node(age), 
+        }
     }
 }
 
@@ -86,51 +385,225 @@ impl<T> DerefMut for State<T> {
 
 impl<T: Phase> fmt::Display for State<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        const EMPTY: &'static str = "  ";
-
-        let State{ maze, phase, ..} = &self;
-        let format_edge = |node, direction| maze.edge(node, direction)
-            .map(|e| match maze.open[e] {
-                true => self.format_coloured(
-                    // if either node is unvisited, draw as unvisited. otherwise, draw oldest age
-                    Option::zip(self.age[e.from], self.age[e.to]).map(|(a, b)| u8::max(a, b)), 
-                    // draw special if both nodes are special
-                    self.special(e.from) && self.special(e.to),
-                ), 
-                false => EMPTY.stylize(), 
-            })
-            .unwrap_or("".stylize());
-
-        for y in 0..maze.height {
-            // draw first row
-            for x in 0..maze.width {
-                let node = Node(x, y);
-                let east_str = format_edge(node, Direction::East);
-                let node_str = self.format_coloured(self.age[node], phase.special(node));
-                write!(f, "{node_str}{east_str}")?;
-            }
+        let mut ansi = Ansi::default();
+        ansi.symbols = self.settings.symbols;
+        ansi.pitch = self.settings.pitch();
+        self.draw(&mut ansi);
+        f.write_str(&ansi.text)
+    }
+}
 
-            if y == maze.height - 1 {
-                continue
-            }
-            write!(f, "\n\r")?;
+/// The timestep at which each node was visited, if at all (see [`State::visited_at`]). 
+///
+/// Whether a node is visited takes a bit, and the timestep 32 bits, rather than the 16 bytes of an
+/// `Option<i64>`. Timesteps are truncated to 32 bits and told apart by their difference to the current one,
+/// which is exact for nodes visited within two billion timesteps of it (long after they're fully aged). 
+#[derive(Clone)]
+pub struct Visits {
+    visited: NodeBits, 
+    at: NodeBuffer<u32>, 
+}
+
+impl Visits {
+    /// Constructs the visits of a maze, with no node visited yet. 
+    pub fn new(maze: &Maze) -> Visits {
+        Visits {
+            visited: NodeBits::new(maze), 
+            at: NodeBuffer::new(maze), 
+        }
+    }
+
+    /// Returns whether the given node is visited. 
+    pub fn is_visited(&self, node: Node) -> bool {
+        self.visited[node]
+    }
+
+    /// Gets the timestep at which a node was visited (if visited), as told apart relative to the current
+    /// timestep. 
+    pub fn get(&self, node: Node, time: i64) -> Option<i64> {
+        let elapsed = (time as u32).wrapping_sub(self.at[node]) as i32;
+        self.visited[node].then_some(time - elapsed as i64)
+    }
+
+    /// Marks a node as visited at the given timestep, returning whether it wasn't visited before. 
+    pub fn set(&mut self, node: Node, time: i64) -> bool {
+        self.at[node] = time as u32;
+        let unvisited = !self.visited[node];
+        self.visited.set(node, true);
+        unvisited
+    }
+
+    /// Unmarks a node as visited, returning whether it was visited before. 
+    pub fn unset(&mut self, node: Node) -> bool {
+        let visited = self.visited[node];
+        self.visited.set(node, false);
+        visited
+    }
+
+    /// Marks all nodes of a maze as visited at the given timestep. 
+    pub fn fill(&mut self, maze: &Maze, time: i64) {
+        self.at.fill(time as u32);
+        for node in maze.nodes_iter() {
+            self.visited.set(node, true);
+        }
+    }
+
+    /// Unmarks all nodes as visited. 
+    pub fn clear(&mut self) {
+        self.visited.clear();
+    }
+
+    /// Counts the visited nodes. 
+    pub fn count(&self) -> usize {
+        self.visited.count_ones()
+    }
+}
+
+/// Fog of war limiting what is drawn to the surroundings of the nodes visited in the current timestep, as
+/// enabled by [`Settings::fog`]. Cells seen before are remembered, and drawn dimmed (see [`Swatch::Fog`]). 
+pub struct Fog {
+    /// Distance from visited nodes within which nodes are in sight, in nodes. 
+    radius: f64, 
+    /// Nodes in sight in the current timestep. 
+    visible: NodeBits, 
+    /// Nodes that have been in sight at any point. 
+    seen: NodeBits, 
+}
+
+/// How much of a cell is known through the fog of war, from least to most. 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Sight {
+    Hidden, 
+    Seen, 
+    Visible, 
+}
 
-            // draw second row
-            for x in 0..maze.width {
-                let node = Node(x, y);
-                let south_str = format_edge(node, Direction::South);
-                write!(f, "{south_str}{EMPTY}")?;
+impl Fog {
+    /// Constructs a fog hiding the whole maze, with nodes coming into sight within a radius of those visited. 
+    pub fn new(maze: &Maze, radius: f64) -> Fog {
+        Fog {
+            radius, 
+            visible: NodeBits::new(maze), 
+            seen: NodeBits::new(maze), 
+        }
+    }
+
+    /// Brings the nodes within the radius of a node into sight. 
+    fn reveal(&mut self, maze: &Maze, Node(x, y): Node) {
+        let reach = self.radius as usize;
+        for ny in y.saturating_sub(reach)..=usize::min(y + reach, maze.height - 1) {
+            for nx in x.saturating_sub(reach)..=usize::min(x + reach, maze.width - 1) {
+                if f64::hypot(nx as f64 - x as f64, ny as f64 - y as f64) <= self.radius {
+                    self.visible.set(Node(nx, ny), true);
+                    self.seen.set(Node(nx, ny), true);
+                }
             }
-            write!(f, "\n\r")?;
         }
-        fmt::Result::Ok(())
+    }
+
+    /// Brings a node into sight, or hides it as if it was never seen. 
+    #[cfg(feature = "terminal")]
+    pub(crate) fn set_visible(&mut self, node: Node, visible: bool) {
+        self.visible.set(node, visible);
+        self.seen.set(node, visible);
+    }
+
+    /// Gets how much of a cell (as laid out on screen, see [`State::grid_size`]) is known, which is as much as
+    /// of the best known node it borders. 
+    fn sight(&self, maze: &Maze, x: usize, y: usize) -> Sight {
+        // cells between nodes border the nodes on either side
+        let xs = [x / 2, usize::min(x.div_ceil(2), maze.width - 1)];
+        let ys = [y / 2, usize::min(y.div_ceil(2), maze.height - 1)];
+        let nodes = xs.into_iter().flat_map(|x| ys.map(|y| Node(x, y)));
+        nodes
+            .map(|node| match (self.visible[node], self.seen[node]) {
+                (true, _) => Sight::Visible, 
+                (false, true) => Sight::Seen, 
+                (false, false) => Sight::Hidden, 
+            })
+            .max()
+            .unwrap_or(Sight::Hidden)
     }
 }
 
 /// Generalisation over different application phases. 
 pub trait Phase {
+    /// Name of the phase, as reported by [`Event::PhaseFinished`]. 
+    const NAME: &'static str;
+
+    /// Node being searched for, whose visit is reported by [`Event::GoalReached`]. 
+    fn goal(&self) -> Option<Node> {
+        None
+    }
     /// Whether a node should be drawn as "special". 
     fn special(&self, _node: Node) -> bool {
         false
     }
+    /// Annotation drawn on top of a node, e.g. its distance or direction. 
+    fn overlay(&self, _node: Node) -> Option<CellOverlay> {
+        None
+    }
+    /// Kind of special node a node is, drawn with the glyph given for it (see
+    /// [`Settings::glyphs`](crate::Settings::glyphs)) unless the phase draws its own overlay upon it. 
+    fn marker(&self, _node: Node) -> Option<Marker> {
+        None
+    }
+    /// Colours a visited node is drawn in, if other than those of the state (see [`State::colours`]). Edges
+    /// are drawn in the colours of the node west or north of them. 
+    fn colours(&self, _node: Node) -> Option<&Lut> {
+        None
+    }
+    /// Node the phase is focused on (e.g. the player), which a scrolled viewport follows (see
+    /// [`State::focus`]). 
+    fn focus(&self) -> Option<Node> {
+        None
+    }
+    /// Called when a node is first visited (see [`State::visit`]), e.g. to note which search visited it. 
+    fn visited(&mut self, _node: Node) {}
+    /// Line of live counters drawn below the maze while animating (see [`Settings::hud`]), given the frontier
+    /// of the running algorithm (see [`Animation::frontier`](crate::Animation::frontier)). 
+    fn status(_state: &State<Self>, _frontier: Option<usize>) -> Option<String>
+    where
+        Self: Sized, 
+    {
+        None
+    }
+}
+
+/// Kind of special node, as given by [`Phase::marker`]. 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Marker {
+    Start, 
+    Goal, 
+    /// A node of the path from the start to the goal. 
+    Path, 
+}
+
+/// A glyph drawn on top of a cell, as given by [`Phase::overlay`] or for one-way edges. Overlays are only
+/// drawn by renderers supporting text, i.e. not by [`Frame`](crate::frame::Frame). 
+#[derive(Clone, Copy, Debug)]
+pub struct CellOverlay {
+    /// Character drawn in the left column of the cell, or across the whole cell if two columns wide. 
+    pub glyph: char, 
+    /// Colour of the glyph. 
+    pub colour: Hsl, 
+    /// Factor in `0..=1` scaling the lightness of the colour, such that e.g. values may fade out. 
+    pub intensity: f64, 
+}
+
+impl CellOverlay {
+    /// Gets the colour of the glyph, scaled by the intensity. 
+    pub(crate) fn foreground(self) -> Colour {
+        let lightness = self.colour.lightness * self.intensity.clamp(0.0, 1.0);
+        self.colour.with_l(lightness).to_colour()
+    }
+
+    /// Styles the glyph to be drawn upon a cell of given background colour. 
+    pub(crate) fn styled(self, background: Colour) -> Painted<String> {
+        // glyphs too wide for the cell are left out
+        padded(self.glyph, 2)
+            .unwrap_or_else(|| "  ".to_owned())
+            .with(self.foreground())
+            .on(background)
+    }
 }
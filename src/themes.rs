@@ -0,0 +1,191 @@
+use std::str::FromStr;
+use clap::ValueEnum;
+use crate::colour::{self, Hsl, Palette};
+
+/// The palettes used by each phase. 
+///
+/// Note that the hues of all palettes are shifted by [`Settings::hue_shift`](crate::Settings::hue_shift)
+/// when rendering, so themes define the relationships between hues rather than the hues themselves. 
+#[derive(Clone, Copy, Debug)]
+pub struct Theme {
+    /// Palette of the generate phase. 
+    pub generate: Palette, 
+    /// Palette of the solve phase. 
+    pub solve: Palette, 
+    /// Palette of the walk phase. 
+    pub walk: Palette, 
+    /// Whether the hues may be randomly shifted. This is disabled for themes relying on specific hues to stay
+    /// distinguishable. 
+    pub shift_hue: bool, 
+}
+
+impl Theme {
+    /// Derives a theme by rotating the hue of the age gradient `hue_step` degrees for each phase, such that
+    /// the youngest colour of one phase is the base of the next. 
+    fn derive(recipe: Recipe) -> Theme {
+        let Recipe{ base, hue_step, old_lightness, unvisited, special_lightness } = recipe;
+        let gradient = |base: Hsl| Palette::new(base.shift_h(hue_step), base.with_l(old_lightness));
+
+        let generate = gradient(base);
+        let solve = {
+            let base = generate.young;
+            let unvisited = base
+                .with_s(unvisited.saturation)
+                .with_l(unvisited.lightness);
+            let special = base
+                .shift_h(hue_step)
+                .with_l(special_lightness);
+            gradient(base)
+                .with_unvisited(unvisited)
+                .with_special(special)
+        };
+        Theme {
+            generate, 
+            solve, 
+            walk: solve, 
+            shift_hue: true, 
+        }
+    }
+
+    /// Constructs a theme for a colour vision deficiency, using a `warm` and a `cool` hue that remain
+    /// distinguishable with it. Gradients run between the two hues (in opposite directions for the generate
+    /// and solve phases), and all kinds of nodes are distinguishable by lightness alone: special nodes are
+    /// near-white, unvisited nodes near-black, and the age gradient runs from light to dark between them. 
+    fn colourblind(warm: f64, cool: f64) -> Theme {
+        let young = |hue| Hsl{ hue, saturation: 1.0, lightness: 0.85 };
+        let old = |hue| Hsl{ hue, saturation: 0.8, lightness: 0.35 };
+
+        let generate = Palette::new(young(warm), old(cool));
+        let solve = Palette::new(young(cool), old(warm))
+            .with_unvisited(Hsl{ hue: cool, saturation: 0.3, lightness: 0.12 })
+            .with_special(Hsl{ hue: warm, saturation: 0.15, lightness: 0.98 });
+        Theme {
+            generate, 
+            solve, 
+            walk: solve, 
+            shift_hue: false, 
+        }
+    }
+}
+
+/// Parameters that a [`Theme`] is derived from. 
+struct Recipe {
+    /// Colour that the gradient of the generate phase is based on. 
+    base: Hsl, 
+    /// Hue rotation between the youngest and oldest colours of a gradient. 
+    hue_step: f64, 
+    /// Lightness of the oldest colours. 
+    old_lightness: f64, 
+    /// Saturation and lightness of unvisited nodes (the hue is ignored). 
+    unvisited: Hsl, 
+    /// Lightness of special nodes. 
+    special_lightness: f64, 
+}
+
+/// Built-in themes, selectable via `--theme`. 
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ThemeName {
+    #[default]
+    Classic, 
+    Neon, 
+    Pastel, 
+    Ember, 
+    /// Orange and blue, for red-green colour blindness (green-weak). 
+    Deuteranopia, 
+    /// Yellow and blue, for red-green colour blindness (red-weak). 
+    Protanopia, 
+    /// Red and cyan, for blue-yellow colour blindness. 
+    Tritanopia, 
+}
+
+impl ThemeName {
+    pub fn theme(self) -> Theme {
+        // hues are in Okhsl, where e.g. red is at ~30°, yellow at ~110°, cyan at ~195°, and blue at ~265°
+        let recipe = match self {
+            ThemeName::Classic => Recipe {
+                base: Hsl{ hue: 0.0, saturation: 1.0, lightness: 0.6 }, 
+                hue_step: 60.0, 
+                old_lightness: 0.3, 
+                unvisited: Hsl{ hue: 0.0, saturation: 0.7, lightness: 0.17 }, 
+                special_lightness: 0.7, 
+            }, 
+            ThemeName::Neon => Recipe {
+                base: Hsl{ hue: 0.0, saturation: 1.0, lightness: 0.75 }, 
+                hue_step: 120.0, 
+                old_lightness: 0.2, 
+                unvisited: Hsl{ hue: 0.0, saturation: 0.9, lightness: 0.1 }, 
+                special_lightness: 0.9, 
+            }, 
+            ThemeName::Pastel => Recipe {
+                base: Hsl{ hue: 0.0, saturation: 0.5, lightness: 0.85 }, 
+                hue_step: 40.0, 
+                old_lightness: 0.55, 
+                unvisited: Hsl{ hue: 0.0, saturation: 0.3, lightness: 0.3 }, 
+                special_lightness: 0.95, 
+            }, 
+            ThemeName::Ember => Recipe {
+                base: Hsl{ hue: 0.0, saturation: 1.0, lightness: 0.55 }, 
+                hue_step: 25.0, 
+                old_lightness: 0.2, 
+                unvisited: Hsl{ hue: 0.0, saturation: 0.8, lightness: 0.12 }, 
+                special_lightness: 0.75, 
+            }, 
+            ThemeName::Deuteranopia => return Theme::colourblind(65.0, 255.0), 
+            ThemeName::Protanopia => return Theme::colourblind(105.0, 255.0), 
+            ThemeName::Tritanopia => return Theme::colourblind(20.0, 195.0), 
+        };
+        Theme::derive(recipe)
+    }
+}
+
+/// Overrides for colours of a palette, as given by `--palette-<phase>` in the form `young=#rrggbb,old=#rrggbb`. 
+/// Colours that aren't given are left as defined by the theme. 
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PaletteOverride {
+    young: Option<Hsl>, 
+    old: Option<Hsl>, 
+    unvisited: Option<Hsl>, 
+    special: Option<Hsl>, 
+    wall: Option<Hsl>, 
+}
+
+impl PaletteOverride {
+    /// Applies the overrides to a palette. 
+    pub fn apply(self, palette: Palette) -> Palette {
+        Palette {
+            young: self.young.unwrap_or(palette.young), 
+            old: self.old.unwrap_or(palette.old), 
+            unvisited: self.unvisited.or(palette.unvisited), 
+            special: self.special.or(palette.special), 
+            wall: self.wall.or(palette.wall), 
+            ..palette
+        }
+    }
+}
+
+impl FromStr for PaletteOverride {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<PaletteOverride, String> {
+        let mut overrides = PaletteOverride::default();
+
+        for pair in s.split(',') {
+            let Some((key, value)) = pair.split_once('=') else {
+                return Err(format!("expected key=colour, got `{pair}`"))
+            };
+            let colour = match key {
+                "young" => &mut overrides.young, 
+                "old" => &mut overrides.old, 
+                "unvisited" => &mut overrides.unvisited, 
+                "special" => &mut overrides.special, 
+                "wall" => &mut overrides.wall, 
+                _ => return Err(format!("expected one of young, old, unvisited, special, or wall, got `{key}`")), 
+            };
+            let Some(rgb) = colour::parse_hex(value) else {
+                return Err(format!("expected a hex colour like #1e1e2e, got `{value}`"))
+            };
+            *colour = Some(Hsl::from_rgb(rgb));
+        }
+        Ok(overrides)
+    }
+}
@@ -0,0 +1,190 @@
+use std::mem;
+use crate::{
+    events::Hooks, 
+    fade, 
+    generate, 
+    maze::{Maze, Node, NodeBits}, 
+    solve, 
+    state::State, 
+    Animation, Error, Settings, Signal, 
+};
+
+/// Text drawn by the title screen. 
+const TITLE: &str = "OVERLOOK";
+
+/// Letters of the title, three nodes wide and five tall, with `#` marking the nodes within each letter. 
+const FONT: [(char, [&str; 5]); 6] = [
+    ('E', ["###", "#  ", "###", "#  ", "###"]), 
+    ('K', ["# #", "## ", "#  ", "## ", "# #"]), 
+    ('L', ["#  ", "#  ", "#  ", "#  ", "###"]), 
+    ('O', ["###", "# #", "# #", "# #", "###"]), 
+    ('R', ["## ", "# #", "## ", "# #", "# #"]), 
+    ('V', ["# #", "# #", "# #", "# #", " # "]), 
+];
+
+/// Plays the title screen upon a maze of given dimensions: the letters of the title generate themselves as
+/// mazes (masked to their shapes, see [`State::mask`]), which are then flooded from their first nodes. Plays
+/// nothing if the maze is too small to fit the title (see [`mask`]). 
+pub fn play(width: u16, height: u16, settings: &Settings) -> Result<(), Error> {
+    // the title isn't part of the run, so nothing is reported, and the letters are never hidden nor linked
+    let settings = Settings {
+        hooks: Hooks::default(), 
+        fog: None, 
+        portals: 0, 
+        one_way: 0.0, 
+        ice: 0.0, 
+        elevation: false, 
+        ..settings.clone()
+    };
+    let mut state = generate::state(Maze::new(width, height), settings);
+    let Some(mask) = mask(&state.maze) else {
+        return Ok(())
+    };
+    state.mask = Some(mask);
+
+    Carve::run(&mut state)?;
+    let mut state = fade::between(state, solve::state)?;
+    Flood::run(&mut state)?;
+    fade::out(&mut state)?;
+    Ok(())
+}
+
+/// Masks a maze to the letters of the title, centred within it, or `None` if the maze is too small to fit
+/// them with a margin of a node around them. 
+pub fn mask(maze: &Maze) -> Option<NodeBits> {
+    let width = 4 * TITLE.len() - 1;
+    if maze.width < width + 2 || maze.height < 7 {
+        return None
+    }
+    let (left, top) = ((maze.width - width) / 2, (maze.height - 5) / 2);
+    let mut mask = NodeBits::new(maze);
+    for (i, letter) in TITLE.chars().enumerate() {
+        let (_, rows) = FONT.iter().find(|&&(c, _)| c == letter).expect("the font has all letters of the title");
+        for (y, row) in rows.iter().enumerate() {
+            for (x, _) in row.chars().enumerate().filter(|&(_, c)| c == '#') {
+                mask.set(Node(left + 4 * i + x, top + y), true);
+            }
+        }
+    }
+    Some(mask)
+}
+
+/// Gets the nodes within the mask of a state (or all nodes, if unmasked), column by column from the left. 
+fn masked<T>(state: &State<T>) -> Vec<Node> {
+    let mut nodes: Vec<Node> = state.maze
+        .nodes_iter()
+        .filter(|&node| state.mask.as_ref().is_none_or(|mask| mask[node]))
+        .collect();
+    nodes.sort_by_key(|&Node(x, y)| (x, y));
+    nodes
+}
+
+/// Animation carving the letters by randomised depth-first search (like the `dfs` generator), one letter (or
+/// stroke of one) after the other. 
+struct Carve {
+    stack: Vec<Node>, 
+    /// Nodes within the mask, in the reverse of the order in which they're carved from. 
+    pending: Vec<Node>, 
+}
+
+impl Animation for Carve {
+    type Phase = generate::Phase;
+
+    fn new(state: &mut generate::State) -> Carve {
+        let mut pending = masked(state);
+        pending.reverse();
+        Carve {
+            stack: Vec::new(), 
+            pending, 
+        }
+    }
+
+    fn step(&mut self, state: &mut generate::State) -> Signal {
+        if self.stack.is_empty() {
+            // move on to the next letter (or stroke of one) not carved yet
+            self.pending.retain(|&node| !state.is_visited(node));
+            let Some(node) = self.pending.pop() else {
+                return Signal::Done
+            };
+            self.stack.push(node);
+        }
+        let head = self.stack.pop().expect("the stack isn't empty");
+        state.visit(head);
+
+        let neighbour = state.maze
+            .neighbours(head)
+            .filter(|n| state.mask.as_ref().is_none_or(|mask| mask[n]) && !state.is_visited(n))
+            .choose(&mut state.rng);
+        if let Some(edge) = neighbour {
+            self.stack.push(head);
+            self.stack.push(edge.to);
+            state.open(edge);
+        }
+        Signal::Continue
+    }
+
+    fn timescale(&self) -> u32 {
+        // the title is but a prelude, so it's drawn quickly
+        400
+    }
+
+    fn heads(&self) -> Vec<Node> {
+        self.stack.last().into_iter().copied().collect()
+    }
+}
+
+/// Animation flooding the carved letters breadth-first, from the first node of each of their strokes at once. 
+struct Flood {
+    frontier: Vec<Node>, 
+}
+
+impl Animation for Flood {
+    type Phase = solve::Phase;
+
+    fn new(state: &mut solve::State) -> Flood {
+        // the strokes are separate mazes, so each is flooded from the first of its nodes
+        let mut reached = NodeBits::new(&state.maze);
+        let mut roots = Vec::new();
+        for node in masked(state) {
+            if reached[node] {
+                continue
+            }
+            roots.push(node);
+            let mut stack = vec![node];
+            reached.set(node, true);
+            while let Some(node) = stack.pop() {
+                for edge in state.maze.open_neighbours(node) {
+                    if !reached[edge.to] {
+                        reached.set(edge.to, true);
+                        stack.push(edge.to);
+                    }
+                }
+            }
+        }
+        for &root in &roots {
+            state.visit(root);
+        }
+        Flood {
+            frontier: roots, 
+        }
+    }
+
+    fn step(&mut self, state: &mut solve::State) -> Signal {
+        if self.frontier.is_empty() {
+            return Signal::Done
+        }
+        for node in mem::take(&mut self.frontier) {
+            for edge in state.maze.open_neighbours(node) {
+                if !state.is_visited(edge.to) {
+                    state.visit(edge.to);
+                    self.frontier.push(edge.to);
+                }
+            }
+        }
+        Signal::Continue
+    }
+
+    fn heads(&self) -> Vec<Node> {
+        self.frontier.clone()
+    }
+}
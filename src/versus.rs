@@ -0,0 +1,165 @@
+//! Comparison of two generators side by side, as animated by `overlook versus`. Each generates its own maze of
+//! the same size from the same seed, in lockstep, and both are tiled into a single maze with a column masked
+//! out between them (as in a [`duel`](crate::duel)), such that they're drawn as one frame. 
+
+use std::fmt;
+use crate::{
+    analysis::Analysis, 
+    events::Hooks, 
+    generate::{self, Generator}, 
+    maze::{Maze, Node, NodeBits}, 
+    Animation, Error, Settings, Signal, Stepper, 
+};
+
+/// One side of the comparison: a generator working upon its own maze. 
+struct Pane {
+    state: generate::State, 
+    /// The generator, until it's done. 
+    stepper: Option<Stepper<generate::Phase>>, 
+    /// Column of the tiled maze that the maze of the pane starts at. 
+    offset: usize, 
+}
+
+impl Pane {
+    /// Copies the maze of the pane, and when each of its nodes was visited, into the tiled maze. 
+    fn mirror(&self, state: &mut generate::State) {
+        let shift = |Node(x, y): Node| Node(x + self.offset, y);
+        let maze = &self.state.maze;
+        for node in maze.nodes_iter() {
+            match self.state.visited_at.get(node, self.state.time) {
+                Some(time) => state.visited_at.set(shift(node), time), 
+                None => state.visited_at.unset(shift(node)), 
+            };
+        }
+        for edge in maze.edges_iter() {
+            if let Some(copy) = state.maze.edge(shift(edge.from), edge.direction) {
+                state.maze.open.set(copy, maze.open[edge]);
+            }
+        }
+    }
+}
+
+/// Constructs the state for comparing generators upon mazes of given dimensions, tiling two of them side by
+/// side. 
+pub fn state((width, height): (u16, u16), settings: Settings) -> generate::State {
+    let tiled = Maze::new(2 * width + 1, height);
+    // the column between the mazes is drawn as walls
+    let mut mask = NodeBits::new(&tiled);
+    for node in tiled.nodes_iter().filter(|node| node.0 != width as usize) {
+        mask.set(node, true);
+    }
+    let mut state = generate::state(tiled, settings);
+    state.mask = Some(mask);
+    state
+}
+
+/// Animation of two generators stepped in lockstep, each upon its own half of a tiled maze (see [`state`]),
+/// which ends once both are done. 
+pub struct Versus {
+    panes: Vec<Pane>, 
+}
+
+impl Versus {
+    /// Constructs a comparison of two generators, left and right, each started from the seed of the state. 
+    pub fn new_with(generators: [Generator; 2], state: &mut generate::State) -> Versus {
+        let (width, height) = ((state.maze.width - 1) / 2, state.maze.height);
+        // the panes have their own coordinates, so their events would only confuse the hooks
+        let settings = Settings{ hooks: Hooks::default(), ..state.settings.clone() };
+        let panes = generators.into_iter().zip([0, width + 1]).map(|(generator, offset)| {
+            let mut state = generate::state(Maze::new(width as u16, height as u16), settings.clone());
+            let stepper = Some(generator.stepper(&mut state));
+            Pane{ state, stepper, offset }
+        });
+        Versus{ panes: panes.collect() }
+    }
+}
+
+impl Animation for Versus {
+    type Phase = generate::Phase;
+
+    /// Constructs a comparison of no generators, which is done right away. 
+    fn new(_state: &mut generate::State) -> Versus {
+        Versus{ panes: Vec::new() }
+    }
+
+    fn step(&mut self, state: &mut generate::State) -> Signal {
+        for pane in &mut self.panes {
+            let Some(stepper) = &mut pane.stepper else {
+                continue
+            };
+            // keep the pane in time with the tiled maze, such that its nodes age alike
+            pane.state.time = state.time;
+            if let Signal::Done = stepper(&mut pane.state) {
+                pane.stepper = None;
+            }
+            pane.mirror(state);
+        }
+        state.visited_count = state.visited_at.count();
+        match self.panes.iter().all(|pane| pane.stepper.is_none()) {
+            true => Signal::Done, 
+            false => Signal::Continue, 
+        }
+    }
+}
+
+/// How each generator of a comparison did, as shown once it's over (see [`play`]). 
+#[derive(Clone, Debug, PartialEq)]
+pub struct Results {
+    /// The generators, left and right. 
+    pub generators: [&'static str; 2], 
+    /// Timesteps each generator took. 
+    pub steps: [usize; 2], 
+    /// Structure of the maze each generator generated. 
+    pub analyses: [Analysis; 2], 
+}
+
+impl Results {
+    /// Generates the mazes of a comparison without animating them. The generators are deterministic given the
+    /// seed, so these are the mazes that the comparison animates. Fails if either maze is invalid and
+    /// verification is enabled (see [`Settings::verify`]). 
+    pub fn new(
+        generators: [Generator; 2], 
+        (width, height): (u16, u16), 
+        settings: Settings, 
+    ) -> Result<Results, Error> {
+        let runs = generators.map(|generator| {
+            let mut state = generate::state(Maze::new(width, height), settings.clone());
+            let steps = generator.run_headless(&mut state);
+            generate::verify(&state)?;
+            Ok::<_, Error>((steps, Analysis::new(&state.maze, None)))
+        });
+        let [left, right] = runs;
+        let [(left_steps, left), (right_steps, right)] = [left?, right?];
+        Ok(Results {
+            generators: generators.map(|generator| generator.name()), 
+            steps: [left_steps, right_steps], 
+            analyses: [left, right], 
+        })
+    }
+}
+
+impl fmt::Display for Results {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [left, right] = self.generators;
+        writeln!(f, "{:<20}{left:>10}{right:>10}", "")?;
+        let row = |f: &mut fmt::Formatter, name: &str, [left, right]: [String; 2]| {
+            writeln!(f, "{name:<20}{left:>10}{right:>10}")
+        };
+        row(f, "timesteps", self.steps.map(|steps| steps.to_string()))?;
+        let [left, right] = &self.analyses;
+        let both = |value: fn(&Analysis) -> String| [value(left), value(right)];
+        row(f, "dead ends", both(|analysis| analysis.degrees[1].to_string()))?;
+        row(f, "junctions", both(|analysis| (analysis.degrees[3] + analysis.degrees[4]).to_string()))?;
+        row(f, "corridors", both(|analysis| analysis.corridors.to_string()))?;
+        row(f, "corridor length", both(|analysis| format!("{:.2}", analysis.corridor_length)))?;
+        row(f, "diameter", both(|analysis| analysis.diameter.to_string()))
+    }
+}
+
+/// Compares two generators upon mazes of given dimensions, animated side by side, returning how each did once
+/// both are done. 
+pub fn play(generators: [Generator; 2], size: (u16, u16), settings: Settings) -> Result<Results, Error> {
+    let mut state = state(size, settings.clone());
+    Versus::new_with(generators, &mut state).play(&mut state)?;
+    Results::new(generators, size, settings)
+}
@@ -0,0 +1,135 @@
+//! Scrolling of mazes too large for the renderer, which then only draws the part of the maze around where the
+//! running algorithm is at (see [`Viewport`]), with a [`Minimap`] of the whole maze in the corner. 
+
+use crate::{
+    colour::Swatch, 
+    maze::Node, 
+    state::{Phase, State}, 
+};
+
+/// Largest size of a minimap, in characters. 
+const MINIMAP_SIZE: (usize, usize) = (24, 8);
+
+/// Part of the maze drawn when it's too large for the renderer, in cells as laid out by the renderer (see
+/// [`Layout`](crate::render::Layout)). 
+///
+/// The viewport follows a node of focus (see [`State::focus`]) in steps of a quarter of its size, keeping the
+/// node within the middle of the viewport without scrolling at every move. 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Viewport {
+    /// Cell at the top left of the viewport. 
+    pub offset: (usize, usize), 
+    /// Number of columns and rows of cells in the viewport. 
+    pub size: (usize, usize), 
+}
+
+impl Viewport {
+    /// Constructs the viewport of a grid of cells within an area of given size, following a cell. Returns
+    /// `None` if the whole grid fits. 
+    pub fn new(grid: (usize, usize), area: (usize, usize), (x, y): (usize, usize)) -> Option<Viewport> {
+        if grid.0 <= area.0 && grid.1 <= area.1 {
+            return None
+        }
+        let axis = |grid: usize, area: usize, focus: usize| {
+            if grid <= area {
+                return (0, grid)
+            }
+            let step = usize::max(area / 4, 1);
+            let offset = focus.saturating_sub(area / 2) / step * step;
+            (offset.min(grid - area), area)
+        };
+        let (x, width) = axis(grid.0, area.0, x);
+        let (y, height) = axis(grid.1, area.1, y);
+        Some(Viewport {
+            offset: (x, y), 
+            size: (width, height), 
+        })
+    }
+}
+
+/// Map of the whole maze downsampled into braille characters, drawn in the corner of the viewport while the
+/// maze is scrolled. Each dot covers a square block of nodes, and is set if any of them is visited or the
+/// block is on the edge of the viewport. 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Minimap {
+    /// Rows of characters, each with the swatch its dots are drawn in: that of the head if it's at one of
+    /// them, special if on the edge of the viewport, and otherwise the youngest age of the nodes it covers. 
+    pub rows: Vec<Vec<(char, Swatch)>>, 
+}
+
+impl Minimap {
+    /// Downsamples the maze of a state into a minimap of at most a given number of columns and rows, marking
+    /// the viewport (given by the nodes at its top left and bottom right corners) and a head. 
+    pub fn new<T: Phase>(
+        state: &State<T>, 
+        (columns, rows): (usize, usize), 
+        (first, last): (Node, Node), 
+        head: Node, 
+    ) -> Minimap {
+        let maze = &state.maze;
+        // a character is two dots wide and four tall
+        let columns = columns.clamp(1, MINIMAP_SIZE.0);
+        let rows = rows.clamp(1, MINIMAP_SIZE.1);
+        let scale = usize::max(maze.width.div_ceil(2 * columns), maze.height.div_ceil(4 * rows)).max(1);
+        let (dots_x, dots_y) = (maze.width.div_ceil(scale), maze.height.div_ceil(scale));
+
+        // the viewport's edge in dots
+        let (x0, y0) = (first.0 / scale, first.1 / scale);
+        let (x1, y1) = (last.0 / scale, last.1 / scale);
+        let head = (head.0 / scale, head.1 / scale);
+        let edge = |x: usize, y: usize| {
+            let inside = (x0..=x1).contains(&x) && (y0..=y1).contains(&y);
+            inside && (x == x0 || x == x1 || y == y0 || y == y1)
+        };
+
+        // youngest age of the nodes covered by each dot, counted down from the oldest such that the youngest
+        // is the greatest and unvisited nodes (i.e. none) the least
+        let mut ages = vec![None; dots_x * dots_y];
+        for node in maze.nodes_iter() {
+            let dot = &mut ages[node.0 / scale + node.1 / scale * dots_x];
+            *dot = Option::max(*dot, state.age(node).map(|age| u8::MAX - age));
+        }
+
+        let rows = (0..dots_y.div_ceil(4)).map(|row| {
+            (0..dots_x.div_ceil(2)).map(|column| {
+                let (mut bits, mut marked, mut youngest) = (0, false, None);
+                for (dx, dy) in (0..2).flat_map(|dx| (0..4).map(move |dy| (dx, dy))) {
+                    let (x, y) = (2 * column + dx, 4 * row + dy);
+                    if x >= dots_x || y >= dots_y {
+                        continue
+                    }
+                    let age = ages[x + y * dots_x];
+                    youngest = Option::max(youngest, age);
+                    if age.is_some() || edge(x, y) || (x, y) == head {
+                        bits |= braille_bit(dx, dy);
+                    }
+                    marked |= edge(x, y);
+                }
+                let contains_head = head.0 / 2 == column && head.1 / 4 == row;
+                let swatch = match (contains_head, marked) {
+                    (true, _) => Swatch::Head, 
+                    (false, true) => Swatch::Special, 
+                    (false, false) => Swatch::node(youngest.map(|age| u8::MAX - age)), 
+                };
+                let glyph = char::from_u32(0x2800 + bits).expect("braille patterns are valid characters");
+                (glyph, swatch)
+            }).collect()
+        });
+        Minimap{ rows: rows.collect() }
+    }
+
+    /// Number of columns the minimap is wide. 
+    pub fn width(&self) -> usize {
+        self.rows.first().map_or(0, Vec::len)
+    }
+}
+
+/// Gets the bit of a braille pattern setting the dot at a column and row of its 2x4 grid. 
+pub(crate) fn braille_bit(x: usize, y: usize) -> u32 {
+    match (x, y) {
+        (0, 3) => 0x40, 
+        (1, 3) => 0x80, 
+        (0, y) => 1 << y, 
+        (_, y) => 1 << (y + 3), 
+    }
+}
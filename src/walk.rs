@@ -1,44 +1,207 @@
+use std::collections::VecDeque;
+use clap::ValueEnum;
+use rand::seq::SliceRandom;
 use crate::{
-    maze::{Node, NodeBuffer}, 
-    solve, 
-    state, 
+    colour::Hsl, 
+    maze::{Node, NodeBits, NodeBuffer}, 
+    ice::Ice, 
+    portals::Portals, 
+    registry::Entry, 
+    solve::{self, Solution}, 
+    state::{self, CellOverlay, Marker}, 
     Animation, 
     Signal, 
 };
+#[cfg(feature = "terminal")]
+use crate::Error;
 
+/// Number of timesteps a pulse takes to travel the solution once (see [`pulse`]). 
+#[cfg(feature = "terminal")]
+const PULSE_STEPS: usize = 30;
+
+/// Number of nodes on either side of a pulse lit by it. 
+#[cfg(feature = "terminal")]
+const PULSE_RADIUS: usize = 3;
+
+/// Walks the solution backward from the goal, following the parents found by the solver. 
 pub struct Walker {
     head: Node, 
 }
 
+/// Runs along the solution forward from the start to the goal, as a glyph leaving the path lit behind it. 
+pub struct Runner {
+    /// Index of the next node of the route to run to. 
+    next: usize, 
+    timescale: u32, 
+}
+
+/// Walks from the start and from random dead ends at once, with all walkers converging on the goal. The
+/// walker from the start walks the solution, and the others leave fading trails. 
+pub struct Swarm {
+    /// Nodes the walkers are at, the first of which started at the start. 
+    walkers: Vec<Node>, 
+    /// The next node toward the goal from each node, i.e. the parents of a search from the goal. 
+    toward_goal: NodeBuffer<Option<Node>>, 
+}
+
+/// Sends a pulse of brightness back and forth along the solution (as many times as
+/// [`Settings::pulses`](crate::Settings::pulses)), once it has been walked. Does nothing if no solution was
+/// found. 
+#[cfg(feature = "terminal")]
+pub fn pulse(state: &mut State) -> Result<(), Error> {
+    if state.settings.pulses > 0 && state.solution().is_some() {
+        Pulse::run(state)?;
+    }
+    Ok(())
+}
+
+/// Animation moving a pulse along the route, which draws the nodes around it by age rather than as the
+/// path. Nodes are relit without being reported as visited again, like the celebration. 
+#[cfg(feature = "terminal")]
+struct Pulse {
+    /// Indices of the route that the pulse is at in each timestep. 
+    positions: VecDeque<usize>, 
+}
+
+/// How the solution is walked, as given by `--walk`. 
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WalkStyle {
+    /// Traces the solution backward from the goal. 
+    #[default]
+    Trace, 
+    /// A runner travels from the start to the goal, leaving the path lit behind it. 
+    Runner, 
+    /// Several walkers travel from the start and from random dead ends to the goal. 
+    Swarm, 
+}
+
+impl WalkStyle {
+    /// Gets the animation walking in this style. 
+    pub fn walker(self) -> Entry<Phase> {
+        match self {
+            WalkStyle::Trace => Entry::new::<Walker>("trace"), 
+            WalkStyle::Runner => Entry::new::<Runner>("runner"), 
+            WalkStyle::Swarm => Entry::new::<Swarm>("swarm"), 
+        }
+    }
+}
+
 pub struct Phase {
     start: Node,
     goal: Node, 
     parents: NodeBuffer<Option<Node>>, 
-    on_path: NodeBuffer<bool>, 
+    /// The solution forward from the start to the goal, or empty if the goal wasn't reached. 
+    route: Vec<Node>, 
+    on_path: NodeBits, 
+    /// Nodes walked so far, in the order walked: from the goal backward, or from the start forward. 
+    path: Vec<Node>, 
+    /// Nodes that walkers drawn as glyphs are at, with the colours of their glyphs (see [`Runner`] and
+    /// [`Swarm`]). 
+    heads: Vec<(Node, Hsl)>, 
+    /// Colour of the runner, contrasting with the path it leaves. 
+    runner_colour: Hsl, 
+    /// Nodes lit by a pulse, which are drawn by age rather than as the path (see [`pulse`]). 
+    pulse: Vec<Node>, 
+    /// Portals placed before solving, still drawn while walking (see
+    /// [`Settings::portals`](crate::Settings::portals)). 
+    portals: Option<Portals>, 
+    /// Ice placed before solving, still drawn while walking (see [`Settings::ice`](crate::Settings::ice)). 
+    ice: Option<Ice>, 
+}
+
+impl Phase {
+    /// Gets the path walked from the start to the goal, once the walk has reached its end. 
+    pub fn solution(&self) -> Option<Solution> {
+        let path = match (self.path.first(), self.path.last()) {
+            (Some(&first), Some(&last)) if (first, last) == (self.goal, self.start) => {
+                self.path.iter().rev().copied().collect()
+            }, 
+            (Some(&first), Some(&last)) if (first, last) == (self.start, self.goal) => self.path.clone(), 
+            _ => return None, 
+        };
+        Some(Solution {
+            start: self.start, 
+            goal: self.goal, 
+            path, 
+        })
+    }
 }
 
 impl state::Phase for Phase {
+    const NAME: &str = "walk";
+
     fn special(&self, node: Node) -> bool {
-        self.on_path[node] || [self.start, self.goal].contains(&node)
+        (self.on_path[node] || [self.start, self.goal].contains(&node)) && !self.pulse.contains(&node)
+    }
+
+    fn marker(&self, node: Node) -> Option<Marker> {
+        match node {
+            _ if node == self.start => Some(Marker::Start), 
+            _ if node == self.goal => Some(Marker::Goal), 
+            _ if self.on_path[node] => Some(Marker::Path), 
+            _ => None, 
+        }
+    }
+
+    fn overlay(&self, node: Node) -> Option<CellOverlay> {
+        let Some(&(_, colour)) = self.heads.iter().find(|&&(head, _)| head == node) else {
+            let portal = self.portals.as_ref().and_then(|portals| portals.overlay(node));
+            return portal.or_else(|| self.ice.as_ref()?.overlay(node))
+        };
+        Some(CellOverlay {
+            glyph: '@', 
+            colour, 
+            intensity: 1.0, 
+        })
     }
 }
 
 pub type State = state::State<Phase>;
 
 pub fn state(previous: solve::State) -> State {
-    let solve::Phase{ start, goal, parents } = previous.phase;
+    let route = previous.phase.solution().map_or_else(Vec::new, |solution| solution.path);
+    let solve::Phase{ start, goal, mut parents, portals, ice, .. } = previous.phase;
+    // the route includes the nodes slid across on ice, which the parents skip, so it's walked instead
+    for pair in route.windows(2) {
+        parents[pair[1]] = Some(pair[0]);
+    }
+    let colours = previous.settings.theme.walk.into_lut(&previous.settings);
+
+    // the runner is drawn upon the path, so it's black or white depending on how light the path is (with
+    // lightness inverted for light backgrounds)
+    let special = colours.palette.special.unwrap_or(colours.palette.young);
+    let runner_colour = Hsl {
+        hue: 0.0, 
+        saturation: 0.0, 
+        lightness: match (special.lightness > 0.5) != previous.settings.light {
+            true => 0.0, 
+            false => 1.0, 
+        }, 
+    };
     let phase = Phase {
         start, 
         goal, 
         parents, 
-        on_path: NodeBuffer::new(&previous.maze), 
+        route, 
+        on_path: NodeBits::new(&previous.maze), 
+        path: Vec::new(), 
+        heads: Vec::new(), 
+        runner_colour, 
+        pulse: Vec::new(), 
+        portals, 
+        ice, 
     };
     State {
         maze: previous.maze, 
         settings: previous.settings, 
-        age: previous.age, 
+        rng: previous.rng, 
+        visited_at: previous.visited_at, 
         visited_count: previous.visited_count, 
-        colours: previous.colours, 
+        time: previous.time, 
+        colours, 
+        active: Vec::new(), 
+        fog: None, 
+        mask: previous.mask, 
         phase, 
     }
 }
@@ -47,8 +210,10 @@ impl Animation for Walker {
     type Phase = Phase;
 
     fn new(state: &mut State) -> Self {
+        let goal = state.goal;
+        state.path.push(goal);
         Walker {
-            head: state.goal, 
+            head: goal, 
         }
     }
 
@@ -56,7 +221,8 @@ impl Animation for Walker {
         let Some(head) = state.parents[self.head] else {
             return Signal::Done
         };
-        state.on_path[head] = true;
+        state.on_path.set(head, true);
+        state.path.push(head);
 
         if head == state.start {
             return Signal::Done
@@ -65,3 +231,149 @@ impl Animation for Walker {
         Signal::Continue
     }
 }
+
+impl Animation for Runner {
+    type Phase = Phase;
+
+    fn new(state: &mut State) -> Self {
+        let speed = state.settings.runner_speed;
+        Runner {
+            next: 0, 
+            timescale: (100.0 * speed).round().max(1.0) as u32, 
+        }
+    }
+
+    fn step(&mut self, state: &mut State) -> Signal {
+        let Some(&head) = state.route.get(self.next) else {
+            // leave the path lit without the runner once it has arrived
+            state.heads.clear();
+            return Signal::Done
+        };
+        state.on_path.set(head, true);
+        state.path.push(head);
+        state.heads = vec![(head, state.runner_colour)];
+        self.next += 1;
+        Signal::Continue
+    }
+
+    fn timescale(&self) -> u32 {
+        self.timescale
+    }
+}
+
+impl Animation for Swarm {
+    type Phase = Phase;
+
+    fn new(state: &mut State) -> Self {
+        let (start, goal) = (state.start, state.goal);
+        let mut toward_goal: NodeBuffer<Option<Node>> = NodeBuffer::new(&state.maze);
+        let mut queue = VecDeque::from([goal]);
+        toward_goal[goal] = Some(goal);
+        while let Some(node) = queue.pop_front() {
+            for edge in state.maze.open_neighbours(node) {
+                if toward_goal[edge.to].is_none() {
+                    toward_goal[edge.to] = Some(node);
+                    queue.push_back(edge.to);
+                }
+            }
+        }
+
+        // walkers only start where the goal can be reached from
+        let dead_ends: Vec<Node> = state.maze
+            .nodes_iter()
+            .filter(|&node| node != start && node != goal && toward_goal[node].is_some())
+            .filter(|&node| state.maze.open_neighbours(node).len() == 1)
+            .collect();
+        let others = state.settings.walkers.saturating_sub(1);
+        let mut walkers = vec![start];
+        walkers.extend(dead_ends.choose_multiple(&mut state.rng, others));
+
+        if toward_goal[start].is_some() {
+            state.on_path.set(start, true);
+            state.path.push(start);
+        }
+        let walkers: Vec<Node> = walkers.into_iter().filter(|&node| toward_goal[node].is_some()).collect();
+        state.heads = heads(&walkers);
+        Swarm {
+            walkers, 
+            toward_goal, 
+        }
+    }
+
+    fn step(&mut self, state: &mut State) -> Signal {
+        let goal = state.goal;
+        if self.walkers.iter().all(|&walker| walker == goal) {
+            state.heads.clear();
+            return Signal::Done
+        }
+        for (i, walker) in self.walkers.iter_mut().enumerate() {
+            let Some(next) = self.toward_goal[*walker].filter(|_| *walker != goal) else {
+                continue
+            };
+            *walker = next;
+            // relight the trail without reporting a visit, since the node was visited while solving
+            state.visited_at.set(next, state.time);
+            if i == 0 && state.path.first() == Some(&state.start) {
+                state.on_path.set(next, true);
+                state.path.push(next);
+            }
+        }
+        state.heads = heads(&self.walkers);
+        Signal::Continue
+    }
+}
+
+#[cfg(feature = "terminal")]
+impl Animation for Pulse {
+    type Phase = Phase;
+
+    fn new(state: &mut State) -> Self {
+        let last = state.route.len().saturating_sub(1);
+        let stride = state.route.len().div_ceil(PULSE_STEPS).max(1);
+        let forward: Vec<usize> = (0..last).step_by(stride).chain([last]).collect();
+        let mut positions = VecDeque::new();
+        for _ in 0..state.settings.pulses {
+            positions.extend(forward.iter().copied());
+            positions.extend(forward.iter().rev().copied());
+        }
+        Pulse{ positions }
+    }
+
+    fn step(&mut self, state: &mut State) -> Signal {
+        let Some(position) = self.positions.pop_front() else {
+            state.pulse.clear();
+            return Signal::Done
+        };
+        let (first, last) = (position.saturating_sub(PULSE_RADIUS), position + PULSE_RADIUS);
+        let lit: Vec<Node> = state.route.iter().copied().take(last + 1).skip(first).collect();
+        // the nodes farther from the centre of the pulse are drawn older, up to half of the full age
+        let age_per_node = state.settings.trail as i64 / (2 * PULSE_RADIUS as i64 + 2);
+        for (i, &node) in lit.iter().enumerate() {
+            let distance = (first + i).abs_diff(position) as i64;
+            state.visited_at.set(node, state.time - distance * age_per_node);
+        }
+        state.pulse = lit;
+        Signal::Continue
+    }
+
+    fn steps_per_frame(&self, _state: &State) -> u32 {
+        // pulses are timed independently of the algorithms
+        1
+    }
+
+    fn finishes_phase(&self) -> bool {
+        false
+    }
+}
+
+/// Gets the glyphs of walkers, with hues spread evenly around the colour wheel. 
+fn heads(walkers: &[Node]) -> Vec<(Node, Hsl)> {
+    walkers
+        .iter()
+        .enumerate()
+        .map(|(i, &walker)| {
+            let hue = 360.0 * i as f64 / walkers.len() as f64;
+            (walker, Hsl{ hue, saturation: 1.0, lightness: 0.8 })
+        })
+        .collect()
+}
@@ -0,0 +1,152 @@
+use wasm_bindgen::prelude::*;
+use crate::{
+    events::Event, 
+    frame::Frame, 
+    generate::{self, GENERATORS}, 
+    maze::Maze, 
+    solve::{self, Solver, SOLVERS}, 
+    state::{self, Phase}, 
+    walk::{self, Walker}, 
+    Animation, Settings, Signal, Stepper, StepsPerFrame, 
+};
+
+/// Plays the animation one frame at a time for a web page to draw upon a `<canvas>`. 
+///
+/// In the browser there is no terminal, stdout, or threads to drive [`Animation::run`], so the page instead
+/// calls [`Player::tick`] from `requestAnimationFrame` and draws [`Player::pixels`] (one pixel per cell,
+/// scaled up with `image-rendering: pixelated`). Phases follow each other without fades. 
+#[wasm_bindgen]
+pub struct Player {
+    /// Current stage, which is only `None` while advancing to the next. 
+    stage: Option<Stage>, 
+    steps_per_frame: u32, 
+    /// Dimensions of the maze in cells (see [`State::grid_size`](state::State::grid_size)). 
+    grid_size: (usize, usize), 
+}
+
+/// The phase being animated, along with its algorithm. States are boxed, since they differ in size. 
+enum Stage {
+    Generate(Box<generate::State>, Stepper<generate::Phase>, Solver), 
+    Solve(Box<solve::State>, Stepper<solve::Phase>), 
+    Walk(Box<walk::State>, Stepper<walk::Phase>), 
+    Done(Box<walk::State>), 
+}
+
+#[wasm_bindgen]
+impl Player {
+    /// Starts generating a maze with the named generator, to be solved with the named solver. 
+    #[wasm_bindgen(constructor)]
+    pub fn new(width: u16, height: u16, generator: &str, solver: &str, seed: u64) -> Result<Player, JsError> {
+        if width < 2 || height < 2 {
+            return Err(JsError::new("mazes are at least 2x2 nodes"))
+        }
+        let generator = GENERATORS
+            .find(generator)
+            .ok_or_else(|| JsError::new(&format!("unknown generator `{generator}`")))?;
+        let solver = SOLVERS
+            .find(solver)
+            .ok_or_else(|| JsError::new(&format!("unknown solver `{solver}`")))?;
+
+        let settings = Settings{ seed, ..Settings::default() };
+        let mut state = generate::state(Maze::new(width, height), settings);
+        let steps_per_frame = StepsPerFrame::Auto.resolve(&state.maze);
+        let grid_size = state.grid_size();
+        let stepper = generator.stepper(&mut state);
+        Ok(Player {
+            stage: Some(Stage::Generate(Box::new(state), stepper, solver)), 
+            steps_per_frame, 
+            grid_size, 
+        })
+    }
+
+    /// Advances the animation by one frame, returning whether it's still running. 
+    pub fn tick(&mut self) -> bool {
+        for _ in 0..self.steps_per_frame {
+            let stage = self.stage.take().expect("stage is restored after each step");
+            self.stage = Some(stage.step());
+        }
+        !matches!(self.stage, Some(Stage::Done(_)))
+    }
+
+    /// Width of the drawn maze in cells (and pixels). 
+    pub fn width(&self) -> usize {
+        self.grid_size.0
+    }
+
+    /// Height of the drawn maze in cells (and pixels). 
+    pub fn height(&self) -> usize {
+        self.grid_size.1
+    }
+
+    /// Colours of all cells in RGBA, row-major, ready for an `ImageData`. Cells without a colour (i.e. the
+    /// terminal's default) are transparent, such that the page background shows through. 
+    pub fn pixels(&self) -> Vec<u8> {
+        self.frame()
+            .cells
+            .into_iter()
+            .flat_map(|cell| match cell {
+                Some([r, g, b]) => [r, g, b, u8::MAX], 
+                None => [0; 4], 
+            })
+            .collect()
+    }
+}
+
+impl Player {
+    /// Captures the current frame of the stage. 
+    fn frame(&self) -> Frame {
+        match self.stage.as_ref().expect("stage is restored after each step") {
+            Stage::Generate(state, ..) => Frame::capture(state), 
+            Stage::Solve(state, _) => Frame::capture(state), 
+            Stage::Walk(state, _) | Stage::Done(state) => Frame::capture(state), 
+        }
+    }
+}
+
+impl Stage {
+    /// Steps the algorithm of the stage, advancing to the next stage once it's done. 
+    fn step(self) -> Stage {
+        match self {
+            Stage::Generate(mut state, mut step, solver) => match step(&mut state) {
+                Signal::Continue => {
+                    state.step();
+                    Stage::Generate(state, step, solver)
+                }, 
+                Signal::Done => {
+                    finish(&state);
+                    let mut state = solve::state(*state);
+                    let step = solver.stepper(&mut state);
+                    Stage::Solve(Box::new(state), step)
+                }, 
+            }, 
+            Stage::Solve(mut state, mut step) => match step(&mut state) {
+                Signal::Continue => {
+                    state.step();
+                    Stage::Solve(state, step)
+                }, 
+                Signal::Done => {
+                    finish(&state);
+                    let mut state = walk::state(*state);
+                    let step = Walker::stepper(&mut state);
+                    Stage::Walk(Box::new(state), step)
+                }, 
+            }, 
+            Stage::Walk(mut state, mut step) => match step(&mut state) {
+                Signal::Continue => {
+                    state.step();
+                    Stage::Walk(state, step)
+                }, 
+                Signal::Done => {
+                    finish(&state);
+                    Stage::Done(state)
+                }, 
+            }, 
+            Stage::Done(state) => Stage::Done(state), 
+        }
+    }
+}
+
+/// Reports that the phase of a state finished. 
+fn finish<T: Phase>(state: &state::State<T>) {
+    state.emit(Event::PhaseFinished(T::NAME));
+}
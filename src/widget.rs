@@ -0,0 +1,75 @@
+use std::io;
+use ratatui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
+use crate::{
+    colour::{Colour, Lut, Swatch}, 
+    render::Renderer, 
+    state::{CellOverlay, Phase, State}, 
+};
+
+/// Widget drawing a state into a ratatui [`Buffer`], such that the animation may be embedded in a pane of a
+/// TUI application. 
+///
+/// The application keeps driving the algorithm (e.g. via [`Animation::stepper`](crate::Animation::stepper))
+/// and renders the widget each frame. Cells are two columns wide as in the terminal, and cells outside the
+/// area are clipped. 
+pub struct MazeWidget<'a, T> {
+    state: &'a State<T>, 
+}
+
+impl<'a, T: Phase> MazeWidget<'a, T> {
+    pub fn new(state: &'a State<T>) -> MazeWidget<'a, T> {
+        MazeWidget{ state }
+    }
+}
+
+impl<T: Phase> Widget for MazeWidget<'_, T> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.state.draw(&mut Pane{ buf, area });
+    }
+}
+
+/// Renders into an area of a buffer. 
+struct Pane<'a> {
+    buf: &'a mut Buffer, 
+    area: Rect, 
+}
+
+impl Renderer for Pane<'_> {
+    fn size(&self) -> Option<(usize, usize)> {
+        Some((self.area.width as usize / 2, self.area.height as usize))
+    }
+
+    fn draw_cell(&mut self, x: usize, y: usize, swatch: Swatch, overlay: Option<CellOverlay>, colours: &Lut) {
+        let (columns, rows) = (self.area.width as usize, self.area.height as usize);
+        if 2 * x + 1 >= columns || y >= rows {
+            return
+        }
+        let background = colours.colour(swatch).into();
+        let (column, row) = (self.area.x + 2 * x as u16, self.area.y + y as u16);
+
+        for (i, cell) in [(column, row), (column + 1, row)].into_iter().enumerate() {
+            let cell = &mut self.buf[cell];
+            cell.reset();
+            cell.set_bg(background);
+
+            if let (0, Some(overlay)) = (i, overlay) {
+                cell.set_char(overlay.glyph);
+                cell.set_fg(overlay.foreground().into());
+            }
+        }
+    }
+
+    fn present(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl From<Colour> for Color {
+    fn from(colour: Colour) -> Color {
+        match colour {
+            Colour::Reset => Color::Reset, 
+            Colour::Indexed(i) => Color::Indexed(i), 
+            Colour::Rgb{ r, g, b } => Color::Rgb(r, g, b), 
+        }
+    }
+}